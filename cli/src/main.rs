@@ -16,6 +16,41 @@
 //!       --preserve-aria  [非推奨・無効] aria-* 属性を保持する
 //!       --drop-shell     nav/header/footer/aside を除外する
 //!       --unwrap-wrappers  意味を持たない div/span/section/article/main をアンラップする
+//!       --drop-hidden    hidden / aria-hidden / display:none などの非表示要素を除外する
+//!       --keep-hidden    非表示要素を除外しない
+//!       --keep-sr-only   sr-only などのスクリーンリーダー専用テキストは残す
+//!       --style-semantics / --no-style-semantics  span の style を強調として扱う／扱わない
+//!       --svg <POLICY>   インライン SVG: skip|alt-text|file|data-uri|raw
+//!       --data-uri <POLICY>  data: URI の画像: inline|file|alt-text|drop
+//!       --image-dimensions <MODE>  画像の幅・高さ: drop|attributes|html
+//!       --emoji <POLICY>  絵文字の画像: unicode|shortcode|image
+//!       --base-url <URL> 相対リンク・画像を解決する基準 URL
+//!       --url-resolution <MODE>  解決する相対参照: all|scheme-relative|path-relative|off
+//!       --clean-urls     リダイレクタを展開し、トラッキング用のパラメータを取り除く
+//!       --tracking-param <NAME>  --clean-urls で取り除くパラメータ（複数指定可）
+//!       --link-style <STYLE>  リンクの書き方: inline|reference|reference-per-section|footnote
+//!       --links <FILTER>  残すリンク: keep|external-only|text-only|drop
+//!       --strip-dead-links / --keep-dead-links  移動先のないリンクをテキストにする／しない
+//!       --autolink / --no-autolink  テキストが URL のリンクを <URL> にする／しない
+//!       --collect-assets  相対パスで参照されたローカルの画像を assets/ へ複製する
+//!       --profile <NAME> 入力の生成元: generic|office|email
+//!       --extract-main / --no-extract-main  本文を採点して抽出する／しない
+//!       --select <SEL>   一致する部分木だけを変換する（複数指定可）
+//!       --exclude <SEL>  一致する要素を除外する（複数指定可）
+//!       --skip-tag / --keep-tag / --shell-tag / --wrapper-tag / --block-tag <TAG>
+//!                        タグ分類を追加・上書きする（複数指定可）
+//!       --boilerplate <FRACTION>  複数ファイル変換で、この割合を超えるページに
+//!                        共通するブロックを取り除く
+//!       --near-duplicates <SIMILARITY>  複数ファイル変換で、変換結果が
+//!                        この類似度以上のファイルをまとめて報告する
+//!       --site           複数ファイル変換で、入力をひとつのサイトとして扱い、
+//!                        ディレクトリ構成を保ってページ間のリンクを .md に書き換える
+//!       --front-matter <FORMAT>  先頭に front matter を付ける: none|yaml|toml
+//!       --front-matter-field <FIELD[=KEY]>  front matter の項目（複数指定可）
+//!       --recover-noscript  他に表示内容がない <noscript> の中身を変換する
+//!       --skip-shadow-roots  宣言的 Shadow DOM を展開しない
+//!       --structured-data / --no-structured-data  JSON-LD の Recipe・FAQ で本文を補う／補わない
+//!       --hydration-payloads  SPA のハイドレーション用 JSON の本文を変換する
 //!   -h, --help           このヘルプを表示
 //! ```
 
@@ -29,7 +64,12 @@ use std::path::PathBuf;
 use std::process;
 use std::str::FromStr;
 
-use mdka::options::{ConversionMode, ConversionOptions};
+use mdka::BatchOptions;
+use mdka::options::{
+    ConversionMode, ConversionOptions, CssSelector, DataUriPolicy, EmojiPolicy, FrontMatterField,
+    FrontMatterFormat, ImageDimensions, LinkFilter, LinkStyle, SourceProfile, SvgPolicy, TagRules,
+    UrlResolution,
+};
 
 const USAGE: &str = "\
 Usage:
@@ -44,6 +84,90 @@ Options:
       --preserve-aria     [非推奨・無効] aria-* 属性を保持する（同上）
       --drop-shell        nav/header/footer/aside を除外する
       --unwrap-wrappers   意味を持たない div/span/section/article/main をアンラップする
+      --drop-hidden       hidden / aria-hidden / display:none / sr-only などの非表示要素を除外する
+                          （balanced・minimal・semantic の既定）
+      --keep-hidden       非表示要素を除外しない（strict・preserve の既定）
+      --keep-sr-only      非表示要素を除外する場合も sr-only などのテキストは残す（semantic の既定）
+      --style-semantics   <span style> の太字・斜体・取り消し線・等幅を ** * ~~ ` で出力する
+                          （minimal 以外の既定）
+      --no-style-semantics  <span style> を強調として扱わない
+      --svg <POLICY>      インライン SVG の扱い: alt-text(既定) | skip | file | data-uri | raw
+                          （preserve モードの既定は raw。file は出力ディレクトリの assets/ へ書き出す）
+      --data-uri <POLICY>  data: URI の画像の扱い: inline(既定) | file | alt-text | drop
+                          （minimal モードの既定は alt-text。file は復号して assets/ へ書き出す）
+      --image-dimensions <MODE>  画像の width / height: drop(既定) | attributes | html
+                          （attributes は {width=100 height=50}、html は <img> タグで残す。
+                           preserve モードの既定は html）
+      --emoji <POLICY>    絵文字の画像（emoji クラス・絵文字 CDN・<g-emoji>）の扱い:
+                          unicode(既定) | shortcode | image（preserve モードの既定は image）
+      --base-url <URL>    相対リンク・画像の URL をこの URL（文書に <base href> があれば、
+                          それをこの URL に対して解決したもの）を基準に絶対 URL にする
+      --url-resolution <MODE>  解決する相対参照: all(既定) | scheme-relative | path-relative | off
+                          （scheme-relative は //host/… だけ、path-relative は /a や a だけ。
+                           off は <base href> も無視する）
+      --clean-urls        リンク・画像の URL を整理する: Google・Facebook・Outlook safelinks
+                          などのリダイレクタを展開し、utm_* や fbclid などのトラッキング用の
+                          パラメータを取り除き、パーセントエンコーディングを正規化する
+      --tracking-param <NAME>  --clean-urls で取り除くクエリパラメータ（複数指定可。末尾の *
+                          は前方一致。指定すると既定の一覧の代わりに使う）
+      --link-style <STYLE>  リンクの書き方: inline(既定) | reference | reference-per-section |
+                          footnote（reference は末尾に [1]: URL の定義をまとめる。
+                          reference-per-section は h1 / h2 の節ごとに定義を置く。
+                          同じ URL は 1 つの定義を共有する）
+      --links <FILTER>    残すリンク: keep(既定) | external-only | text-only | drop
+                          （external-only は他のホストへのリンクだけを残し、他はテキストに。
+                           text-only は URL を書かない。drop はテキストごと取り除く。
+                           minimal モードの既定は external-only）
+      --strip-dead-links  href のない・空の・# だけの・javascript: のリンクをテキストにする
+                          （minimal モードの既定）
+      --keep-dead-links   移動先のないリンクもそのままリンクとして書く
+      --autolink          テキストが URL と同じリンクを <https://…> にする（minimal モードの既定）
+      --no-autolink       自動リンクを使わない
+      --collect-assets    相対パスで参照されたローカルの画像を出力ディレクトリの assets/ へ
+                          複製し、参照を書き換える（同じ内容のファイルは 1 つにまとめる）
+      --recover-noscript  同じ親に他の表示内容がない <noscript> の中身を変換する
+                          （遅延読み込み画像の代替など。既定では変換しない）
+      --skip-shadow-roots <template shadowrootmode> の中身を展開しない
+      --structured-data   JSON-LD の Recipe・FAQPage が表示内容より詳しければ本文の末尾に書き足す
+                          （見出しは英語固定。既定では書き足さない）
+      --no-structured-data  構造化データで本文を補わない
+      --hydration-payloads  __NEXT_DATA__ や window.__NUXT__ などの JSON に入った
+                          HTML・リッチテキストを変換する（本文が空のシェルなら置き換え、
+                          そうでなければ本文にない内容を末尾に書き足す）
+      --profile <NAME>    入力 HTML の生成元: generic(既定) | office | email
+                          （office: Word / Outlook / Google Docs の書き出しの癖を取り除く。
+                           email: レイアウト表・トラッキング画像・プレヘッダーを取り除く）
+      --extract-main      本文らしいコンテナを採点して選び、その中身だけを変換する
+                          （サイドバー・コメント・関連記事・共有ボタンも除く）
+      --no-extract-main   本文抽出を行わない
+      --select <SEL>      CSS セレクタに一致する部分木だけを文書順に変換する（複数指定可）
+      --exclude <SEL>     CSS セレクタに一致する要素を中身ごと除外する（複数指定可）
+      --skip-tag <TAG>    中身ごとスキップするタグを追加する（例: form）
+      --keep-tag <TAG>    既定でスキップするタグを変換対象に戻す（例: iframe）
+      --shell-tag <TAG>   --drop-shell で除外するシェル要素を追加する
+      --wrapper-tag <TAG> --unwrap-wrappers でアンラップするラッパーを追加する
+      --block-tag <TAG>   div と同じブロックとして扱うタグを追加する（例: my-card）
+                          （タグ指定はいずれも複数指定可）
+      --front-matter <FORMAT>
+                          タイトルなどのメタデータを front matter として先頭に付ける:
+                          none(既定) | yaml (---) | toml (+++)
+      --front-matter-field <FIELD[=KEY]>
+                          front matter の項目を順に指定する（複数指定可。既定は
+                          title, description, canonical_url, date, tags, source）。
+                          項目: title | description | canonical_url | date | modified |
+                          author | lang | site_name | image | tags | source
+                          =KEY でキー名を変える（例: date=pubDate）
+      --boilerplate <FRACTION>
+                          複数ファイル変換で、ページのこの割合（0〜1）を超える数に現れる
+                          ブロック（クッキーバナー・フッターなど）を取り除き、標準エラーに報告する
+      --near-duplicates <SIMILARITY>
+                          複数ファイル変換で、変換結果の類似度（0〜1、SimHash）がこの値以上の
+                          ファイルを近似重複として標準エラーに報告する（出力は削除しない）。
+                          無関係なページでも 0.5 前後になるため 0.9 程度を目安にする
+      --site              複数ファイル変換で、入力をひとつのサイトとして扱う。入力の共通の
+                          親ディレクトリからの構成を保って書き出し、ページ間のリンクと
+                          #fragment を出力の .md と見出しのアンカーに書き換える。
+                          見つからないリンク先は標準エラーに報告する
   -h, --help              このヘルプを表示
 
 モード説明:
@@ -63,6 +187,14 @@ Examples:
   mdka -o out/ index.html                 # → out/index.md
   mdka --mode minimal --drop-shell *.html # nav/header/footer を除外
   mdka --mode preserve -o archive/ *.html # 最大限の情報を保持
+  mdka --profile office report.html       # Word から保存した HTML
+  mdka --profile email -o mail/ *.html    # HTML メールのアーカイブ
+  mdka --select article --exclude '.share, .ad' page.html  # 本文だけ
+  mdka --front-matter yaml --front-matter-field title \
+       --front-matter-field date=pubDate -o content/ *.html  # Astro 向け
+  mdka --boilerplate 0.5 -o out/ site/*.html  # サイト共通のブロックを除去
+  mdka --near-duplicates 0.9 -o out/ mirror/*.html  # ほぼ同じページを報告
+  mdka --site -o docs/ site/**/*.html   # リンクを保ったままサイトを変換
 ";
 
 fn main() {
@@ -82,6 +214,34 @@ fn main() {
     let mut preserve_aria_override: Option<bool> = None;
     let mut drop_shell = false;
     let mut unwrap_wrappers = false;
+    let mut drop_hidden: Option<bool> = None;
+    let mut keep_sr_only = false;
+    let mut style_semantics: Option<bool> = None;
+    let mut svg_policy: Option<SvgPolicy> = None;
+    let mut data_uri_policy: Option<DataUriPolicy> = None;
+    let mut image_dimensions: Option<ImageDimensions> = None;
+    let mut emoji_policy: Option<EmojiPolicy> = None;
+    let mut base_url: Option<String> = None;
+    let mut url_resolution: Option<UrlResolution> = None;
+    let mut link_style: Option<LinkStyle> = None;
+    let mut clean_urls = false;
+    let mut tracking_params: Vec<String> = Vec::new();
+    let mut link_filter: Option<LinkFilter> = None;
+    let mut strip_dead_links: Option<bool> = None;
+    let mut autolink: Option<bool> = None;
+    let mut collect_assets = false;
+    let mut recover_noscript = false;
+    let mut skip_shadow_roots = false;
+    let mut structured_data: Option<bool> = None;
+    let mut hydration_payloads = false;
+    let mut profile: Option<SourceProfile> = None;
+    let mut extract_main: Option<bool> = None;
+    let mut include_selectors: Vec<CssSelector> = Vec::new();
+    let mut exclude_selectors: Vec<CssSelector> = Vec::new();
+    let mut tag_rules = TagRules::default();
+    let mut batch = BatchOptions::default();
+    let mut front_matter: Option<FrontMatterFormat> = None;
+    let mut front_matter_fields: Vec<FrontMatterField> = Vec::new();
     let mut file_args: Vec<String> = Vec::new();
 
     let mut iter = args.into_iter().peekable();
@@ -109,6 +269,156 @@ fn main() {
             "--preserve-aria" => preserve_aria_override = Some(true),
             "--drop-shell" => drop_shell = true,
             "--unwrap-wrappers" => unwrap_wrappers = true,
+            "--drop-hidden" => drop_hidden = Some(true),
+            "--keep-hidden" => drop_hidden = Some(false),
+            "--keep-sr-only" => keep_sr_only = true,
+            "--style-semantics" => style_semantics = Some(true),
+            "--no-style-semantics" => style_semantics = Some(false),
+            "--recover-noscript" => recover_noscript = true,
+            "--extract-main" => extract_main = Some(true),
+            "--no-extract-main" => extract_main = Some(false),
+            "--skip-shadow-roots" => skip_shadow_roots = true,
+            "--structured-data" => structured_data = Some(true),
+            "--no-structured-data" => structured_data = Some(false),
+            "--hydration-payloads" => hydration_payloads = true,
+            "--svg" => {
+                let p = iter.next().unwrap_or_default();
+                svg_policy = Some(SvgPolicy::from_str(&p).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: skip|alt-text|file|data-uri|raw");
+                    process::exit(1);
+                }));
+            }
+            "--data-uri" => {
+                let p = iter.next().unwrap_or_default();
+                data_uri_policy = Some(DataUriPolicy::from_str(&p).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: inline|file|alt-text|drop");
+                    process::exit(1);
+                }));
+            }
+            "--image-dimensions" => {
+                let d = iter.next().unwrap_or_default();
+                image_dimensions = Some(ImageDimensions::from_str(&d).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: drop|attributes|html");
+                    process::exit(1);
+                }));
+            }
+            "--emoji" => {
+                let p = iter.next().unwrap_or_default();
+                emoji_policy = Some(EmojiPolicy::from_str(&p).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: unicode|shortcode|image");
+                    process::exit(1);
+                }));
+            }
+            "--base-url" => {
+                base_url = Some(iter.next().unwrap_or_else(|| {
+                    eprintln!("error: --base-url requires a URL");
+                    process::exit(1);
+                }));
+            }
+            "--url-resolution" => {
+                let r = iter.next().unwrap_or_default();
+                url_resolution = Some(UrlResolution::from_str(&r).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: all|scheme-relative|path-relative|off");
+                    process::exit(1);
+                }));
+            }
+            "--clean-urls" => clean_urls = true,
+            "--tracking-param" => {
+                tracking_params.push(iter.next().unwrap_or_else(|| {
+                    eprintln!("error: --tracking-param requires a parameter name");
+                    process::exit(1);
+                }));
+            }
+            "--link-style" => {
+                let s = iter.next().unwrap_or_default();
+                link_style = Some(LinkStyle::from_str(&s).unwrap_or_else(|err| {
+                    eprintln!(
+                        "error: {err}. Valid: inline|reference|reference-per-section|footnote"
+                    );
+                    process::exit(1);
+                }));
+            }
+            "--links" => {
+                let f = iter.next().unwrap_or_default();
+                link_filter = Some(LinkFilter::from_str(&f).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: keep|external-only|text-only|drop");
+                    process::exit(1);
+                }));
+            }
+            "--strip-dead-links" => strip_dead_links = Some(true),
+            "--keep-dead-links" => strip_dead_links = Some(false),
+            "--autolink" => autolink = Some(true),
+            "--no-autolink" => autolink = Some(false),
+            "--collect-assets" => collect_assets = true,
+            "--profile" => {
+                let p = iter.next().unwrap_or_default();
+                profile = Some(SourceProfile::from_str(&p).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: generic|office|email");
+                    process::exit(1);
+                }));
+            }
+            "--select" | "--exclude" => {
+                let raw = iter.next().unwrap_or_else(|| {
+                    eprintln!("error: {arg} requires a CSS selector");
+                    process::exit(1);
+                });
+                let selector = CssSelector::parse(&raw).unwrap_or_else(|err| {
+                    eprintln!("error: {err}");
+                    process::exit(1);
+                });
+                if arg == "--select" {
+                    include_selectors.push(selector);
+                } else {
+                    exclude_selectors.push(selector);
+                }
+            }
+            "--skip-tag" | "--keep-tag" | "--shell-tag" | "--wrapper-tag" | "--block-tag" => {
+                let tag = iter.next().unwrap_or_else(|| {
+                    eprintln!("error: {arg} requires a tag name");
+                    process::exit(1);
+                });
+                tag_rules = match arg.as_str() {
+                    "--skip-tag" => tag_rules.skip_tag(tag),
+                    "--keep-tag" => tag_rules.keep_tag(tag),
+                    "--shell-tag" => tag_rules.shell_tag(tag),
+                    "--wrapper-tag" => tag_rules.wrapper_tag(tag),
+                    _ => tag_rules.block_tag(tag),
+                };
+            }
+            "--front-matter" => {
+                let f = iter.next().unwrap_or_default();
+                front_matter = Some(FrontMatterFormat::from_str(&f).unwrap_or_else(|err| {
+                    eprintln!("error: {err}. Valid: none|yaml|toml");
+                    process::exit(1);
+                }));
+            }
+            "--front-matter-field" => {
+                let spec = iter.next().unwrap_or_default();
+                front_matter_fields.push(FrontMatterField::from_str(&spec).unwrap_or_else(|err| {
+                    eprintln!(
+                        "error: {err}. Valid: title|description|canonical_url|date|modified|\
+                             author|lang|site_name|image|tags|source, optionally =KEY"
+                    );
+                    process::exit(1);
+                }));
+            }
+            "--site" => batch = batch.site_links(true),
+            "--boilerplate" | "--near-duplicates" => {
+                let raw = iter.next().unwrap_or_default();
+                let fraction = raw
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|f| (0.0..=1.0).contains(f))
+                    .unwrap_or_else(|| {
+                        eprintln!("error: {arg} requires a number between 0 and 1");
+                        process::exit(1);
+                    });
+                batch = if arg == "--boilerplate" {
+                    batch.boilerplate_threshold(fraction)
+                } else {
+                    batch.near_duplicate_threshold(fraction)
+                };
+            }
             _ => file_args.push(arg),
         }
     }
@@ -139,6 +449,85 @@ fn main() {
     if unwrap_wrappers {
         opts.unwrap_unknown_wrappers = true;
     }
+    if let Some(v) = drop_hidden {
+        opts.drop_hidden_content = v;
+    }
+    if keep_sr_only {
+        opts.keep_screen_reader_text = true;
+    }
+    if let Some(v) = style_semantics {
+        opts.inline_style_semantics = v;
+    }
+    if let Some(p) = svg_policy {
+        opts.svg_policy = p;
+    }
+    if let Some(p) = data_uri_policy {
+        opts.data_uri_policy = p;
+    }
+    if let Some(d) = image_dimensions {
+        opts.image_dimensions = d;
+    }
+    if let Some(p) = emoji_policy {
+        opts.emoji_policy = p;
+    }
+    if base_url.is_some() {
+        opts.base_url = base_url;
+    }
+    if let Some(r) = url_resolution {
+        opts.url_resolution = r;
+    }
+    if clean_urls {
+        opts.clean_urls = true;
+    }
+    if !tracking_params.is_empty() {
+        opts.tracking_params = tracking_params;
+    }
+    if let Some(s) = link_style {
+        opts.link_style = s;
+    }
+    if let Some(f) = link_filter {
+        opts.link_policy.filter = f;
+    }
+    if let Some(v) = strip_dead_links {
+        opts.link_policy.strip_dead = v;
+    }
+    if let Some(v) = autolink {
+        opts.link_policy.autolink = v;
+    }
+    if collect_assets {
+        opts.collect_local_assets = true;
+    }
+    if recover_noscript {
+        opts.recover_noscript = true;
+    }
+    if skip_shadow_roots {
+        opts.expand_shadow_roots = false;
+    }
+    if let Some(v) = structured_data {
+        opts.render_structured_data = v;
+    }
+    if hydration_payloads {
+        opts.recover_hydration_payloads = true;
+    }
+    if let Some(p) = profile {
+        opts.profile = p;
+    }
+    if let Some(v) = extract_main {
+        opts.extract_main_content = v;
+    }
+    if !include_selectors.is_empty() {
+        opts.include_selectors = include_selectors;
+    }
+    if !exclude_selectors.is_empty() {
+        opts.exclude_selectors = exclude_selectors;
+    }
+    opts.tag_rules = tag_rules;
+    if let Some(f) = front_matter {
+        opts.front_matter = f;
+    }
+    if !front_matter_fields.is_empty() {
+        opts.front_matter_fields = front_matter_fields;
+    }
 
     // ── 実行分岐 ──────────────────────────────────────────────────────
     match (file_args.is_empty(), file_args.len(), &out_dir) {
@@ -172,9 +561,35 @@ fn main() {
                 process::exit(1);
             }
             let paths: Vec<PathBuf> = file_args.iter().map(PathBuf::from).collect();
-            let results = mdka::html_files_to_markdown_with(&paths, dir, &opts);
+            let report = mdka::html_files_to_markdown_batch(&paths, dir, &opts, &batch);
+            for block in &report.boilerplate {
+                let first_line = block.markdown.lines().next().unwrap_or_default();
+                eprintln!(
+                    "boilerplate: dropped from {} pages: {first_line}",
+                    block.pages
+                );
+            }
+            for group in &report.duplicates {
+                let names: Vec<String> = group
+                    .iter()
+                    .map(|&i| report.files[i].0.display().to_string())
+                    .collect();
+                eprintln!("near-duplicates: {}", names.join(", "));
+            }
+            for link in &report.dangling_links {
+                let what = if link.missing_fragment {
+                    "missing anchor"
+                } else {
+                    "dangling link"
+                };
+                eprintln!(
+                    "{what}: {}: {}",
+                    report.files[link.file].0.display(),
+                    link.href
+                );
+            }
             let mut had_error = false;
-            for (src, res) in results {
+            for (src, res) in report.files {
                 match res {
                     Ok(dest) => println!("{} -> {}", src.display(), dest.display()),
                     Err(e) => {
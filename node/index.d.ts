@@ -1,5 +1,35 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/** バッチ変換の結果。 */
+export interface BatchReport {
+  /** 入力ごとの結果。`paths` と同じ順序。 */
+  files: Array<ConvertResult>
+  /** 取り除いた定型ブロック。現れたページの多い順。 */
+  boilerplate: Array<BoilerplateBlock>
+  /** 近似重複のグループ。`files` の添字を昇順に並べたもの。 */
+  duplicates: Array<Array<number>>
+  /** サイト変換で見つからなかったリンク先。 */
+  danglingLinks: Array<DanglingLink>
+}
+
+/** 定型文として取り除いたブロック。 */
+export interface BoilerplateBlock {
+  /** 取り除いたブロックの Markdown。 */
+  markdown: string
+  /** このブロックが現れたページの数。 */
+  pages: number
+}
+
+/** サイト変換で入力の中に見つからなかったリンク先。 */
+export interface DanglingLink {
+  /** リンクを含む入力の `files` の添字。 */
+  file: number
+  /** 元の `href`。 */
+  href: string
+  /** ページはあるが `#fragment` が見つからない。 */
+  missingFragment: boolean
+}
+
 /** ファイル変換の結果。 */
 export interface ConvertResult {
   /** 変換した入力ファイルのパス。 */
@@ -10,8 +40,48 @@ export interface ConvertResult {
   error?: string
 }
 
+/** 文書メタデータ。見つからない項目は undefined。 */
+export interface DocumentMetadata {
+  /** `<title>` → `og:title` → `twitter:title` */
+  title?: string
+  /** `<meta name="description">` → `og:description` → `twitter:description` */
+  description?: string
+  /** `<link rel="canonical">` → `og:url` */
+  canonicalUrl?: string
+  /** `<html lang>` → `content-language` → `og:locale` */
+  lang?: string
+  /** `<meta name="author">` → `article:author` → `twitter:creator` */
+  author?: string
+  /** `article:published_time` → `<meta name="date">` など */
+  published?: string
+  /** `article:modified_time` → `og:updated_time` など */
+  modified?: string
+  /** `og:site_name` */
+  siteName?: string
+  /** `og:image` → `twitter:image` */
+  image?: string
+  /** `<meta name="keywords">` をカンマで分けたもの */
+  keywords: Array<string>
+  /** `og:*` / `article:*` のすべて（文書順） */
+  openGraph: Array<MetaProperty>
+  /** `twitter:*` のすべて（文書順） */
+  twitter: Array<MetaProperty>
+  /** JSON-LD・microdata・microformats */
+  structuredData: StructuredData
+}
+
 export declare function htmlFilesToMarkdown(paths: Array<string>, outDir: string): Promise<Array<ConvertResult>>
 
+/**
+ * 複数の HTML ファイルを文書横断の処理付きで変換する。
+ *
+ * ```js
+ * const report = await htmlFilesToMarkdownBatch(paths, 'out/', {}, { boilerplateThreshold: 0.5 })
+ * for (const b of report.boilerplate) console.log(b.pages, b.markdown)
+ * ```
+ */
+export declare function htmlFilesToMarkdownBatch(paths: Array<string>, outDir: string, options?: JsConversionOptions | undefined | null, batchOptions?: JsBatchOptions | undefined | null): Promise<BatchReport>
+
 export declare function htmlFilesToMarkdownWith(paths: Array<string>, outDir: string, options?: JsConversionOptions | undefined | null): Promise<Array<ConvertResult>>
 
 /**
@@ -37,10 +107,33 @@ export declare function htmlToMarkdown(html: string): string
 
 export declare function htmlToMarkdownAsync(html: string): Promise<string>
 
+/**
+ * HTML を Markdown に変換し、タイトルなどのメタデータも返す。
+ *
+ * ```js
+ * const { markdown, metadata } = htmlToMarkdownDocument(html, { mode: 'minimal' })
+ * console.log(metadata.title, metadata.canonicalUrl)
+ * ```
+ */
+export declare function htmlToMarkdownDocument(html: string, options?: JsConversionOptions | undefined | null): MarkdownDocument
+
 export declare function htmlToMarkdownWith(html: string, options?: JsConversionOptions | undefined | null): string
 
 export declare function htmlToMarkdownWithAsync(html: string, options?: JsConversionOptions | undefined | null): Promise<string>
 
+/** バッチ変換のオプション。 */
+export interface JsBatchOptions {
+  /** ページのこの割合（0〜1）を超える数に現れるブロックを定型文として取り除く。 */
+  boilerplateThreshold?: number
+  /** 変換結果の類似度（0〜1、SimHash）がこの値以上のファイルを近似重複としてまとめる。 */
+  nearDuplicateThreshold?: number
+  /**
+   * 入力をひとつのサイトとして扱い、ディレクトリ構成を保って書き出し、
+   * 入力同士のリンクを出力の `.md` に書き換える。
+   */
+  siteLinks?: boolean
+}
+
 export interface JsConversionOptions {
   /** "balanced" | "strict" | "minimal" | "semantic" | "preserve" */
   mode?: string
@@ -49,7 +142,98 @@ export interface JsConversionOptions {
   preserveDataAttrs?: boolean
   preserveAriaAttrs?: boolean
   dropInteractiveShell?: boolean
+  dropHiddenContent?: boolean
+  keepScreenReaderText?: boolean
   unwrapUnknownWrappers?: boolean
+  inlineStyleSemantics?: boolean
+  recoverNoscript?: boolean
+  expandShadowRoots?: boolean
+  /** Append Recipe / FAQPage JSON-LD that is richer than the visible HTML. */
+  renderStructuredData?: boolean
+  /** Convert HTML / rich text from SPA hydration JSON when the body is empty. */
+  recoverHydrationPayloads?: boolean
+  /** "skip" | "alt-text" | "file" | "data-uri" | "raw" */
+  svgPolicy?: string
+  /** "inline" | "file" | "alt-text" | "drop" */
+  dataUriPolicy?: string
+  /** "drop" | "attributes" | "html" */
+  imageDimensions?: string
+  /** "unicode" | "shortcode" | "image" */
+  emojiPolicy?: string
+  /** "generic" | "office" | "email" */
+  profile?: string
+  /**
+   * URL of the source page; relative links and images are resolved
+   * against it (or against the document's `<base href>`).
+   */
+  baseUrl?: string
+  /** "all" | "scheme-relative" | "path-relative" | "off" */
+  urlResolution?: string
+  /**
+   * Unwrap redirectors, strip tracking parameters and normalise
+   * percent-encoding in link and image URLs.
+   */
+  cleanUrls?: boolean
+  /**
+   * Query parameters removed by `cleanUrls` (`*` suffix = prefix match);
+   * replaces the built-in list.
+   */
+  trackingParams?: Array<string>
+  /** "inline" | "reference" | "reference-per-section" | "footnote" */
+  linkStyle?: string
+  /** "keep" | "external-only" | "text-only" | "drop" */
+  linkFilter?: string
+  /** Write links without a destination (`javascript:`, empty, `#`) as text. */
+  stripDeadLinks?: boolean
+  /** Write links whose text is their URL as `<https://…>`. */
+  autolink?: boolean
+  /**
+   * File conversion only: copy images referenced by relative `src` into
+   * `assets/` next to the output and rewrite the references.
+   */
+  collectLocalAssets?: boolean
+  /** Convert only the highest-scoring main-content container. */
+  extractMainContent?: boolean
+  /** CSS selectors; only matching subtrees are converted. */
+  includeSelectors?: Array<string>
+  /** CSS selectors; matching elements are dropped with their content. */
+  excludeSelectors?: Array<string>
+  /** Tags to skip with their content, in addition to the built-in list. */
+  skipTags?: Array<string>
+  /** Built-in skipped tags to convert after all (e.g. "iframe"). */
+  keepTags?: Array<string>
+  /** Extra shell tags dropped by `dropInteractiveShell`. */
+  shellTags?: Array<string>
+  /** Extra wrapper tags unwrapped by `unwrapUnknownWrappers`. */
+  wrapperTags?: Array<string>
+  /** Tags rendered as block containers, like `div` (e.g. "my-card"). */
+  blockTags?: Array<string>
+  /** "none" | "yaml" | "toml" */
+  frontMatter?: string
+  /**
+   * Front matter fields in order, as "field" or "field=key"
+   * (e.g. "date=pubDate").
+   */
+  frontMatterFields?: Array<string>
+}
+
+/** `htmlToMarkdownDocument` の結果。 */
+export interface MarkdownDocument {
+  markdown: string
+  metadata: DocumentMetadata
+}
+
+/** `<meta>` の名前と値の組。 */
+export interface MetaProperty {
+  name: string
+  content: string
+}
+
+/** 文書に埋め込まれた構造化データ。各項目は JSON のオブジェクト。 */
+export interface StructuredData {
+  jsonLd: Array<any>
+  microdata: Array<any>
+  microformats: Array<any>
 }
 
 export declare function version(): string
@@ -577,11 +577,13 @@ if (!nativeBinding) {
 
 module.exports = nativeBinding
 module.exports.htmlFilesToMarkdown = nativeBinding.htmlFilesToMarkdown
+module.exports.htmlFilesToMarkdownBatch = nativeBinding.htmlFilesToMarkdownBatch
 module.exports.htmlFilesToMarkdownWith = nativeBinding.htmlFilesToMarkdownWith
 module.exports.htmlFileToMarkdown = nativeBinding.htmlFileToMarkdown
 module.exports.htmlFileToMarkdownWith = nativeBinding.htmlFileToMarkdownWith
 module.exports.htmlToMarkdown = nativeBinding.htmlToMarkdown
 module.exports.htmlToMarkdownAsync = nativeBinding.htmlToMarkdownAsync
+module.exports.htmlToMarkdownDocument = nativeBinding.htmlToMarkdownDocument
 module.exports.htmlToMarkdownWith = nativeBinding.htmlToMarkdownWith
 module.exports.htmlToMarkdownWithAsync = nativeBinding.htmlToMarkdownWithAsync
 module.exports.version = nativeBinding.version
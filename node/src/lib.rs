@@ -16,7 +16,69 @@ pub struct JsConversionOptions {
     pub preserve_data_attrs: Option<bool>,
     pub preserve_aria_attrs: Option<bool>,
     pub drop_interactive_shell: Option<bool>,
+    pub drop_hidden_content: Option<bool>,
+    pub keep_screen_reader_text: Option<bool>,
     pub unwrap_unknown_wrappers: Option<bool>,
+    pub inline_style_semantics: Option<bool>,
+    pub recover_noscript: Option<bool>,
+    pub expand_shadow_roots: Option<bool>,
+    /// Append Recipe / FAQPage JSON-LD that is richer than the visible HTML.
+    pub render_structured_data: Option<bool>,
+    /// Convert HTML / rich text from SPA hydration JSON when the body is empty.
+    pub recover_hydration_payloads: Option<bool>,
+    /// "skip" | "alt-text" | "file" | "data-uri" | "raw"
+    pub svg_policy: Option<String>,
+    /// "inline" | "file" | "alt-text" | "drop"
+    pub data_uri_policy: Option<String>,
+    /// "drop" | "attributes" | "html"
+    pub image_dimensions: Option<String>,
+    /// "unicode" | "shortcode" | "image"
+    pub emoji_policy: Option<String>,
+    /// "generic" | "office" | "email"
+    pub profile: Option<String>,
+    /// URL of the source page; relative links and images are resolved
+    /// against it (or against the document's `<base href>`).
+    pub base_url: Option<String>,
+    /// "all" | "scheme-relative" | "path-relative" | "off"
+    pub url_resolution: Option<String>,
+    /// Unwrap redirectors, strip tracking parameters and normalise
+    /// percent-encoding in link and image URLs.
+    pub clean_urls: Option<bool>,
+    /// Query parameters removed by `cleanUrls` (`*` suffix = prefix match);
+    /// replaces the built-in list.
+    pub tracking_params: Option<Vec<String>>,
+    /// "inline" | "reference" | "reference-per-section" | "footnote"
+    pub link_style: Option<String>,
+    /// "keep" | "external-only" | "text-only" | "drop"
+    pub link_filter: Option<String>,
+    /// Write links without a destination (`javascript:`, empty, `#`) as text.
+    pub strip_dead_links: Option<bool>,
+    /// Write links whose text is their URL as `<https://…>`.
+    pub autolink: Option<bool>,
+    /// File conversion only: copy images referenced by relative `src` into
+    /// `assets/` next to the output and rewrite the references.
+    pub collect_local_assets: Option<bool>,
+    /// Convert only the highest-scoring main-content container.
+    pub extract_main_content: Option<bool>,
+    /// CSS selectors; only matching subtrees are converted.
+    pub include_selectors: Option<Vec<String>>,
+    /// CSS selectors; matching elements are dropped with their content.
+    pub exclude_selectors: Option<Vec<String>>,
+    /// Tags to skip with their content, in addition to the built-in list.
+    pub skip_tags: Option<Vec<String>>,
+    /// Built-in skipped tags to convert after all (e.g. "iframe").
+    pub keep_tags: Option<Vec<String>>,
+    /// Extra shell tags dropped by `dropInteractiveShell`.
+    pub shell_tags: Option<Vec<String>>,
+    /// Extra wrapper tags unwrapped by `unwrapUnknownWrappers`.
+    pub wrapper_tags: Option<Vec<String>>,
+    /// Tags rendered as block containers, like `div` (e.g. "my-card").
+    pub block_tags: Option<Vec<String>>,
+    /// "none" | "yaml" | "toml"
+    pub front_matter: Option<String>,
+    /// Front matter fields in order, as "field" or "field=key"
+    /// (e.g. "date=pubDate").
+    pub front_matter_fields: Option<Vec<String>>,
 }
 
 /// `process.emitWarning(message, 'DeprecationWarning')` for a field that is
@@ -96,13 +158,117 @@ fn to_rust_opts(
     if let Some(v) = js.drop_interactive_shell {
         opts.drop_interactive_shell = v;
     }
+    if let Some(v) = js.drop_hidden_content {
+        opts.drop_hidden_content = v;
+    }
+    if let Some(v) = js.keep_screen_reader_text {
+        opts.keep_screen_reader_text = v;
+    }
     if let Some(v) = js.unwrap_unknown_wrappers {
         opts.unwrap_unknown_wrappers = v;
     }
+    if let Some(v) = js.inline_style_semantics {
+        opts.inline_style_semantics = v;
+    }
+    if let Some(v) = js.recover_noscript {
+        opts.recover_noscript = v;
+    }
+    if let Some(v) = js.expand_shadow_roots {
+        opts.expand_shadow_roots = v;
+    }
+    if let Some(v) = js.render_structured_data {
+        opts.render_structured_data = v;
+    }
+    if let Some(v) = js.recover_hydration_payloads {
+        opts.recover_hydration_payloads = v;
+    }
+    if let Some(v) = js.svg_policy.as_deref() {
+        opts.svg_policy = mdka::SvgPolicy::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.data_uri_policy.as_deref() {
+        opts.data_uri_policy = mdka::DataUriPolicy::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.image_dimensions.as_deref() {
+        opts.image_dimensions = mdka::ImageDimensions::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.emoji_policy.as_deref() {
+        opts.emoji_policy = mdka::EmojiPolicy::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.base_url {
+        opts.base_url = Some(v);
+    }
+    if let Some(v) = js.url_resolution.as_deref() {
+        opts.url_resolution = mdka::UrlResolution::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.clean_urls {
+        opts.clean_urls = v;
+    }
+    if let Some(v) = js.tracking_params {
+        opts.tracking_params = v;
+    }
+    if let Some(v) = js.link_style.as_deref() {
+        opts.link_style = mdka::LinkStyle::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.link_filter.as_deref() {
+        opts.link_policy.filter = mdka::LinkFilter::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.strip_dead_links {
+        opts.link_policy.strip_dead = v;
+    }
+    if let Some(v) = js.autolink {
+        opts.link_policy.autolink = v;
+    }
+    if let Some(v) = js.collect_local_assets {
+        opts.collect_local_assets = v;
+    }
+    if let Some(v) = js.profile.as_deref() {
+        opts.profile = mdka::SourceProfile::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.extract_main_content {
+        opts.extract_main_content = v;
+    }
+    if let Some(v) = js.include_selectors.as_deref() {
+        opts.include_selectors = parse_selectors(v)?;
+    }
+    if let Some(v) = js.exclude_selectors.as_deref() {
+        opts.exclude_selectors = parse_selectors(v)?;
+    }
+    if let Some(v) = js.skip_tags {
+        opts.tag_rules.skip = v;
+    }
+    if let Some(v) = js.keep_tags {
+        opts.tag_rules.keep = v;
+    }
+    if let Some(v) = js.shell_tags {
+        opts.tag_rules.shell = v;
+    }
+    if let Some(v) = js.wrapper_tags {
+        opts.tag_rules.wrapper = v;
+    }
+    if let Some(v) = js.block_tags {
+        opts.tag_rules.block = v;
+    }
+    if let Some(v) = js.front_matter.as_deref() {
+        opts.front_matter = mdka::FrontMatterFormat::from_str(v).map_err(Error::from_reason)?;
+    }
+    if let Some(v) = js.front_matter_fields {
+        opts.front_matter_fields = v
+            .iter()
+            .map(|f| mdka::FrontMatterField::from_str(f))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(Error::from_reason)?;
+    }
 
     Ok(opts)
 }
 
+fn parse_selectors(selectors: &[String]) -> Result<Vec<mdka::CssSelector>> {
+    selectors
+        .iter()
+        .map(|s| mdka::CssSelector::parse(s).map_err(|err| Error::from_reason(err.to_string())))
+        .collect()
+}
+
 // ─── 変換結果 ─────────────────────────────────────────────────────────────
 
 /// ファイル変換の結果。
@@ -116,6 +282,178 @@ pub struct ConvertResult {
     pub error: Option<String>,
 }
 
+/// バッチ変換のオプション。
+#[napi(object)]
+pub struct JsBatchOptions {
+    /// ページのこの割合（0〜1）を超える数に現れるブロックを定型文として取り除く。
+    pub boilerplate_threshold: Option<f64>,
+    /// 変換結果の類似度（0〜1、SimHash）がこの値以上のファイルを近似重複としてまとめる。
+    pub near_duplicate_threshold: Option<f64>,
+    /// 入力をひとつのサイトとして扱い、ディレクトリ構成を保って書き出し、
+    /// 入力同士のリンクを出力の `.md` に書き換える。
+    pub site_links: Option<bool>,
+}
+
+/// 定型文として取り除いたブロック。
+#[napi(object)]
+pub struct BoilerplateBlock {
+    /// 取り除いたブロックの Markdown。
+    pub markdown: String,
+    /// このブロックが現れたページの数。
+    pub pages: u32,
+}
+
+/// サイト変換で入力の中に見つからなかったリンク先。
+#[napi(object)]
+pub struct DanglingLink {
+    /// リンクを含む入力の `files` の添字。
+    pub file: u32,
+    /// 元の `href`。
+    pub href: String,
+    /// ページはあるが `#fragment` が見つからない。
+    pub missing_fragment: bool,
+}
+
+/// バッチ変換の結果。
+#[napi(object)]
+pub struct BatchReport {
+    /// 入力ごとの結果。`paths` と同じ順序。
+    pub files: Vec<ConvertResult>,
+    /// 取り除いた定型ブロック。現れたページの多い順。
+    pub boilerplate: Vec<BoilerplateBlock>,
+    /// 近似重複のグループ。`files` の添字を昇順に並べたもの。
+    pub duplicates: Vec<Vec<u32>>,
+    /// サイト変換で見つからなかったリンク先。
+    pub dangling_links: Vec<DanglingLink>,
+}
+
+fn to_convert_result(
+    p: &std::path::Path,
+    res: std::result::Result<std::path::PathBuf, mdka::MdkaError>,
+) -> ConvertResult {
+    match res {
+        Ok(dest) => ConvertResult {
+            src: p.to_string_lossy().into_owned(),
+            dest: Some(dest.to_string_lossy().into_owned()),
+            error: None,
+        },
+        Err(e) => ConvertResult {
+            src: p.to_string_lossy().into_owned(),
+            dest: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// `<meta>` の名前と値の組。
+#[napi(object)]
+pub struct MetaProperty {
+    pub name: String,
+    pub content: String,
+}
+
+/// 文書メタデータ。見つからない項目は undefined。
+#[napi(object)]
+pub struct DocumentMetadata {
+    /// `<title>` → `og:title` → `twitter:title`
+    pub title: Option<String>,
+    /// `<meta name="description">` → `og:description` → `twitter:description`
+    pub description: Option<String>,
+    /// `<link rel="canonical">` → `og:url`
+    pub canonical_url: Option<String>,
+    /// `<html lang>` → `content-language` → `og:locale`
+    pub lang: Option<String>,
+    /// `<meta name="author">` → `article:author` → `twitter:creator`
+    pub author: Option<String>,
+    /// `article:published_time` → `<meta name="date">` など
+    pub published: Option<String>,
+    /// `article:modified_time` → `og:updated_time` など
+    pub modified: Option<String>,
+    /// `og:site_name`
+    pub site_name: Option<String>,
+    /// `og:image` → `twitter:image`
+    pub image: Option<String>,
+    /// `<meta name="keywords">` をカンマで分けたもの
+    pub keywords: Vec<String>,
+    /// `og:*` / `article:*` のすべて（文書順）
+    pub open_graph: Vec<MetaProperty>,
+    /// `twitter:*` のすべて（文書順）
+    pub twitter: Vec<MetaProperty>,
+    /// JSON-LD・microdata・microformats
+    pub structured_data: StructuredData,
+}
+
+/// 文書に埋め込まれた構造化データ。各項目は JSON のオブジェクト。
+#[napi(object)]
+pub struct StructuredData {
+    pub json_ld: Vec<serde_json::Value>,
+    pub microdata: Vec<serde_json::Value>,
+    pub microformats: Vec<serde_json::Value>,
+}
+
+impl From<mdka::StructuredData> for StructuredData {
+    fn from(data: mdka::StructuredData) -> Self {
+        let values = |v: Vec<mdka::JsonValue>| v.into_iter().map(to_json).collect();
+        Self {
+            json_ld: values(data.json_ld),
+            microdata: values(data.microdata),
+            microformats: values(data.microformats),
+        }
+    }
+}
+
+fn to_json(value: mdka::JsonValue) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        mdka::JsonValue::Null => Value::Null,
+        mdka::JsonValue::Bool(b) => Value::Bool(b),
+        mdka::JsonValue::Number(n) => {
+            serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+        }
+        mdka::JsonValue::String(s) => Value::String(s),
+        mdka::JsonValue::Array(items) => Value::Array(items.into_iter().map(to_json).collect()),
+        mdka::JsonValue::Object(entries) => {
+            Value::Object(entries.into_iter().map(|(k, v)| (k, to_json(v))).collect())
+        }
+    }
+}
+
+/// `htmlToMarkdownDocument` の結果。
+#[napi(object)]
+pub struct MarkdownDocument {
+    pub markdown: String,
+    pub metadata: DocumentMetadata,
+}
+
+impl From<mdka::MarkdownDocument> for MarkdownDocument {
+    fn from(doc: mdka::MarkdownDocument) -> Self {
+        let props = |v: Vec<(String, String)>| {
+            v.into_iter()
+                .map(|(name, content)| MetaProperty { name, content })
+                .collect()
+        };
+        let m = doc.metadata;
+        Self {
+            markdown: doc.markdown,
+            metadata: DocumentMetadata {
+                title: m.title,
+                description: m.description,
+                canonical_url: m.canonical_url,
+                lang: m.lang,
+                author: m.author,
+                published: m.published,
+                modified: m.modified,
+                site_name: m.site_name,
+                image: m.image,
+                keywords: m.keywords,
+                open_graph: props(m.open_graph),
+                twitter: props(m.twitter),
+                structured_data: m.structured_data.into(),
+            },
+        }
+    }
+}
+
 // ─── 文字列変換 API ───────────────────────────────────────────────────────
 
 #[napi]
@@ -135,6 +473,22 @@ pub fn html_to_markdown_with(
     }
 }
 
+/// HTML を Markdown に変換し、タイトルなどのメタデータも返す。
+///
+/// ```js
+/// const { markdown, metadata } = htmlToMarkdownDocument(html, { mode: 'minimal' })
+/// console.log(metadata.title, metadata.canonicalUrl)
+/// ```
+#[napi]
+pub fn html_to_markdown_document(
+    html: String,
+    options: Option<JsConversionOptions>,
+    env: Env,
+) -> Result<MarkdownDocument> {
+    let opts = to_rust_opts(Some(&env), options)?;
+    Ok(mdka::html_to_markdown_document(&html, &opts).into())
+}
+
 #[napi]
 pub async fn html_to_markdown_async(html: String) -> Result<String> {
     tokio::task::spawn_blocking(move || mdka::html_to_markdown(&html))
@@ -221,18 +575,7 @@ pub async fn html_files_to_markdown_with(
         let raw = mdka::html_files_to_markdown_with(&path_bufs, out, &opts);
         Ok(raw
             .into_iter()
-            .map(|(p, res)| match res {
-                Ok(dest) => ConvertResult {
-                    src: p.to_string_lossy().into_owned(),
-                    dest: Some(dest.to_string_lossy().into_owned()),
-                    error: None,
-                },
-                Err(e) => ConvertResult {
-                    src: p.to_string_lossy().into_owned(),
-                    dest: None,
-                    error: Some(e.to_string()),
-                },
-            })
+            .map(|(p, res)| to_convert_result(p, res))
             .collect())
     })
     .await
@@ -240,6 +583,68 @@ pub async fn html_files_to_markdown_with(
     .map_err(Error::from_reason)
 }
 
+/// 複数の HTML ファイルを文書横断の処理付きで変換する。
+///
+/// ```js
+/// const report = await htmlFilesToMarkdownBatch(paths, 'out/', {}, { boilerplateThreshold: 0.5 })
+/// for (const b of report.boilerplate) console.log(b.pages, b.markdown)
+/// ```
+#[napi]
+pub async fn html_files_to_markdown_batch(
+    paths: Vec<String>,
+    out_dir: String,
+    options: Option<JsConversionOptions>,
+    batch_options: Option<JsBatchOptions>,
+) -> Result<BatchReport> {
+    let opts = to_rust_opts(None, options)?;
+    let mut batch = mdka::BatchOptions::default();
+    if let Some(b) = batch_options {
+        batch.boilerplate_threshold = b.boilerplate_threshold;
+        batch.near_duplicate_threshold = b.near_duplicate_threshold;
+        batch.site_links = b.site_links.unwrap_or(false);
+    }
+    tokio::task::spawn_blocking(move || -> std::result::Result<BatchReport, String> {
+        use std::path::Path;
+        let out = Path::new(&out_dir);
+        std::fs::create_dir_all(out).map_err(|e| format!("cannot create out_dir: {e}"))?;
+        let path_bufs: Vec<std::path::PathBuf> =
+            paths.iter().map(std::path::PathBuf::from).collect();
+        let report = mdka::html_files_to_markdown_batch(&path_bufs, out, &opts, &batch);
+        Ok(BatchReport {
+            files: report
+                .files
+                .into_iter()
+                .map(|(p, res)| to_convert_result(p, res))
+                .collect(),
+            boilerplate: report
+                .boilerplate
+                .into_iter()
+                .map(|b| BoilerplateBlock {
+                    markdown: b.markdown,
+                    pages: b.pages as u32,
+                })
+                .collect(),
+            duplicates: report
+                .duplicates
+                .into_iter()
+                .map(|g| g.into_iter().map(|i| i as u32).collect())
+                .collect(),
+            dangling_links: report
+                .dangling_links
+                .into_iter()
+                .map(|d| DanglingLink {
+                    file: d.file as u32,
+                    href: d.href,
+                    missing_fragment: d.missing_fragment,
+                })
+                .collect(),
+        })
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("task panicked: {e}")))?
+    .map_err(Error::from_reason)
+}
+
 // ─── バージョン ───────────────────────────────────────────────────────────
 
 #[napi]
@@ -195,7 +195,8 @@ async function run(name, fn) {
   // ── ConversionOptions / モード別テスト ───────────────────────────────────
   ; (async () => {
     const {
-      htmlToMarkdownWith, htmlToMarkdownWithAsync, htmlFilesToMarkdownWith
+      htmlToMarkdownWith, htmlToMarkdownWithAsync, htmlFilesToMarkdownWith,
+      htmlFilesToMarkdownBatch, htmlToMarkdownDocument
     } = require('./index')
     const fs = require('fs'), path = require('path'), os = require('os')
 
@@ -257,6 +258,77 @@ async function run(name, fn) {
       assert.strictEqual(withUnwrap, 'BeforeinnerAfter\n')
     })
 
+    await run('htmlToMarkdownWith: svgPolicy names icon links', () => {
+      const html = '<a href="/gh"><svg><title>GitHub</title><path d="M0"/></svg></a>'
+      assert.strictEqual(htmlToMarkdownWith(html, { svgPolicy: 'alt-text' }), '[GitHub](/gh)\n')
+      assert.strictEqual(htmlToMarkdownWith(html, { svgPolicy: 'skip' }), '[](/gh)\n')
+      assert.throws(() => htmlToMarkdownWith(html, { svgPolicy: 'bogus' }), /unknown svg policy/)
+    })
+
+    await run('htmlToMarkdownWith: recoverNoscript / expandShadowRoots', () => {
+      const noscript = '<div class="app"></div><noscript><p>Server text</p></noscript>'
+      assert.strictEqual(htmlToMarkdownWith(noscript, { recoverNoscript: true }), 'Server text\n')
+      assert.strictEqual(htmlToMarkdownWith(noscript, {}), '')
+      const shadow = '<x-card><template shadowrootmode="open"><p>Inside</p></template></x-card>'
+      assert.strictEqual(htmlToMarkdownWith(shadow, {}), 'Inside\n')
+      assert.strictEqual(htmlToMarkdownWith(shadow, { expandShadowRoots: false }), '')
+    })
+
+    await run('htmlToMarkdownWith: dropHiddenContent / keepScreenReaderText', () => {
+      const html = '<p>Body <span class="sr-only">(new tab)</span></p><div hidden>Modal</div>'
+      assert.strictEqual(htmlToMarkdownWith(html, {}), 'Body\n')
+      assert.strictEqual(htmlToMarkdownWith(html, { keepScreenReaderText: true }), 'Body (new tab)\n')
+      assert.strictEqual(htmlToMarkdownWith(html, { dropHiddenContent: false }), 'Body (new tab)\n\nModal\n')
+    })
+
+    await run('htmlToMarkdownWith: inlineStyleSemantics', () => {
+      const html = '<p><span style="font-weight:700">bold</span></p>'
+      assert.strictEqual(htmlToMarkdownWith(html, {}), '**bold**\n')
+      assert.strictEqual(htmlToMarkdownWith(html, { inlineStyleSemantics: false }), 'bold\n')
+    })
+
+    await run('htmlToMarkdownWith: profile office rebuilds Word lists', () => {
+      const html = '<p class="MsoListParagraph" style="mso-list:l0 level1 lfo1"><span style="mso-list:Ignore">·</span>One<o:p></o:p></p>'
+      assert.strictEqual(htmlToMarkdownWith(html, { profile: 'office' }), '- One\n')
+      assert.notStrictEqual(htmlToMarkdownWith(html, { profile: 'generic' }), '- One\n')
+      assert.throws(() => htmlToMarkdownWith(html, { profile: 'word' }), /unknown source profile/)
+    })
+
+    await run('htmlToMarkdownWith: profile email unwraps layout tables', () => {
+      const html = '<div class="preheader">Preview</div><table><tr><td>Hello</td></tr><tr><td>World<img src="t.gif" width="1" height="1"></td></tr></table>'
+      assert.strictEqual(htmlToMarkdownWith(html, { profile: 'email' }), 'Hello\n\nWorld\n')
+    })
+
+    await run('htmlToMarkdownWith: includeSelectors / excludeSelectors', () => {
+      const html = '<div class="ad">Buy</div><article><p>Lead</p><p class="share">Share</p></article>'
+      assert.strictEqual(htmlToMarkdownWith(html, { includeSelectors: ['article'] }), 'Lead\n\nShare\n')
+      assert.strictEqual(
+        htmlToMarkdownWith(html, { includeSelectors: ['article'], excludeSelectors: ['.share'] }),
+        'Lead\n',
+      )
+      assert.throws(() => htmlToMarkdownWith(html, { excludeSelectors: ['div['] }), /invalid selector/)
+    })
+
+    await run('htmlToMarkdownWith: tag classification overrides', () => {
+      const html = '<my-card>One</my-card><my-card>Two</my-card><form>Sign up</form><iframe>Fallback</iframe>'
+      assert.strictEqual(htmlToMarkdownWith(html, {}), 'OneTwoSign up\n')
+      assert.strictEqual(
+        htmlToMarkdownWith(html, { blockTags: ['my-card'], skipTags: ['form'], keepTags: ['iframe'] }),
+        'One\n\nTwo\n\nFallback\n',
+      )
+    })
+
+    await run('htmlToMarkdownWith: extractMainContent', () => {
+      const html = '<div class="sidebar"><p>Subscribe to the newsletter for weekly updates and more.</p></div>' +
+        '<article><h1>Title</h1><p>The article body is long enough to be scored, with commas, clauses, and detail.</p></article>'
+      const md = 'Subscribe to the newsletter for weekly updates and more.\n\n# Title\n\nThe article body is long enough to be scored, with commas, clauses, and detail.\n'
+      assert.strictEqual(htmlToMarkdownWith(html, {}), md)
+      assert.strictEqual(
+        htmlToMarkdownWith(html, { extractMainContent: true }),
+        '# Title\n\nThe article body is long enough to be scored, with commas, clauses, and detail.\n',
+      )
+    })
+
     // Both warning tests run in a fresh child process rather than sharing
     // this file's process: process.emitWarning delivers the 'warning' event
     // asynchronously (queued, not inline), and earlier tests in this same
@@ -314,6 +386,169 @@ async function run(name, fn) {
       fs.rmSync(tmp, { recursive: true })
     })
 
+    await run('htmlToMarkdownDocument: returns metadata', () => {
+      const doc = htmlToMarkdownDocument(
+        '<html lang="en"><head><title>Hello</title>' +
+        '<link rel="canonical" href="https://example.com/hello">' +
+        '<meta property="og:site_name" content="Site"></head>' +
+        '<body><nav>Menu</nav><p>Body</p></body></html>',
+        { dropInteractiveShell: true }
+      )
+      assert.equal(doc.markdown, 'Body\n')
+      assert.equal(doc.metadata.title, 'Hello')
+      assert.equal(doc.metadata.canonicalUrl, 'https://example.com/hello')
+      assert.equal(doc.metadata.lang, 'en')
+      assert.deepEqual(doc.metadata.openGraph, [{ name: 'og:site_name', content: 'Site' }])
+      assert.equal(doc.metadata.description, undefined)
+    })
+
+    await run('htmlToMarkdownWith: frontMatter', () => {
+      const html = '<head><title>Hi</title><meta property="article:published_time" content="2026-01-02">' +
+        '</head><p>Body</p>'
+      const md = htmlToMarkdownWith(html, {
+        frontMatter: 'toml', frontMatterFields: ['title', 'date=pubDate']
+      })
+      assert.equal(md, '+++\ntitle = "Hi"\npubDate = "2026-01-02"\n+++\n\nBody\n')
+      assert.throws(() => htmlToMarkdownWith(html, { frontMatterFields: ['nope'] }),
+        { message: 'unknown metadata field: nope' })
+    })
+
+    await run('htmlToMarkdownDocument: structuredData and renderStructuredData', () => {
+      const html = '<script type="application/ld+json">' +
+        '{"@type": "Recipe", "name": "Tea", "recipeIngredient": ["1 tea bag", "hot water"],' +
+        ' "recipeInstructions": "Steep.", "yield": 1}</script><p>Simple.</p>'
+      const doc = htmlToMarkdownDocument(html)
+      assert.deepEqual(doc.metadata.structuredData.jsonLd, [{
+        '@type': 'Recipe', name: 'Tea', recipeIngredient: ['1 tea bag', 'hot water'],
+        recipeInstructions: 'Steep.', yield: 1
+      }])
+      assert.deepEqual(doc.metadata.structuredData.microdata, [])
+      assert.equal(doc.metadata.title, 'Tea')
+      assert.equal(doc.markdown, 'Simple.\n')
+      const md = htmlToMarkdownWith(html, { renderStructuredData: true })
+      assert.ok(md.includes('- 1 tea bag'), md)
+    })
+
+    await run('htmlToMarkdownWith: recoverHydrationPayloads', () => {
+      const html = '<div id="__next"></div><script id="__NEXT_DATA__" type="application/json">' +
+        '{"props": {"pageProps": {"html": "<h2>Intro</h2><p>From JSON.</p>"}}}</script>'
+      assert.equal(htmlToMarkdownWith(html, { recoverHydrationPayloads: true }), '## Intro\n\nFrom JSON.\n')
+      assert.equal(htmlToMarkdownWith(html, { preserveIds: false }), '')
+    })
+
+    await run('htmlToMarkdownWith: baseUrl and urlResolution', () => {
+      const html = '<a href="../guide">Guide</a> <img src="//cdn.example/a.png" alt="A">'
+      assert.equal(
+        htmlToMarkdownWith(html, { baseUrl: 'https://example.com/docs/intro' }),
+        '[Guide](https://example.com/guide) ![A](https://cdn.example/a.png)\n'
+      )
+      assert.equal(
+        htmlToMarkdownWith(html, { baseUrl: 'https://example.com/docs/intro', urlResolution: 'scheme-relative' }),
+        '[Guide](../guide) ![A](https://cdn.example/a.png)\n'
+      )
+      assert.throws(() => htmlToMarkdownWith(html, { urlResolution: 'relative' }))
+    })
+
+    await run('htmlToMarkdownWith: linkStyle', () => {
+      const html = '<p><a href="/a">A</a> and <a href="/a">again</a></p>'
+      assert.equal(htmlToMarkdownWith(html, { linkStyle: 'reference' }), '[A][1] and [again][1]\n\n[1]: /a\n')
+      assert.equal(htmlToMarkdownWith(html, { linkStyle: 'footnote' }), 'A[^1] and again[^1]\n\n[^1]: /a\n')
+      assert.throws(() => htmlToMarkdownWith(html, { linkStyle: 'numbered' }))
+    })
+
+    await run('htmlToMarkdownWith: linkFilter, stripDeadLinks and autolink', () => {
+      const html = '<p><a href="/guide">Guide</a> <a href="https://example.com">https://example.com</a> <a href="#">Top</a></p>'
+      assert.equal(
+        htmlToMarkdownWith(html, { linkFilter: 'external-only', autolink: true }),
+        'Guide <https://example.com> Top\n'
+      )
+      assert.equal(
+        htmlToMarkdownWith(html, { linkFilter: 'text-only', stripDeadLinks: true }),
+        'Guide https://example.com Top\n'
+      )
+      assert.throws(() => htmlToMarkdownWith(html, { linkFilter: 'external' }))
+    })
+
+    await run('htmlToMarkdownWith: cleanUrls and trackingParams', () => {
+      const html = '<a href="https://www.google.com/url?q=https%3A%2F%2Fexample.com%2Fa%3Futm_source%3Dx%26id%3D1">A</a>'
+      assert.equal(htmlToMarkdownWith(html, { cleanUrls: true }), '[A](https://example.com/a?id=1)\n')
+      assert.equal(
+        htmlToMarkdownWith(html, { cleanUrls: true, trackingParams: ['id'] }),
+        '[A](https://example.com/a?utm_source=x)\n'
+      )
+    })
+
+    await run('htmlToMarkdownWith: dataUriPolicy', () => {
+      const html = '<p>A <img src="data:image/png;base64,Zm9v" alt="Chart"> B</p>'
+      assert.equal(htmlToMarkdownWith(html, { dataUriPolicy: 'alt-text' }), 'A Chart B\n')
+      assert.equal(htmlToMarkdownWith(html, { dataUriPolicy: 'drop' }), 'A B\n')
+      assert.throws(() => htmlToMarkdownWith(html, { dataUriPolicy: 'bogus' }), /unknown data uri policy/)
+    })
+
+    await run('htmlToMarkdownWith: imageDimensions', () => {
+      const html = '<img src="a.png" alt="A" width="100" height="50">'
+      assert.equal(htmlToMarkdownWith(html, {}), '![A](a.png)\n')
+      assert.equal(htmlToMarkdownWith(html, { imageDimensions: 'attributes' }), '![A](a.png){width=100 height=50}\n')
+      assert.equal(htmlToMarkdownWith(html, { imageDimensions: 'html' }), '<img src="a.png" alt="A" width="100" height="50">\n')
+      assert.throws(() => htmlToMarkdownWith(html, { imageDimensions: 'bogus' }), /unknown image dimensions/)
+    })
+
+    await run('htmlToMarkdownWith: emojiPolicy', () => {
+      const html = '<p>Hi <g-emoji alias="wave">👋</g-emoji> <img class="emoji" alt="😄" src="s.png"></p>'
+      assert.equal(htmlToMarkdownWith(html, {}), 'Hi 👋 😄\n')
+      assert.equal(htmlToMarkdownWith(html, { emojiPolicy: 'shortcode' }), 'Hi :wave: 😄\n')
+      assert.equal(htmlToMarkdownWith(html, { emojiPolicy: 'image' }), 'Hi 👋 ![😄](s.png)\n')
+      assert.throws(() => htmlToMarkdownWith(html, { emojiPolicy: 'bogus' }), /unknown emoji policy/)
+    })
+
+    await run('htmlFilesToMarkdownBatch: drops site-wide boilerplate', async () => {
+      const tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'mdka-batch-'))
+      const out = path.join(tmp, 'out')
+      const files = ['a', 'b', 'c'].map(name => {
+        const f = path.join(tmp, `${name}.html`)
+        fs.writeFileSync(f, `<p>We use cookies.</p><h1>${name}</h1>`)
+        return f
+      })
+      const report = await htmlFilesToMarkdownBatch(files, out, {}, { boilerplateThreshold: 0.5 })
+      assert.equal(report.files.length, 3)
+      assert.deepEqual(report.boilerplate, [{ markdown: 'We use cookies.', pages: 3 }])
+      const content = fs.readFileSync(report.files[0].dest, 'utf8')
+      assert.equal(content, '# a\n')
+      fs.rmSync(tmp, { recursive: true })
+    })
+
+    await run('htmlFilesToMarkdownBatch: groups near-duplicates', async () => {
+      const tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'mdka-dup-'))
+      const text = 'Download the archive from the releases page, unpack it into a directory ' +
+        'on your PATH, and run the binary once to create the default configuration file.'
+      const pages = [text, 'Boil the pasta in salted water and serve it warm with basil.', text]
+      const files = pages.map((body, i) => {
+        const f = path.join(tmp, `p${i}.html`)
+        fs.writeFileSync(f, `<p>${body}</p>`)
+        return f
+      })
+      const report = await htmlFilesToMarkdownBatch(files, path.join(tmp, 'out'), {}, {
+        nearDuplicateThreshold: 0.9
+      })
+      assert.deepEqual(report.duplicates, [[0, 2]])
+      fs.rmSync(tmp, { recursive: true })
+    })
+
+    await run('htmlFilesToMarkdownBatch: rewrites links between pages', async () => {
+      const tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'mdka-site-'))
+      fs.mkdirSync(path.join(tmp, 'guide'))
+      const index = path.join(tmp, 'index.html')
+      const intro = path.join(tmp, 'guide', 'intro.html')
+      fs.writeFileSync(index, '<h1>Home</h1><p><a href="guide/intro.html#setup">Setup</a> <a href="gone.html">Gone</a></p>')
+      fs.writeFileSync(intro, '<h1>Intro</h1><h2 id="setup">Set up</h2><p><a href="../index.html">Home</a></p>')
+      const out = path.join(tmp, 'out')
+      const report = await htmlFilesToMarkdownBatch([index, intro], out, {}, { siteLinks: true })
+      assert.ok(fs.readFileSync(path.join(out, 'index.md'), 'utf8').includes('[Setup](guide/intro.md#set-up)'))
+      assert.ok(fs.readFileSync(path.join(out, 'guide', 'intro.md'), 'utf8').includes('[Home](../index.md)'))
+      assert.deepEqual(report.danglingLinks, [{ file: 0, href: 'gone.html', missingFragment: false }])
+      fs.rmSync(tmp, { recursive: true })
+    })
+
     await run('ConversionOptions: unknown mode falls back to balanced', () => {
       assert.throws(
         () => {
@@ -391,6 +626,22 @@ async function run(name, fn) {
       fs.rmSync(tmp, { recursive: true })
     })
 
+    await run('htmlFileToMarkdownWith: collectLocalAssets', async () => {
+      const tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'mdka-assets-'))
+      const outDir = path.join(tmp, 'out')
+      fs.mkdirSync(path.join(tmp, 'img'))
+      fs.writeFileSync(path.join(tmp, 'img', 'a.png'), 'fake png')
+      const src = path.join(tmp, 'page.html')
+      fs.writeFileSync(src, '<img src="img/a.png" alt="A">')
+
+      const r = await htmlFileToMarkdownWith(src, outDir, { collectLocalAssets: true })
+      const content = fs.readFileSync(r.dest, 'utf8')
+      const href = content.split('](')[1].split(')')[0]
+      assert.ok(href.startsWith('assets/') && href.endsWith('.png'), `got: ${content}`)
+      assert.equal(fs.readFileSync(path.join(outDir, href), 'utf8'), 'fake png')
+      fs.rmSync(tmp, { recursive: true })
+    })
+
     await run('htmlFileToMarkdown consistency with htmlFilesToMarkdown', async () => {
       const tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'mdka-consist-'))
       const out1 = path.join(tmp, 'out1')
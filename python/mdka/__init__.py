@@ -5,12 +5,20 @@ mdka — 省メモリ・高速な HTML to Markdown コンバータ (Rust製)
 from .mdka_python import (  # noqa: F401
     html_to_markdown,
     html_to_markdown_with,
+    html_to_markdown_document,
     html_to_markdown_many,
     html_file_to_markdown,
     html_files_to_markdown,
+    html_files_to_markdown_batch,
     ConversionMode,
     ConvertResult,
     BulkConvertResult,
+    BatchReport,
+    BoilerplateBlock,
+    DanglingLink,
+    DocumentMetadata,
+    MarkdownDocument,
+    StructuredData,
     MdkaError,
     version,
 )
@@ -19,12 +27,20 @@ __version__ = version()
 __all__ = [
     "html_to_markdown",
     "html_to_markdown_with",
+    "html_to_markdown_document",
     "html_to_markdown_many",
     "html_file_to_markdown",
     "html_files_to_markdown",
+    "html_files_to_markdown_batch",
     "ConversionMode",
     "ConvertResult",
     "BulkConvertResult",
+    "BatchReport",
+    "BoilerplateBlock",
+    "DanglingLink",
+    "DocumentMetadata",
+    "MarkdownDocument",
+    "StructuredData",
     "MdkaError",
     "version",
 ]
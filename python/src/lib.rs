@@ -59,6 +59,16 @@ fn to_rust_mode(m: ConversionMode) -> ::mdka::ConversionMode {
     }
 }
 
+fn parse_selectors(selectors: &[String]) -> PyResult<Vec<::mdka::CssSelector>> {
+    selectors
+        .iter()
+        .map(|s| {
+            ::mdka::CssSelector::parse(s)
+                .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))
+        })
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_opts(
     py: Python<'_>,
@@ -69,6 +79,37 @@ fn build_opts(
     preserve_aria_attrs: Option<bool>,
     drop_interactive_shell: Option<bool>,
     unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
 ) -> PyResult<::mdka::ConversionOptions> {
     let mut opts = ::mdka::ConversionOptions::for_mode(to_rust_mode(mode));
     if let Some(v) = preserve_ids {
@@ -98,6 +139,103 @@ fn build_opts(
     if let Some(v) = unwrap_unknown_wrappers {
         opts.unwrap_unknown_wrappers = v;
     }
+    if let Some(v) = svg_policy.as_deref() {
+        opts.svg_policy = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = recover_noscript {
+        opts.recover_noscript = v;
+    }
+    if let Some(v) = expand_shadow_roots {
+        opts.expand_shadow_roots = v;
+    }
+    if let Some(v) = drop_hidden_content {
+        opts.drop_hidden_content = v;
+    }
+    if let Some(v) = keep_screen_reader_text {
+        opts.keep_screen_reader_text = v;
+    }
+    if let Some(v) = inline_style_semantics {
+        opts.inline_style_semantics = v;
+    }
+    if let Some(v) = profile.as_deref() {
+        opts.profile = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = include_selectors {
+        opts.include_selectors = parse_selectors(&v)?;
+    }
+    if let Some(v) = exclude_selectors {
+        opts.exclude_selectors = parse_selectors(&v)?;
+    }
+    if let Some(v) = skip_tags {
+        opts.tag_rules.skip = v;
+    }
+    if let Some(v) = keep_tags {
+        opts.tag_rules.keep = v;
+    }
+    if let Some(v) = shell_tags {
+        opts.tag_rules.shell = v;
+    }
+    if let Some(v) = wrapper_tags {
+        opts.tag_rules.wrapper = v;
+    }
+    if let Some(v) = block_tags {
+        opts.tag_rules.block = v;
+    }
+    if let Some(v) = extract_main_content {
+        opts.extract_main_content = v;
+    }
+    if let Some(v) = front_matter.as_deref() {
+        opts.front_matter = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = front_matter_fields {
+        opts.front_matter_fields = v
+            .iter()
+            .map(|f| f.parse())
+            .collect::<Result<_, _>>()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = render_structured_data {
+        opts.render_structured_data = v;
+    }
+    if let Some(v) = recover_hydration_payloads {
+        opts.recover_hydration_payloads = v;
+    }
+    if let Some(v) = base_url {
+        opts.base_url = Some(v);
+    }
+    if let Some(v) = url_resolution.as_deref() {
+        opts.url_resolution = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = link_style.as_deref() {
+        opts.link_style = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = link_filter.as_deref() {
+        opts.link_policy.filter = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = strip_dead_links {
+        opts.link_policy.strip_dead = v;
+    }
+    if let Some(v) = autolink {
+        opts.link_policy.autolink = v;
+    }
+    if let Some(v) = clean_urls {
+        opts.clean_urls = v;
+    }
+    if let Some(v) = tracking_params {
+        opts.tracking_params = v;
+    }
+    if let Some(v) = collect_local_assets {
+        opts.collect_local_assets = v;
+    }
+    if let Some(v) = data_uri_policy.as_deref() {
+        opts.data_uri_policy = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = image_dimensions.as_deref() {
+        opts.image_dimensions = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    if let Some(v) = emoji_policy.as_deref() {
+        opts.emoji_policy = v.parse().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
     Ok(opts)
 }
 
@@ -153,6 +291,223 @@ impl BulkConvertResult {
     }
 }
 
+fn to_bulk_result(
+    p: &std::path::Path,
+    res: Result<std::path::PathBuf, ::mdka::MdkaError>,
+) -> BulkConvertResult {
+    match res {
+        Ok(dest) => BulkConvertResult {
+            src: p.to_string_lossy().into_owned(),
+            dest: Some(dest.to_string_lossy().into_owned()),
+            error: None,
+        },
+        Err(e) => BulkConvertResult {
+            src: p.to_string_lossy().into_owned(),
+            dest: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// 定型文として取り除いたブロック。
+///
+/// Attributes:
+///     markdown (str): 取り除いたブロックの Markdown
+///     pages (int): このブロックが現れたページの数
+#[pyclass(get_all)]
+pub struct BoilerplateBlock {
+    pub markdown: String,
+    pub pages: usize,
+}
+
+#[pymethods]
+impl BoilerplateBlock {
+    fn __repr__(&self) -> String {
+        format!(
+            "BoilerplateBlock(pages={}, markdown={:?})",
+            self.pages, self.markdown
+        )
+    }
+}
+
+/// サイト変換で入力の中に見つからなかったリンク先。
+///
+/// Attributes:
+///     file (int): リンクを含む入力の files の添字
+///     href (str): 元の href
+///     missing_fragment (bool): ページはあるが #fragment が見つからない
+#[pyclass(get_all)]
+pub struct DanglingLink {
+    pub file: usize,
+    pub href: String,
+    pub missing_fragment: bool,
+}
+
+#[pymethods]
+impl DanglingLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "DanglingLink(file={}, href={:?}, missing_fragment={})",
+            self.file,
+            self.href,
+            if self.missing_fragment {
+                "True"
+            } else {
+                "False"
+            }
+        )
+    }
+}
+
+/// バッチ変換の結果。
+///
+/// Attributes:
+///     files (list[BulkConvertResult]): 入力ごとの結果（paths と同じ順序）
+///     boilerplate (list[BoilerplateBlock]): 取り除いた定型ブロック（現れたページの多い順）
+///     duplicates (list[list[int]]): 近似重複のグループ（files の添字の昇順）
+///     dangling_links (list[DanglingLink]): サイト変換で見つからなかったリンク先
+#[pyclass(get_all)]
+pub struct BatchReport {
+    pub files: Vec<Py<BulkConvertResult>>,
+    pub boilerplate: Vec<Py<BoilerplateBlock>>,
+    pub duplicates: Vec<Vec<usize>>,
+    pub dangling_links: Vec<Py<DanglingLink>>,
+}
+
+/// 文書メタデータ。見つからない項目は None。
+///
+/// Attributes:
+///     title (str | None): <title> → og:title → twitter:title
+///     description (str | None): <meta name="description"> → og:description → twitter:description
+///     canonical_url (str | None): <link rel="canonical"> → og:url
+///     lang (str | None): <html lang> → content-language → og:locale
+///     author (str | None): <meta name="author"> → article:author → twitter:creator
+///     published (str | None): article:published_time → <meta name="date"> など
+///     modified (str | None): article:modified_time → og:updated_time など
+///     site_name (str | None): og:site_name
+///     image (str | None): og:image → twitter:image
+///     keywords (list[str]): <meta name="keywords"> をカンマで分けたもの
+///     open_graph (list[tuple[str, str]]): og:* / article:* のすべて（文書順）
+///     twitter (list[tuple[str, str]]): twitter:* のすべて（文書順）
+///     structured_data (StructuredData): JSON-LD・microdata・microformats
+#[pyclass(get_all)]
+pub struct DocumentMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub lang: Option<String>,
+    pub author: Option<String>,
+    pub published: Option<String>,
+    pub modified: Option<String>,
+    pub site_name: Option<String>,
+    pub image: Option<String>,
+    pub keywords: Vec<String>,
+    pub open_graph: Vec<(String, String)>,
+    pub twitter: Vec<(String, String)>,
+    pub structured_data: Py<StructuredData>,
+}
+
+#[pymethods]
+impl DocumentMetadata {
+    fn __repr__(&self) -> String {
+        format!(
+            "DocumentMetadata(title={:?}, canonical_url={:?})",
+            self.title, self.canonical_url
+        )
+    }
+}
+
+/// 文書に埋め込まれた構造化データ。各項目は JSON 相当の dict。
+///
+/// Attributes:
+///     json_ld (list[dict]): <script type="application/ld+json"> の各項目
+///     microdata (list[dict]): itemscope の各項目
+///     microformats (list[dict]): h-* の各項目（microformats2 の JSON 形式）
+#[pyclass(get_all)]
+pub struct StructuredData {
+    pub json_ld: Vec<Py<PyAny>>,
+    pub microdata: Vec<Py<PyAny>>,
+    pub microformats: Vec<Py<PyAny>>,
+}
+
+impl StructuredData {
+    fn new(py: Python<'_>, data: ::mdka::StructuredData) -> PyResult<Self> {
+        let values = |v: Vec<::mdka::JsonValue>| -> PyResult<Vec<Py<PyAny>>> {
+            v.iter().map(|item| to_py(py, item)).collect()
+        };
+        Ok(Self {
+            json_ld: values(data.json_ld)?,
+            microdata: values(data.microdata)?,
+            microformats: values(data.microformats)?,
+        })
+    }
+}
+
+/// JSON の値を dict / list / str / int / float / bool / None に変換する。
+fn to_py(py: Python<'_>, value: &::mdka::JsonValue) -> PyResult<Py<PyAny>> {
+    use ::mdka::JsonValue;
+    use pyo3::types::{PyDict, PyList};
+    Ok(match value {
+        JsonValue::Null => py.None(),
+        JsonValue::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => {
+            (*n as i64).into_pyobject(py)?.into_any().unbind()
+        }
+        JsonValue::Number(n) => n.into_pyobject(py)?.into_any().unbind(),
+        JsonValue::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        JsonValue::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any().unbind()
+        }
+        JsonValue::Object(entries) => {
+            let dict = PyDict::new(py);
+            for (k, v) in entries {
+                dict.set_item(k, to_py(py, v)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// `html_to_markdown_document` の結果。
+///
+/// Attributes:
+///     markdown (str): 変換した Markdown
+///     metadata (DocumentMetadata): 文書メタデータ
+#[pyclass(get_all)]
+pub struct MarkdownDocument {
+    pub markdown: String,
+    pub metadata: Py<DocumentMetadata>,
+}
+
+impl MarkdownDocument {
+    fn new(py: Python<'_>, doc: ::mdka::MarkdownDocument) -> PyResult<Self> {
+        let m = doc.metadata;
+        let metadata = DocumentMetadata {
+            title: m.title,
+            description: m.description,
+            canonical_url: m.canonical_url,
+            lang: m.lang,
+            author: m.author,
+            published: m.published,
+            modified: m.modified,
+            site_name: m.site_name,
+            image: m.image,
+            keywords: m.keywords,
+            open_graph: m.open_graph,
+            twitter: m.twitter,
+            structured_data: Py::new(py, StructuredData::new(py, m.structured_data)?)?,
+        };
+        Ok(Self {
+            markdown: doc.markdown,
+            metadata: Py::new(py, metadata)?,
+        })
+    }
+}
+
 // ─── 文字列変換 API ───────────────────────────────────────────────────────
 
 #[pyfunction]
@@ -163,7 +518,7 @@ fn html_to_markdown(html: &str) -> String {
 #[pyfunction]
 #[pyo3(signature = (html, mode=ConversionMode::Balanced, preserve_ids=None,
     preserve_classes=None, preserve_data_attrs=None, preserve_aria_attrs=None,
-    drop_interactive_shell=None, unwrap_unknown_wrappers=None))]
+    drop_interactive_shell=None, unwrap_unknown_wrappers=None, svg_policy=None, recover_noscript=None, expand_shadow_roots=None, drop_hidden_content=None, keep_screen_reader_text=None, inline_style_semantics=None, profile=None, include_selectors=None, exclude_selectors=None, skip_tags=None, keep_tags=None, shell_tags=None, wrapper_tags=None, block_tags=None, extract_main_content=None, front_matter=None, front_matter_fields=None, render_structured_data=None, recover_hydration_payloads=None, base_url=None, url_resolution=None, link_style=None, link_filter=None, strip_dead_links=None, autolink=None, clean_urls=None, tracking_params=None, collect_local_assets=None, data_uri_policy=None, image_dimensions=None, emoji_policy=None))]
 #[allow(clippy::too_many_arguments)]
 fn html_to_markdown_with(
     py: Python<'_>,
@@ -175,6 +530,37 @@ fn html_to_markdown_with(
     preserve_aria_attrs: Option<bool>,
     drop_interactive_shell: Option<bool>,
     unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
 ) -> PyResult<String> {
     let opts = build_opts(
         py,
@@ -185,10 +571,135 @@ fn html_to_markdown_with(
         preserve_aria_attrs,
         drop_interactive_shell,
         unwrap_unknown_wrappers,
+        svg_policy,
+        recover_noscript,
+        expand_shadow_roots,
+        drop_hidden_content,
+        keep_screen_reader_text,
+        inline_style_semantics,
+        profile,
+        include_selectors,
+        exclude_selectors,
+        skip_tags,
+        keep_tags,
+        shell_tags,
+        wrapper_tags,
+        block_tags,
+        extract_main_content,
+        front_matter,
+        front_matter_fields,
+        render_structured_data,
+        recover_hydration_payloads,
+        base_url,
+        url_resolution,
+        link_style,
+        link_filter,
+        strip_dead_links,
+        autolink,
+        clean_urls,
+        tracking_params,
+        collect_local_assets,
+        data_uri_policy,
+        image_dimensions,
+        emoji_policy,
     )?;
     Ok(::mdka::html_to_markdown_with(html, &opts))
 }
 
+/// HTML を Markdown に変換し、タイトルなどのメタデータも返す。
+///
+/// キーワード引数は `html_to_markdown_with` と同じ。
+#[pyfunction]
+#[pyo3(signature = (html, mode=ConversionMode::Balanced, preserve_ids=None,
+    preserve_classes=None, preserve_data_attrs=None, preserve_aria_attrs=None,
+    drop_interactive_shell=None, unwrap_unknown_wrappers=None, svg_policy=None, recover_noscript=None, expand_shadow_roots=None, drop_hidden_content=None, keep_screen_reader_text=None, inline_style_semantics=None, profile=None, include_selectors=None, exclude_selectors=None, skip_tags=None, keep_tags=None, shell_tags=None, wrapper_tags=None, block_tags=None, extract_main_content=None, front_matter=None, front_matter_fields=None, render_structured_data=None, recover_hydration_payloads=None, base_url=None, url_resolution=None, link_style=None, link_filter=None, strip_dead_links=None, autolink=None, clean_urls=None, tracking_params=None, collect_local_assets=None, data_uri_policy=None, image_dimensions=None, emoji_policy=None))]
+#[allow(clippy::too_many_arguments)]
+fn html_to_markdown_document(
+    py: Python<'_>,
+    html: &str,
+    mode: ConversionMode,
+    preserve_ids: Option<bool>,
+    preserve_classes: Option<bool>,
+    preserve_data_attrs: Option<bool>,
+    preserve_aria_attrs: Option<bool>,
+    drop_interactive_shell: Option<bool>,
+    unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
+) -> PyResult<MarkdownDocument> {
+    let opts = build_opts(
+        py,
+        mode,
+        preserve_ids,
+        preserve_classes,
+        preserve_data_attrs,
+        preserve_aria_attrs,
+        drop_interactive_shell,
+        unwrap_unknown_wrappers,
+        svg_policy,
+        recover_noscript,
+        expand_shadow_roots,
+        drop_hidden_content,
+        keep_screen_reader_text,
+        inline_style_semantics,
+        profile,
+        include_selectors,
+        exclude_selectors,
+        skip_tags,
+        keep_tags,
+        shell_tags,
+        wrapper_tags,
+        block_tags,
+        extract_main_content,
+        front_matter,
+        front_matter_fields,
+        render_structured_data,
+        recover_hydration_payloads,
+        base_url,
+        url_resolution,
+        link_style,
+        link_filter,
+        strip_dead_links,
+        autolink,
+        clean_urls,
+        tracking_params,
+        collect_local_assets,
+        data_uri_policy,
+        image_dimensions,
+        emoji_policy,
+    )?;
+    MarkdownDocument::new(py, ::mdka::html_to_markdown_document(html, &opts))
+}
+
 /// # The `html_to_markdown_many` function releases the Python GIL and utilizes `rayon`
 /// to perform conversions in parallel across multiple CPU cores for maximum throughput.
 #[pyfunction]
@@ -223,7 +734,7 @@ fn html_to_markdown_many(py: Python<'_>, html_list: Vec<String>) -> Vec<String>
 #[pyfunction]
 #[pyo3(signature = (path, out_dir=None, mode=ConversionMode::Balanced, preserve_ids=None,
     preserve_classes=None, preserve_data_attrs=None, preserve_aria_attrs=None,
-    drop_interactive_shell=None, unwrap_unknown_wrappers=None))]
+    drop_interactive_shell=None, unwrap_unknown_wrappers=None, svg_policy=None, recover_noscript=None, expand_shadow_roots=None, drop_hidden_content=None, keep_screen_reader_text=None, inline_style_semantics=None, profile=None, include_selectors=None, exclude_selectors=None, skip_tags=None, keep_tags=None, shell_tags=None, wrapper_tags=None, block_tags=None, extract_main_content=None, front_matter=None, front_matter_fields=None, render_structured_data=None, recover_hydration_payloads=None, base_url=None, url_resolution=None, link_style=None, link_filter=None, strip_dead_links=None, autolink=None, clean_urls=None, tracking_params=None, collect_local_assets=None, data_uri_policy=None, image_dimensions=None, emoji_policy=None))]
 // This argument list is the published Python keyword-argument API; restructuring
 // it to satisfy clippy would break the published surface.
 #[allow(clippy::too_many_arguments)]
@@ -238,6 +749,37 @@ fn html_file_to_markdown(
     preserve_aria_attrs: Option<bool>,
     drop_interactive_shell: Option<bool>,
     unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
 ) -> PyResult<ConvertResult> {
     let opts = build_opts(
         py,
@@ -248,6 +790,37 @@ fn html_file_to_markdown(
         preserve_aria_attrs,
         drop_interactive_shell,
         unwrap_unknown_wrappers,
+        svg_policy,
+        recover_noscript,
+        expand_shadow_roots,
+        drop_hidden_content,
+        keep_screen_reader_text,
+        inline_style_semantics,
+        profile,
+        include_selectors,
+        exclude_selectors,
+        skip_tags,
+        keep_tags,
+        shell_tags,
+        wrapper_tags,
+        block_tags,
+        extract_main_content,
+        front_matter,
+        front_matter_fields,
+        render_structured_data,
+        recover_hydration_payloads,
+        base_url,
+        url_resolution,
+        link_style,
+        link_filter,
+        strip_dead_links,
+        autolink,
+        clean_urls,
+        tracking_params,
+        collect_local_assets,
+        data_uri_policy,
+        image_dimensions,
+        emoji_policy,
     )?;
     let out_dir_ref: Option<&str> = out_dir.as_deref();
 
@@ -266,7 +839,7 @@ fn html_file_to_markdown(
 #[pyfunction]
 #[pyo3(signature = (paths, out_dir, mode=ConversionMode::Balanced, preserve_ids=None,
     preserve_classes=None, preserve_data_attrs=None, preserve_aria_attrs=None,
-    drop_interactive_shell=None, unwrap_unknown_wrappers=None))]
+    drop_interactive_shell=None, unwrap_unknown_wrappers=None, svg_policy=None, recover_noscript=None, expand_shadow_roots=None, drop_hidden_content=None, keep_screen_reader_text=None, inline_style_semantics=None, profile=None, include_selectors=None, exclude_selectors=None, skip_tags=None, keep_tags=None, shell_tags=None, wrapper_tags=None, block_tags=None, extract_main_content=None, front_matter=None, front_matter_fields=None, render_structured_data=None, recover_hydration_payloads=None, base_url=None, url_resolution=None, link_style=None, link_filter=None, strip_dead_links=None, autolink=None, clean_urls=None, tracking_params=None, collect_local_assets=None, data_uri_policy=None, image_dimensions=None, emoji_policy=None))]
 // This argument list is the published Python keyword-argument API; restructuring
 // it to satisfy clippy would break the published surface.
 #[allow(clippy::too_many_arguments)]
@@ -281,6 +854,37 @@ fn html_files_to_markdown(
     preserve_aria_attrs: Option<bool>,
     drop_interactive_shell: Option<bool>,
     unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
 ) -> PyResult<Vec<BulkConvertResult>> {
     use std::path::Path;
     let out = Path::new(&out_dir);
@@ -296,6 +900,37 @@ fn html_files_to_markdown(
         preserve_aria_attrs,
         drop_interactive_shell,
         unwrap_unknown_wrappers,
+        svg_policy,
+        recover_noscript,
+        expand_shadow_roots,
+        drop_hidden_content,
+        keep_screen_reader_text,
+        inline_style_semantics,
+        profile,
+        include_selectors,
+        exclude_selectors,
+        skip_tags,
+        keep_tags,
+        shell_tags,
+        wrapper_tags,
+        block_tags,
+        extract_main_content,
+        front_matter,
+        front_matter_fields,
+        render_structured_data,
+        recover_hydration_payloads,
+        base_url,
+        url_resolution,
+        link_style,
+        link_filter,
+        strip_dead_links,
+        autolink,
+        clean_urls,
+        tracking_params,
+        collect_local_assets,
+        data_uri_policy,
+        image_dimensions,
+        emoji_policy,
     )?;
     let path_bufs: Vec<std::path::PathBuf> = paths.iter().map(std::path::PathBuf::from).collect();
 
@@ -303,21 +938,165 @@ fn html_files_to_markdown(
 
     Ok(results
         .into_iter()
-        .map(|(p, res)| match res {
-            Ok(dest) => BulkConvertResult {
-                src: p.to_string_lossy().into_owned(),
-                dest: Some(dest.to_string_lossy().into_owned()),
-                error: None,
-            },
-            Err(e) => BulkConvertResult {
-                src: p.to_string_lossy().into_owned(),
-                dest: None,
-                error: Some(e.to_string()),
-            },
-        })
+        .map(|(p, res)| to_bulk_result(p, res))
         .collect())
 }
 
+/// 複数の HTML ファイルを文書横断の処理付きで変換する。
+///
+/// `boilerplate_threshold` を指定すると、ページのこの割合（0〜1）を超える数に
+/// 現れるブロックを定型文として取り除き、`BatchReport.boilerplate` に報告する。
+/// `near_duplicate_threshold` を指定すると、変換結果の類似度（0〜1、SimHash）が
+/// この値以上のファイルを `BatchReport.duplicates` にまとめる。
+/// `site_links=True` では入力をひとつのサイトとして、ディレクトリ構成を保って
+/// 書き出し、入力同士のリンクを出力の `.md` に書き換える。見つからないリンク先は
+/// `BatchReport.dangling_links` に報告する。
+#[pyfunction]
+#[pyo3(signature = (paths, out_dir, boilerplate_threshold=None, near_duplicate_threshold=None, site_links=false, mode=ConversionMode::Balanced, preserve_ids=None,
+    preserve_classes=None, preserve_data_attrs=None, preserve_aria_attrs=None,
+    drop_interactive_shell=None, unwrap_unknown_wrappers=None, svg_policy=None, recover_noscript=None, expand_shadow_roots=None, drop_hidden_content=None, keep_screen_reader_text=None, inline_style_semantics=None, profile=None, include_selectors=None, exclude_selectors=None, skip_tags=None, keep_tags=None, shell_tags=None, wrapper_tags=None, block_tags=None, extract_main_content=None, front_matter=None, front_matter_fields=None, render_structured_data=None, recover_hydration_payloads=None, base_url=None, url_resolution=None, link_style=None, link_filter=None, strip_dead_links=None, autolink=None, clean_urls=None, tracking_params=None, collect_local_assets=None, data_uri_policy=None, image_dimensions=None, emoji_policy=None))]
+// This argument list is the published Python keyword-argument API; restructuring
+// it to satisfy clippy would break the published surface.
+#[allow(clippy::too_many_arguments)]
+fn html_files_to_markdown_batch(
+    py: Python<'_>,
+    paths: Vec<String>,
+    out_dir: String,
+    boilerplate_threshold: Option<f64>,
+    near_duplicate_threshold: Option<f64>,
+    site_links: bool,
+    mode: ConversionMode,
+    preserve_ids: Option<bool>,
+    preserve_classes: Option<bool>,
+    preserve_data_attrs: Option<bool>,
+    preserve_aria_attrs: Option<bool>,
+    drop_interactive_shell: Option<bool>,
+    unwrap_unknown_wrappers: Option<bool>,
+    svg_policy: Option<String>,
+    recover_noscript: Option<bool>,
+    expand_shadow_roots: Option<bool>,
+    drop_hidden_content: Option<bool>,
+    keep_screen_reader_text: Option<bool>,
+    inline_style_semantics: Option<bool>,
+    profile: Option<String>,
+    include_selectors: Option<Vec<String>>,
+    exclude_selectors: Option<Vec<String>>,
+    skip_tags: Option<Vec<String>>,
+    keep_tags: Option<Vec<String>>,
+    shell_tags: Option<Vec<String>>,
+    wrapper_tags: Option<Vec<String>>,
+    block_tags: Option<Vec<String>>,
+    extract_main_content: Option<bool>,
+    front_matter: Option<String>,
+    front_matter_fields: Option<Vec<String>>,
+    render_structured_data: Option<bool>,
+    recover_hydration_payloads: Option<bool>,
+    base_url: Option<String>,
+    url_resolution: Option<String>,
+    link_style: Option<String>,
+    link_filter: Option<String>,
+    strip_dead_links: Option<bool>,
+    autolink: Option<bool>,
+    clean_urls: Option<bool>,
+    tracking_params: Option<Vec<String>>,
+    collect_local_assets: Option<bool>,
+    data_uri_policy: Option<String>,
+    image_dimensions: Option<String>,
+    emoji_policy: Option<String>,
+) -> PyResult<BatchReport> {
+    use std::path::Path;
+    let out = Path::new(&out_dir);
+    std::fs::create_dir_all(out)
+        .map_err(|e| MdkaError::new_err(format!("cannot create out_dir: {e}")))?;
+
+    let opts = build_opts(
+        py,
+        mode,
+        preserve_ids,
+        preserve_classes,
+        preserve_data_attrs,
+        preserve_aria_attrs,
+        drop_interactive_shell,
+        unwrap_unknown_wrappers,
+        svg_policy,
+        recover_noscript,
+        expand_shadow_roots,
+        drop_hidden_content,
+        keep_screen_reader_text,
+        inline_style_semantics,
+        profile,
+        include_selectors,
+        exclude_selectors,
+        skip_tags,
+        keep_tags,
+        shell_tags,
+        wrapper_tags,
+        block_tags,
+        extract_main_content,
+        front_matter,
+        front_matter_fields,
+        render_structured_data,
+        recover_hydration_payloads,
+        base_url,
+        url_resolution,
+        link_style,
+        link_filter,
+        strip_dead_links,
+        autolink,
+        clean_urls,
+        tracking_params,
+        collect_local_assets,
+        data_uri_policy,
+        image_dimensions,
+        emoji_policy,
+    )?;
+    let path_bufs: Vec<std::path::PathBuf> = paths.iter().map(std::path::PathBuf::from).collect();
+
+    let batch = ::mdka::BatchOptions {
+        boilerplate_threshold,
+        near_duplicate_threshold,
+        site_links,
+    };
+
+    let report = py.detach(|| ::mdka::html_files_to_markdown_batch(&path_bufs, out, &opts, &batch));
+
+    Ok(BatchReport {
+        files: report
+            .files
+            .into_iter()
+            .map(|(p, res)| Py::new(py, to_bulk_result(p, res)))
+            .collect::<PyResult<_>>()?,
+        boilerplate: report
+            .boilerplate
+            .into_iter()
+            .map(|b| {
+                Py::new(
+                    py,
+                    BoilerplateBlock {
+                        markdown: b.markdown,
+                        pages: b.pages,
+                    },
+                )
+            })
+            .collect::<PyResult<_>>()?,
+        duplicates: report.duplicates,
+        dangling_links: report
+            .dangling_links
+            .into_iter()
+            .map(|d| {
+                Py::new(
+                    py,
+                    DanglingLink {
+                        file: d.file,
+                        href: d.href,
+                        missing_fragment: d.missing_fragment,
+                    },
+                )
+            })
+            .collect::<PyResult<_>>()?,
+    })
+}
+
 #[pyfunction]
 fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
@@ -331,11 +1110,19 @@ fn mdka_python(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ConversionMode>()?;
     m.add_class::<ConvertResult>()?;
     m.add_class::<BulkConvertResult>()?;
+    m.add_class::<BatchReport>()?;
+    m.add_class::<DocumentMetadata>()?;
+    m.add_class::<MarkdownDocument>()?;
+    m.add_class::<StructuredData>()?;
+    m.add_class::<BoilerplateBlock>()?;
+    m.add_class::<DanglingLink>()?;
     m.add_function(wrap_pyfunction!(html_to_markdown, m)?)?;
     m.add_function(wrap_pyfunction!(html_to_markdown_with, m)?)?;
+    m.add_function(wrap_pyfunction!(html_to_markdown_document, m)?)?;
     m.add_function(wrap_pyfunction!(html_to_markdown_many, m)?)?;
     m.add_function(wrap_pyfunction!(html_file_to_markdown, m)?)?;
     m.add_function(wrap_pyfunction!(html_files_to_markdown, m)?)?;
+    m.add_function(wrap_pyfunction!(html_files_to_markdown_batch, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
     Ok(())
 }
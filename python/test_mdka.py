@@ -19,9 +19,11 @@ import mdka
 from mdka import (
     html_to_markdown,
     html_to_markdown_with,
+    html_to_markdown_document,
     html_to_markdown_many,
     html_file_to_markdown,
     html_files_to_markdown,
+    html_files_to_markdown_batch,
     ConversionMode,
     ConvertResult,
     BulkConvertResult,
@@ -314,6 +316,56 @@ def test_file_conversion_mixed_results(tmp_path):
     assert len(error_results) == 1
 
 
+def test_batch_drops_site_wide_boilerplate(tmp_path):
+    files = []
+    for name in ["a", "b", "c"]:
+        src = tmp_path / f"{name}.html"
+        src.write_text(f"<p>We use cookies.</p><h1>{name}</h1>")
+        files.append(str(src))
+    out_dir = tmp_path / "out"
+
+    report = html_files_to_markdown_batch(files, str(out_dir), boilerplate_threshold=0.5)
+    assert len(report.files) == 3
+    assert [(b.markdown, b.pages) for b in report.boilerplate] == [("We use cookies.", 3)]
+    assert Path(report.files[0].dest).read_text() == "# a\n"
+
+
+def test_batch_groups_near_duplicates(tmp_path):
+    text = ("Download the archive from the releases page, unpack it into a directory "
+            "on your PATH, and run the binary once to create the default configuration file.")
+    pages = [text, "Boil the pasta in salted water and serve it warm with basil.", text]
+    files = []
+    for i, body in enumerate(pages):
+        src = tmp_path / f"p{i}.html"
+        src.write_text(f"<p>{body}</p>")
+        files.append(str(src))
+
+    report = html_files_to_markdown_batch(
+        files, str(tmp_path / "out"), near_duplicate_threshold=0.9
+    )
+    assert report.duplicates == [[0, 2]]
+
+
+def test_batch_site_links(tmp_path):
+    (tmp_path / "guide").mkdir()
+    (tmp_path / "index.html").write_text(
+        '<h1>Home</h1><p><a href="guide/intro.html#setup">Setup</a> <a href="gone.html">Gone</a></p>'
+    )
+    (tmp_path / "guide" / "intro.html").write_text(
+        '<h1>Intro</h1><h2 id="setup">Set up</h2><p><a href="../index.html">Home</a></p>'
+    )
+    files = [str(tmp_path / "index.html"), str(tmp_path / "guide" / "intro.html")]
+    out_dir = tmp_path / "out"
+
+    report = html_files_to_markdown_batch(files, str(out_dir), site_links=True)
+    assert all(r.error is None for r in report.files)
+    assert "[Setup](guide/intro.md#set-up)" in (out_dir / "index.md").read_text()
+    assert "[Home](../index.md)" in (out_dir / "guide" / "intro.md").read_text()
+    assert [(d.file, d.href, d.missing_fragment) for d in report.dangling_links] == [
+        (0, "gone.html", False)
+    ]
+
+
 # ─── BulkConvertResult ───────────────────────────────────────────────────────────
 
 def test_convert_result_ok_property(tmp_path):
@@ -446,6 +498,181 @@ def test_with_unwrap_unknown_wrappers_flag():
     assert without == "Before\n\ninner\n\nAfter\n"
     assert with_unwrap == "BeforeinnerAfter\n"
 
+def test_with_svg_policy():
+    html = '<a href="/gh"><svg><title>GitHub</title><path d="M0"/></svg></a>'
+    assert html_to_markdown_with(html, svg_policy="alt-text") == "[GitHub](/gh)\n"
+    assert html_to_markdown_with(html, svg_policy="skip") == "[](/gh)\n"
+    with pytest.raises(ValueError, match="unknown svg policy"):
+        html_to_markdown_with(html, svg_policy="bogus")
+
+def test_with_noscript_and_shadow_root_recovery():
+    noscript = '<div class="app"></div><noscript><p>Server text</p></noscript>'
+    assert html_to_markdown_with(noscript, recover_noscript=True) == "Server text\n"
+    assert html_to_markdown_with(noscript) == ""
+    shadow = '<x-card><template shadowrootmode="open"><p>Inside</p></template></x-card>'
+    assert html_to_markdown_with(shadow) == "Inside\n"
+    assert html_to_markdown_with(shadow, expand_shadow_roots=False) == ""
+
+def test_with_hidden_content_flags():
+    html = '<p>Body <span class="sr-only">(new tab)</span></p><div hidden>Modal</div>'
+    assert html_to_markdown_with(html) == "Body\n"
+    assert html_to_markdown_with(html, keep_screen_reader_text=True) == "Body (new tab)\n"
+    assert html_to_markdown_with(html, drop_hidden_content=False) == "Body (new tab)\n\nModal\n"
+
+def test_with_inline_style_semantics():
+    html = '<p><span style="font-weight:700">bold</span></p>'
+    assert html_to_markdown_with(html) == "**bold**\n"
+    assert html_to_markdown_with(html, inline_style_semantics=False) == "bold\n"
+
+def test_with_profile_office():
+    html = '<p class="MsoListParagraph" style="mso-list:l0 level1 lfo1"><span style="mso-list:Ignore">·</span>One<o:p></o:p></p>'
+    assert html_to_markdown_with(html, profile="office") == "- One\n"
+    assert html_to_markdown_with(html, profile="generic") != "- One\n"
+    with pytest.raises(ValueError, match="unknown source profile"):
+        html_to_markdown_with(html, profile="word")
+
+def test_with_profile_email():
+    html = '<div class="preheader">Preview</div><table><tr><td>Hello</td></tr><tr><td>World<img src="t.gif" width="1" height="1"></td></tr></table>'
+    assert html_to_markdown_with(html, profile="email") == "Hello\n\nWorld\n"
+
+def test_with_include_and_exclude_selectors():
+    html = '<div class="ad">Buy</div><article><p>Lead</p><p class="share">Share</p></article>'
+    assert html_to_markdown_with(html, include_selectors=["article"]) == "Lead\n\nShare\n"
+    assert (
+        html_to_markdown_with(html, include_selectors=["article"], exclude_selectors=[".share"])
+        == "Lead\n"
+    )
+    with pytest.raises(ValueError, match="invalid selector"):
+        html_to_markdown_with(html, exclude_selectors=["div["])
+
+def test_with_tag_classification_overrides():
+    html = "<my-card>One</my-card><my-card>Two</my-card><form>Sign up</form><iframe>Fallback</iframe>"
+    assert html_to_markdown_with(html) == "OneTwoSign up\n"
+    assert (
+        html_to_markdown_with(html, block_tags=["my-card"], skip_tags=["form"], keep_tags=["iframe"])
+        == "One\n\nTwo\n\nFallback\n"
+    )
+
+def test_with_extract_main_content():
+    html = (
+        '<div class="sidebar"><p>Subscribe to the newsletter for weekly updates and more.</p></div>'
+        "<article><h1>Title</h1><p>The article body is long enough to be scored, with commas, clauses, and detail.</p></article>"
+    )
+    assert "Subscribe" in html_to_markdown_with(html)
+    assert html_to_markdown_with(html, extract_main_content=True) == (
+        "# Title\n\nThe article body is long enough to be scored, with commas, clauses, and detail.\n"
+    )
+
+def test_html_to_markdown_document_returns_metadata():
+    doc = html_to_markdown_document(
+        '<html lang="en"><head><title>Hello</title>'
+        '<meta property="og:site_name" content="Site"></head>'
+        '<body><nav>Menu</nav><p>Body</p></body></html>',
+        drop_interactive_shell=True,
+    )
+    assert doc.markdown == "Body\n"
+    assert doc.metadata.title == "Hello"
+    assert doc.metadata.lang == "en"
+    assert doc.metadata.site_name == "Site"
+    assert doc.metadata.open_graph == [("og:site_name", "Site")]
+    assert doc.metadata.description is None
+
+
+def test_structured_data():
+    html = ('<script type="application/ld+json">'
+            '{"@type": "Recipe", "name": "Tea", "recipeIngredient": ["1 tea bag", "hot water"],'
+            ' "recipeInstructions": "Steep.", "yield": 1, "rating": 4.5, "vegan": true, "note": null}'
+            '</script><p>Simple.</p>')
+    doc = html_to_markdown_document(html)
+    assert doc.metadata.structured_data.json_ld == [{
+        "@type": "Recipe", "name": "Tea", "recipeIngredient": ["1 tea bag", "hot water"],
+        "recipeInstructions": "Steep.", "yield": 1, "rating": 4.5, "vegan": True, "note": None,
+    }]
+    assert doc.metadata.structured_data.microformats == []
+    assert doc.metadata.title == "Tea"
+    assert doc.markdown == "Simple.\n"
+    assert "- 1 tea bag" in html_to_markdown_with(html, render_structured_data=True)
+
+
+def test_recover_hydration_payloads():
+    html = ('<div id="__next"></div><script id="__NEXT_DATA__" type="application/json">'
+            '{"props": {"pageProps": {"html": "<h2>Intro</h2><p>From JSON.</p>"}}}</script>')
+    assert html_to_markdown_with(html, recover_hydration_payloads=True) == "## Intro\n\nFrom JSON.\n"
+    assert html_to_markdown_with(html, preserve_ids=False) == ""
+
+
+def test_base_url():
+    html = '<a href="../guide">Guide</a> <img src="//cdn.example/a.png" alt="A">'
+    base = "https://example.com/docs/intro"
+    assert html_to_markdown_with(html, base_url=base) == \
+        "[Guide](https://example.com/guide) ![A](https://cdn.example/a.png)\n"
+    assert html_to_markdown_with(html, base_url=base, url_resolution="scheme-relative") == \
+        "[Guide](../guide) ![A](https://cdn.example/a.png)\n"
+    with pytest.raises(ValueError, match="unknown url resolution"):
+        html_to_markdown_with(html, url_resolution="relative")
+
+
+def test_link_style():
+    html = '<p><a href="/a">A</a> and <a href="/a">again</a></p>'
+    assert html_to_markdown_with(html, link_style="reference") == "[A][1] and [again][1]\n\n[1]: /a\n"
+    assert html_to_markdown_with(html, link_style="footnote") == "A[^1] and again[^1]\n\n[^1]: /a\n"
+    with pytest.raises(ValueError, match="unknown link style"):
+        html_to_markdown_with(html, link_style="numbered")
+
+
+def test_link_policy():
+    html = '<p><a href="/guide">Guide</a> <a href="https://example.com">https://example.com</a> <a href="#">Top</a></p>'
+    assert html_to_markdown_with(html, link_filter="external-only", autolink=True) == \
+        "Guide <https://example.com> Top\n"
+    assert html_to_markdown_with(html, strip_dead_links=True, link_filter="text-only") == \
+        "Guide https://example.com Top\n"
+    with pytest.raises(ValueError, match="unknown link filter"):
+        html_to_markdown_with(html, link_filter="external")
+
+
+def test_clean_urls():
+    html = '<a href="https://www.google.com/url?q=https%3A%2F%2Fexample.com%2Fa%3Futm_source%3Dx%26id%3D1">A</a>'
+    assert html_to_markdown_with(html, clean_urls=True) == "[A](https://example.com/a?id=1)\n"
+    assert html_to_markdown_with(html, clean_urls=True, tracking_params=["id"]) == \
+        "[A](https://example.com/a?utm_source=x)\n"
+
+
+def test_data_uri_policy():
+    html = '<p>A <img src="data:image/png;base64,Zm9v" alt="Chart"> B</p>'
+    assert html_to_markdown_with(html, data_uri_policy="alt-text") == "A Chart B\n"
+    assert html_to_markdown_with(html, data_uri_policy="drop") == "A B\n"
+    with pytest.raises(ValueError):
+        html_to_markdown_with(html, data_uri_policy="bogus")
+
+
+def test_image_dimensions():
+    html = '<img src="a.png" alt="A" width="100" height="50">'
+    assert html_to_markdown_with(html) == "![A](a.png)\n"
+    assert html_to_markdown_with(html, image_dimensions="attributes") == "![A](a.png){width=100 height=50}\n"
+    assert (html_to_markdown_with(html, image_dimensions="html")
+            == '<img src="a.png" alt="A" width="100" height="50">\n')
+    with pytest.raises(ValueError):
+        html_to_markdown_with(html, image_dimensions="bogus")
+
+
+def test_emoji_policy():
+    html = '<p>Hi <g-emoji alias="wave">👋</g-emoji> <img class="emoji" alt="😄" src="s.png"></p>'
+    assert html_to_markdown_with(html) == "Hi 👋 😄\n"
+    assert html_to_markdown_with(html, emoji_policy="shortcode") == "Hi :wave: 😄\n"
+    assert html_to_markdown_with(html, emoji_policy="image") == "Hi 👋 ![😄](s.png)\n"
+    with pytest.raises(ValueError):
+        html_to_markdown_with(html, emoji_policy="bogus")
+
+
+def test_front_matter():
+    html = ('<head><title>Hi</title><meta property="article:published_time" content="2026-01-02">'
+            '</head><p>Body</p>')
+    md = html_to_markdown_with(html, front_matter="yaml", front_matter_fields=["title", "date=pubDate"])
+    assert md == '---\ntitle: "Hi"\npubDate: "2026-01-02"\n---\n\nBody\n'
+    with pytest.raises(ValueError):
+        html_to_markdown_with(html, front_matter_fields=["nope"])
+
+
 def test_explicit_deprecated_field_emits_warning():
     with pytest.warns(DeprecationWarning, match="preserve_classes"):
         html_to_markdown_with(
@@ -554,6 +781,21 @@ def test_file_to_markdown_with_mode(tmp_path):
     assert "# Title"                  in content, f"got: {content}"
     assert "nav" not in content.lower(),           f"nav leaked: {content}"
 
+def test_file_to_markdown_collect_local_assets(tmp_path):
+    """collect_local_assets で相対パスの画像が assets/ へ複製される。"""
+    (tmp_path / "img").mkdir()
+    (tmp_path / "img" / "a.png").write_bytes(b"\x89PNG fake")
+    src = tmp_path / "page.html"
+    src.write_text('<img src="img/a.png" alt="A">')
+    out_dir = tmp_path / "out"
+
+    html_file_to_markdown(str(src), str(out_dir), collect_local_assets=True)
+
+    content = (out_dir / "page.md").read_text()
+    href = content.split("](")[1].split(")")[0]
+    assert href.startswith("assets/") and href.endswith(".png"), f"got: {content}"
+    assert (out_dir / href).read_bytes() == b"\x89PNG fake"
+
 def test_file_to_markdown_returns_correct_types(tmp_path):
     """ConvertResult は src と dest を str として持つ。"""
     src = tmp_path / "x.html"
@@ -0,0 +1,108 @@
+//! 変換中に生成される付随ファイル（アセット）
+//!
+//! レンダラーが Markdown から相対パスで参照するファイルを蓄積し、
+//! ファイル変換 API が出力ディレクトリの [`ASSET_DIR`] へ書き出す。
+//! ファイル名は内容のハッシュで決まるため、同じ内容は同じ名前に集約される。
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::url;
+
+/// アセットを書き出す出力ディレクトリ直下のサブディレクトリ名。
+pub(crate) const ASSET_DIR: &str = "assets";
+
+/// 書き出し待ちのアセット 1 件。
+#[derive(Debug, Clone)]
+pub(crate) struct Asset {
+    /// [`ASSET_DIR`] 内のファイル名（例: `3f2a…c1.svg`）。
+    pub file_name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Asset {
+    /// 内容ハッシュからファイル名を決めてアセットを生成する。
+    pub fn new(bytes: Vec<u8>, ext: &str) -> Self {
+        let file_name = format!("{:016x}.{ext}", content_hash(&bytes));
+        Self { file_name, bytes }
+    }
+
+    /// Markdown から参照する相対パス（`assets/<file_name>`）。
+    pub fn href(&self) -> String {
+        format!("{ASSET_DIR}/{}", self.file_name)
+    }
+}
+
+/// ローカルの画像として複製する拡張子。
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "apng", "avif", "bmp", "gif", "ico", "jpeg", "jpg", "png", "svg", "tif", "tiff", "webp",
+];
+
+/// 相対パスで参照されたローカルの画像を探す場所。
+#[derive(Debug, Clone)]
+pub(crate) struct AssetRoot {
+    /// 参照を解決する起点（入力ファイルのディレクトリ）。
+    dir: PathBuf,
+    /// 読み込みを許すディレクトリ（正規化済み）。
+    scope: PathBuf,
+}
+
+impl AssetRoot {
+    /// 入力ファイル `source` のディレクトリを起点にする。`scope` はその中だけを
+    /// 読むディレクトリで、`None` なら起点と同じ。`scope` が存在しなければ `None`。
+    pub fn new(source: &Path, scope: Option<&Path>) -> Option<Self> {
+        let dir = match source.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let scope = scope.unwrap_or(&dir).canonicalize().ok()?;
+        Some(Self { dir, scope })
+    }
+
+    /// 相対パスで参照された画像ファイルを読み込んでアセットにする。
+    /// 相対パスでない参照、画像の拡張子でないファイル、読めないファイル、
+    /// `..` やシンボリックリンクで `scope` の外を指すファイルは `None`。
+    /// 信頼できない HTML に任意のファイルを出力へ複製させないため、拡張子と
+    /// 場所の両方で絞る。
+    pub fn local_image(&self, reference: &str) -> Option<Asset> {
+        let path = self
+            .dir
+            .join(url::relative_path(reference)?)
+            .canonicalize()
+            .ok()?;
+        if !path.starts_with(&self.scope) {
+            return None;
+        }
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        if !IMAGE_EXTENSIONS.contains(&ext.as_str()) || !path.is_file() {
+            return None;
+        }
+        let bytes = fs::read(&path).ok()?;
+        Some(Asset::new(bytes, &ext))
+    }
+}
+
+/// 64-bit FNV-1a。プラットフォームやビルドに依存しない安定したハッシュで、
+/// 再変換しても同じファイル名になることを保証する。
+pub(crate) fn content_hash(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+/// アセットを `out_dir/assets/` へ書き出す。アセットがなければ何もしない。
+pub(crate) fn write_assets(out_dir: &Path, assets: &[Asset]) -> io::Result<()> {
+    if assets.is_empty() {
+        return Ok(());
+    }
+    let dir = out_dir.join(ASSET_DIR);
+    fs::create_dir_all(&dir)?;
+    for asset in assets {
+        fs::write(dir.join(&asset.file_name), &asset.bytes)?;
+    }
+    Ok(())
+}
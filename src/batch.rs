@@ -0,0 +1,383 @@
+//! 複数文書をまとめて扱うバッチ変換
+//!
+//! [`html_files_to_markdown_with`](crate::html_files_to_markdown_with) は
+//! 文書を 1 件ずつ独立に変換する。バッチ変換はすべての文書をいったん
+//! Markdown にしてから、文書をまたいだ処理（サイト共通の定型ブロックの
+//! 除去、近似重複の検出）を行い、最後に書き出す。入力同士のリンクの
+//! 書き換え（サイト変換）は、変換の前に全入力を読んで索引を作る。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use scraper::Html;
+
+use crate::assets::content_hash;
+use crate::options::ConversionOptions;
+use crate::simhash::{markdown_simhash, simhash_similarity};
+use crate::site::{self, Dangling, Site};
+use crate::traversal::Rendered;
+use crate::{MdkaError, convert_linked, render_file, write_rendered, write_rendered_to};
+
+/// バッチ変換のオプション。既定ではどの処理も行わない。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchOptions {
+    /// 文書のこの割合（0.0〜1.0）を超える数に現れるブロックを定型文として
+    /// 取り除く。`None` なら取り除かない。2 文書以上に現れるブロックだけが対象。
+    pub boilerplate_threshold: Option<f64>,
+    /// 変換結果の類似度（[`simhash_similarity`]、0.0〜1.0）がこの値以上の
+    /// 文書を近似重複としてまとめる。`None` なら検出しない。無関係な文書
+    /// どうしでも類似度は 0.5 前後になるため、0.9 程度を目安にする。
+    pub near_duplicate_threshold: Option<f64>,
+    /// 入力をひとつのサイトとして変換するか。入力の共通の親ディレクトリからの
+    /// 相対パスを保って書き出し、入力同士のリンクを出力の `.md` へ、
+    /// `#fragment` を変換後の見出しのアンカーへ書き換える。入力の中に
+    /// 見つからないリンク先は [`BatchReport::dangling_links`] に報告する。
+    pub site_links: bool,
+}
+
+impl BatchOptions {
+    /// ビルダー: 定型ブロックとみなす出現割合を設定する。
+    pub fn boilerplate_threshold(mut self, v: f64) -> Self {
+        self.boilerplate_threshold = Some(v);
+        self
+    }
+
+    /// ビルダー: 近似重複とみなす類似度を設定する。
+    pub fn near_duplicate_threshold(mut self, v: f64) -> Self {
+        self.near_duplicate_threshold = Some(v);
+        self
+    }
+
+    /// ビルダー: サイト変換（入力同士のリンクの書き換え）を設定する。
+    pub fn site_links(mut self, v: bool) -> Self {
+        self.site_links = v;
+        self
+    }
+}
+
+/// 定型文として取り除いたブロック。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoilerplateBlock {
+    /// 取り除いたブロックの Markdown（最初に現れた文書のもの）。
+    pub markdown: String,
+    /// このブロックが現れた文書の数。
+    pub pages: usize,
+}
+
+/// 入力の中に見つからない内部リンク（サイト変換のみ）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingLink {
+    /// リンク元の [`BatchReport::files`] の添字。
+    pub file: usize,
+    /// 元の HTML に書かれていたリンク先。
+    pub href: String,
+    /// `true` ならリンク先の文書はあるが、`#fragment` に当たる要素がない。
+    /// `false` ならリンク先のファイル自体が入力にも実在のファイルにもない。
+    pub missing_fragment: bool,
+}
+
+/// バッチ変換の結果。
+#[derive(Debug)]
+pub struct BatchReport<'a, P> {
+    /// 入力ごとの結果。`paths` と同じ順序。
+    pub files: Vec<(&'a P, Result<PathBuf, MdkaError>)>,
+    /// 取り除いた定型ブロック。現れた文書の多い順。
+    pub boilerplate: Vec<BoilerplateBlock>,
+    /// 近似重複のグループ。各グループは `files` の添字を昇順に並べたもので、
+    /// 2 件以上を含む。グループは先頭の添字の順。
+    pub duplicates: Vec<Vec<usize>>,
+    /// 入力の中に見つからない内部リンク（`site_links` が有効なときのみ）。
+    /// `files` の順、同じ文書の中では文書順。
+    pub dangling_links: Vec<DanglingLink>,
+}
+
+/// 複数の HTML ファイルを文書横断の処理付きで変換し、`out_dir` へ書き出す。
+///
+/// 出力先のファイル名は [`html_files_to_markdown_with`] と同じ。ただし
+/// `site_links` が有効なら、入力の共通の親ディレクトリからの相対パスを保つ。
+/// 読み込みや変換に失敗した文書は、文書横断の処理の対象から外れる。
+///
+/// [`html_files_to_markdown_with`]: crate::html_files_to_markdown_with
+pub fn html_files_to_markdown_batch<'a, P>(
+    paths: &'a [P],
+    out_dir: &Path,
+    opts: &ConversionOptions,
+    batch: &BatchOptions,
+) -> BatchReport<'a, P>
+where
+    P: AsRef<Path> + Sync,
+{
+    let site = batch.site_links.then(|| Site::index(paths, opts));
+    // ローカルの画像は、入力の共通の親ディレクトリ（サイトのルート）の中から
+    // だけ複製する。ページ同士で画像ディレクトリを共有する `../img/` を許すため
+    let asset_scope = match &site {
+        Some(site) => Some(site.root().to_path_buf()),
+        None if opts.collect_local_assets => {
+            let inputs: Vec<PathBuf> = paths
+                .iter()
+                .filter_map(|p| p.as_ref().canonicalize().ok())
+                .collect();
+            (!inputs.is_empty()).then(|| site::common_dir(inputs.iter().map(PathBuf::as_path)))
+        }
+        None => None,
+    };
+    let asset_scope = asset_scope.as_deref();
+    let (mut rendered, dangling): (Vec<Result<Rendered, MdkaError>>, Vec<Vec<Dangling>>) = paths
+        .par_iter()
+        .enumerate()
+        .map(|(i, path)| match &site {
+            Some(site) => match render_site_page(site, i, path.as_ref(), asset_scope, opts) {
+                Ok((doc, dangling)) => (Ok(doc), dangling),
+                Err(e) => (Err(e), Vec::new()),
+            },
+            None => (render_file(path.as_ref(), asset_scope, opts), Vec::new()),
+        })
+        .unzip();
+    let dangling_links = dangling
+        .into_iter()
+        .enumerate()
+        .flat_map(|(file, links)| {
+            links.into_iter().map(move |d| DanglingLink {
+                file,
+                href: d.href,
+                missing_fragment: d.missing_fragment,
+            })
+        })
+        .collect();
+
+    let boilerplate = match batch.boilerplate_threshold {
+        Some(threshold) => remove_boilerplate(&mut rendered, threshold),
+        None => Vec::new(),
+    };
+    // 定型ブロックを除いた後の本文で比べる
+    let duplicates = match batch.near_duplicate_threshold {
+        Some(threshold) => group_near_duplicates(&rendered, threshold),
+        None => Vec::new(),
+    };
+
+    let files = paths
+        .par_iter()
+        .zip(rendered.into_par_iter())
+        .enumerate()
+        .map(|(i, (path, doc))| {
+            let output = site.as_ref().and_then(|site| site.output_path(i));
+            let result = doc.and_then(|doc| match output {
+                Some(output) => write_rendered_to(&out_dir.join(output), &doc),
+                None => write_rendered(path.as_ref(), out_dir, &doc),
+            });
+            (path, result)
+        })
+        .collect();
+
+    BatchReport {
+        files,
+        boilerplate,
+        duplicates,
+        dangling_links,
+    }
+}
+
+/// サイト変換の 1 文書を読み込み、入力同士のリンクを書き換えて変換する。
+fn render_site_page(
+    site: &Site,
+    index: usize,
+    src: &Path,
+    asset_scope: Option<&Path>,
+    opts: &ConversionOptions,
+) -> Result<(Rendered, Vec<Dangling>), MdkaError> {
+    let html = std::fs::read_to_string(src)?;
+    let document = Html::parse_document(&html);
+    let (links, dangling) = site.links(index, &document);
+    Ok((
+        convert_linked(&document, opts, true, Some(src), asset_scope, links),
+        dangling,
+    ))
+}
+
+// ─── 定型ブロックの除去 ───────────────────────────────────────────────────
+
+/// 文書の `threshold` 割合を超える数に現れるブロックを各文書から取り除き、
+/// 取り除いたブロックを返す。
+fn remove_boilerplate(
+    docs: &mut [Result<Rendered, MdkaError>],
+    threshold: f64,
+) -> Vec<BoilerplateBlock> {
+    let blocks: Vec<Option<Vec<String>>> = docs
+        .iter()
+        .map(|doc| doc.as_ref().ok().map(|d| split_blocks(&d.markdown)))
+        .collect();
+    let page_count = blocks.iter().flatten().count();
+
+    // 指紋 → (出現文書数, 初出順, 初出のブロック)
+    let mut seen: HashMap<u64, (usize, usize, &str)> = HashMap::new();
+    for doc_blocks in blocks.iter().flatten() {
+        let mut in_this_doc: Vec<u64> = doc_blocks.iter().map(|b| fingerprint(b)).collect();
+        in_this_doc.sort_unstable();
+        in_this_doc.dedup();
+        for fp in in_this_doc {
+            let order = seen.len();
+            seen.entry(fp).or_insert((0, order, "")).0 += 1;
+        }
+        for block in doc_blocks {
+            let entry = seen.get_mut(&fingerprint(block)).expect("counted above");
+            if entry.2.is_empty() {
+                entry.2 = block;
+            }
+        }
+    }
+
+    let limit = threshold * page_count as f64;
+    let mut dropped: Vec<(u64, usize, usize, &str)> = seen
+        .iter()
+        .filter(|(_, (pages, _, _))| *pages >= 2 && *pages as f64 > limit)
+        .map(|(&fp, &(pages, order, block))| (fp, pages, order, block))
+        .collect();
+    if dropped.is_empty() {
+        return Vec::new();
+    }
+    dropped.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+
+    let mut fingerprints: Vec<u64> = dropped.iter().map(|d| d.0).collect();
+    fingerprints.sort_unstable();
+    for (doc, doc_blocks) in docs.iter_mut().zip(&blocks) {
+        let (Ok(doc), Some(doc_blocks)) = (doc, doc_blocks) else {
+            continue;
+        };
+        let kept: Vec<&str> = doc_blocks
+            .iter()
+            .map(String::as_str)
+            .filter(|b| fingerprints.binary_search(&fingerprint(b)).is_err())
+            .collect();
+        doc.markdown = join_blocks(&kept);
+    }
+
+    dropped
+        .into_iter()
+        .map(|(_, pages, _, block)| BoilerplateBlock {
+            markdown: block.to_string(),
+            pages,
+        })
+        .collect()
+}
+
+/// Markdown を空行区切りのブロックに分ける。コードフェンスの中の空行では
+/// 分けない。
+fn split_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if line.trim().is_empty() && !in_fence {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+fn join_blocks(blocks: &[&str]) -> String {
+    if blocks.is_empty() {
+        return String::new();
+    }
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+/// 空白の違いを無視したブロックの指紋。
+fn fingerprint(block: &str) -> u64 {
+    let mut normalised = String::with_capacity(block.len());
+    for word in block.split_whitespace() {
+        if !normalised.is_empty() {
+            normalised.push(' ');
+        }
+        normalised.push_str(word);
+    }
+    content_hash(normalised.as_bytes())
+}
+
+// ─── 近似重複の検出 ───────────────────────────────────────────────────────
+
+/// 指紋の類似度が `threshold` 以上の文書の組を連結し、2 件以上のグループを返す。
+/// 読み込みや変換に失敗した文書と、本文が空の文書は対象外。
+///
+/// 全組を比べる代わりに、64 ビットの指紋を `k + 1` 個の帯に分け、帯の値が
+/// 一致する文書どうしだけを比べる（`k` は許されるハミング距離の上限）。
+/// 距離が `k` 以下の 2 つの指紋は、鳩の巣原理によりどれかの帯で必ず一致する
+/// ため、取りこぼしはない。
+fn group_near_duplicates(docs: &[Result<Rendered, MdkaError>], threshold: f64) -> Vec<Vec<usize>> {
+    let fingerprints: Vec<(usize, u64)> = docs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, doc)| {
+            let doc = doc.as_ref().ok()?;
+            (!doc.markdown.trim().is_empty()).then(|| (i, markdown_simhash(&doc.markdown)))
+        })
+        .collect();
+
+    // union-find（親は常に添字の小さい方）
+    let mut parent: Vec<usize> = (0..docs.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    // 類似度 1 − d/64 ≥ threshold ⇔ d ≤ 64(1 − threshold)。丸め誤差で
+    // 境界の組を落とさないよう少し広げ、最後は類似度そのもので判定する
+    let max_distance = (64.0 * (1.0 - threshold) + 1e-9).floor();
+    if max_distance < 0.0 {
+        return Vec::new();
+    }
+    let bands = (max_distance as usize + 1).min(64);
+    let mut buckets: HashMap<(usize, u64), Vec<(usize, u64)>> = HashMap::new();
+    for &(i, hash) in &fingerprints {
+        for band in 0..bands {
+            let (lo, hi) = (band * 64 / bands, (band + 1) * 64 / bands);
+            let mask = if hi - lo == 64 {
+                u64::MAX
+            } else {
+                ((1u64 << (hi - lo)) - 1) << lo
+            };
+            buckets
+                .entry((band, hash & mask))
+                .or_default()
+                .push((i, hash));
+        }
+    }
+    for bucket in buckets.values() {
+        for (n, &(i, a)) in bucket.iter().enumerate() {
+            for &(j, b) in &bucket[n + 1..] {
+                if simhash_similarity(a, b) >= threshold {
+                    let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                    if ri != rj {
+                        parent[ri.max(rj)] = ri.min(rj);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(i, _) in &fingerprints {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+    let mut groups: Vec<Vec<usize>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    groups.sort_by_key(|g| g[0]);
+    groups
+}
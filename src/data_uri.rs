@@ -0,0 +1,82 @@
+//! `data:` URI の画像の変換
+//!
+//! 保存したページやメールの書き出しは画像を `data:image/png;base64,…` として
+//! 埋め込む。そのまま書くと数 MB の 1 行になるため、[`DataUriPolicy`] に従って
+//! ファイルへ書き出すか、代替テキストにするか、取り除く。
+
+use scraper::node::Element;
+
+use crate::assets::Asset;
+use crate::options::DataUriPolicy;
+use crate::renderer::{ImageSize, MarkdownRenderer};
+use crate::{url, utils};
+
+/// `data:` URI か（スキームは大文字小文字を区別しない）。
+pub(crate) fn is_data_uri(src: &str) -> bool {
+    src.trim_start()
+        .get(..5)
+        .is_some_and(|s| s.eq_ignore_ascii_case("data:"))
+}
+
+/// `src` が `data:` URI の `<img>` をポリシーに従って出力する。
+pub(crate) fn render_image(renderer: &mut MarkdownRenderer, img: &Element, policy: DataUriPolicy) {
+    let src = img.attr("src").unwrap_or("");
+    let alt = img.attr("alt").unwrap_or("");
+    let title = img.attr("title");
+    let size = ImageSize::of(img);
+    match policy {
+        DataUriPolicy::Drop => {}
+        DataUriPolicy::AltText => {
+            if !alt.trim().is_empty() {
+                renderer.process_text(alt);
+            }
+        }
+        DataUriPolicy::File if renderer.collects_assets() => match decode_image(src) {
+            Some((bytes, ext)) => {
+                let asset = Asset::new(bytes, ext);
+                let href = asset.href();
+                renderer.add_asset(asset);
+                renderer.push_image(alt, &href, title, size);
+            }
+            None => renderer.push_image(alt, src, title, size),
+        },
+        _ => renderer.push_image(alt, src, title, size),
+    }
+}
+
+/// `data:image/<subtype>[;…][;base64],<data>` を復号し、内容と拡張子を返す。
+/// 画像でない URI や、Base64 として不正な URI は `None`。
+fn decode_image(uri: &str) -> Option<(Vec<u8>, &str)> {
+    let (header, data) = uri.trim().get(5..)?.split_once(',')?;
+    let mut params = header.split(';');
+    let mime = params.next()?.trim();
+    let subtype = mime
+        .get(..6)
+        .filter(|t| t.eq_ignore_ascii_case("image/"))
+        .and(mime.get(6..))?;
+    let ext = extension(subtype)?;
+    let bytes = if params.any(|p| p.trim().eq_ignore_ascii_case("base64")) {
+        utils::decode_base64(&url::percent_decode(data))?
+    } else {
+        url::percent_decode(data).into_bytes()
+    };
+    (!bytes.is_empty()).then_some((bytes, ext))
+}
+
+/// 画像の MIME サブタイプに対応する拡張子。
+fn extension(subtype: &str) -> Option<&'static str> {
+    let ext = match subtype.to_ascii_lowercase().as_str() {
+        "png" => "png",
+        "jpeg" | "jpg" | "pjpeg" => "jpg",
+        "gif" => "gif",
+        "webp" => "webp",
+        "avif" => "avif",
+        "apng" => "apng",
+        "svg+xml" => "svg",
+        "bmp" | "x-ms-bmp" => "bmp",
+        "x-icon" | "vnd.microsoft.icon" => "ico",
+        "tiff" => "tiff",
+        _ => return None,
+    };
+    Some(ext)
+}
@@ -0,0 +1,158 @@
+//! 絵文字の画像の変換
+//!
+//! GitHub・Slack の書き出し・Twemoji・WordPress は絵文字を
+//! `<img class="emoji" alt="😄">` や `<g-emoji alias="smile">` として描く。
+//! 画像のまま変換すると `![😄](https://…/1f604.png)` が本文に散らばるため、
+//! [`EmojiPolicy`] に従って文字か `:shortcode:` にする。
+
+use scraper::ElementRef;
+
+use crate::options::EmojiPolicy;
+use crate::renderer::{ImageSize, MarkdownRenderer};
+use crate::url;
+
+/// 絵文字の画像に付くクラス（WordPress の `wp-smiley`、Slack の `c-emoji` など）。
+const EMOJI_CLASSES: &[&str] = &["emoji", "wp-smiley", "twemoji", "c-emoji", "emoticon"];
+
+/// 絵文字の画像を配信するホストと、そのパスの接頭辞。
+const EMOJI_HOSTS: &[(&str, &str)] = &[
+    ("twemoji.maxcdn.com", "/"),
+    ("cdn.jsdelivr.net", "/gh/twitter/twemoji"),
+    ("cdn.jsdelivr.net", "/gh/jdecked/twemoji"),
+    ("cdnjs.cloudflare.com", "/ajax/libs/twemoji"),
+    ("s.w.org", "/images/core/emoji"),
+    ("github.githubassets.com", "/images/icons/emoji"),
+    ("assets-cdn.github.com", "/images/icons/emoji"),
+    ("emoji.slack-edge.com", "/"),
+    ("a.slack-edge.com", "/production-standard-emoji-assets"),
+];
+
+/// 絵文字を表す要素か。`<g-emoji>` と、絵文字のクラスを持つか絵文字 CDN の
+/// 画像を参照する `<img>`。
+pub(crate) fn is_emoji(elem: ElementRef) -> bool {
+    let e = elem.value();
+    match e.name() {
+        "g-emoji" => true,
+        "img" => {
+            e.classes()
+                .any(|c| EMOJI_CLASSES.iter().any(|k| c.eq_ignore_ascii_case(k)))
+                || e.attr("src").is_some_and(is_emoji_url)
+        }
+        _ => false,
+    }
+}
+
+/// 絵文字をポリシーに従って出力する。文字も名前も分からなければ、
+/// `<img>` は画像のまま、`<g-emoji>` は中身のテキストを出力する。
+pub(crate) fn render_emoji(renderer: &mut MarkdownRenderer, elem: ElementRef, policy: EmojiPolicy) {
+    let shortcode = || shortcode(elem).map(|name| format!(":{name}:"));
+    match policy {
+        EmojiPolicy::Shortcode => match shortcode().or_else(|| unicode(elem)) {
+            Some(s) => renderer.push_inline_markdown(&s),
+            None => render_fallback(renderer, elem),
+        },
+        _ => match unicode(elem) {
+            Some(s) => renderer.process_text(&s),
+            None => match shortcode() {
+                Some(s) => renderer.push_inline_markdown(&s),
+                None => render_fallback(renderer, elem),
+            },
+        },
+    }
+}
+
+fn render_fallback(renderer: &mut MarkdownRenderer, elem: ElementRef) {
+    let e = elem.value();
+    if e.name() == "img" {
+        let src = e.attr("src").unwrap_or("");
+        renderer.push_image(
+            e.attr("alt").unwrap_or(""),
+            src,
+            e.attr("title"),
+            ImageSize::of(e),
+        );
+    } else {
+        renderer.process_text(&elem.text().collect::<String>());
+    }
+}
+
+/// 絵文字 CDN の画像の URL か。
+fn is_emoji_url(src: &str) -> bool {
+    url::host_and_path(src).is_some_and(|(host, path)| {
+        EMOJI_HOSTS
+            .iter()
+            .any(|(h, prefix)| host.eq_ignore_ascii_case(h) && path.starts_with(prefix))
+    })
+}
+
+/// 絵文字の文字。`alt`（`<g-emoji>` は中身）が文字ならそれを、そうでなければ
+/// 画像のファイル名のコードポイント（`1f468-200d-1f469.png`）から復元する。
+fn unicode(elem: ElementRef) -> Option<String> {
+    let e = elem.value();
+    let (text, src) = if e.name() == "img" {
+        (e.attr("alt").unwrap_or("").to_string(), e.attr("src"))
+    } else {
+        (elem.text().collect(), e.attr("fallback-src"))
+    };
+    let text = text.trim();
+    if is_emoji_text(text) {
+        return Some(text.to_string());
+    }
+    src.and_then(codepoints_from_file_name)
+}
+
+/// 絵文字の名前（`:` を除く）。`<g-emoji alias>`、Slack の
+/// `data-stringify-emoji`、`:name:` 形式の `alt` / `title` の順に探す。
+fn shortcode<'a>(elem: ElementRef<'a>) -> Option<&'a str> {
+    let e = elem.value();
+    if let Some(alias) = e.attr("alias").map(str::trim)
+        && is_shortcode_name(alias)
+    {
+        return Some(alias);
+    }
+    ["data-stringify-emoji", "alt", "title"]
+        .into_iter()
+        .filter_map(|name| e.attr(name))
+        .find_map(|v| {
+            v.trim()
+                .strip_prefix(':')?
+                .strip_suffix(':')
+                .filter(|name| is_shortcode_name(name))
+        })
+}
+
+/// 絵文字の文字として出力できるテキストか。ASCII 以外の文字を含み、
+/// 制御文字・空白を含まず、`:name:` 形式でないもの。
+fn is_emoji_text(text: &str) -> bool {
+    !text.is_empty()
+        && !text.is_ascii()
+        && !text.starts_with(':')
+        && !text.chars().any(|c| c.is_control() || c.is_whitespace())
+}
+
+/// `[A-Za-z0-9_+-]+`
+fn is_shortcode_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'+' | b'-'))
+}
+
+/// `…/1f604.png` や `…/1f44d-1f3fd@2x.png` のファイル名を `-` 区切りの
+/// コードポイントとして読む。
+fn codepoints_from_file_name(src: &str) -> Option<String> {
+    let path = src.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    let stem = name.split(['.', '@']).next()?;
+    let text = stem
+        .split('-')
+        .map(|hex| {
+            (1..=6)
+                .contains(&hex.len())
+                .then(|| u32::from_str_radix(hex, 16).ok())
+                .flatten()
+                .and_then(char::from_u32)
+        })
+        .collect::<Option<String>>()?;
+    is_emoji_text(&text).then_some(text)
+}
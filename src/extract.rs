@@ -0,0 +1,369 @@
+//! 本文抽出（Mozilla Readability 方式のスコアリング）
+//!
+//! 段落相当の要素ごとにテキスト量と読点の数からスコアを求め、祖先の
+//! コンテナに加算する。コンテナのスコアは class / id の語（`content`、
+//! `sidebar` など）で補正し、リンク密度が高いほど割り引く。最高点の
+//! コンテナを本文のルートとする。
+//!
+//! 変換時は選ばれたルートの中から、さらにサイドバー・コメント・関連記事・
+//! 共有ボタンなどの付随要素（[`clutter_nodes`]）を取り除く。
+//!
+//! 要素ごとのテキスト量とリンク内の文字数は、最初に 1 回の後順走査で
+//! 集計して（[`TextStats`]）使い回す。要素ごとに子孫をたどり直すと、
+//! 入れ子の深い文書で二乗の時間がかかるため。
+
+use std::collections::{HashMap, HashSet};
+
+use ego_tree::iter::Edge;
+use ego_tree::{NodeId, NodeRef};
+use scraper::{ElementRef, Html, Node};
+
+/// 本文らしさを示す class / id の語（小文字、部分一致）。
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "text", "blog", "story",
+];
+
+/// 本文以外を示す class / id の語（小文字、部分一致）。
+const NEGATIVE_HINTS: &[&str] = &[
+    "banner", "combx", "comment", "contact", "footer", "masthead", "meta", "outbrain", "promo",
+    "related", "share", "sidebar", "social", "sponsor", "widget", "advert", "popup", "nav",
+];
+
+/// 本文内でも取り除く付随要素を示す class / id（小文字）。部分一致では
+/// `has-sidebar` や `comment-body` のような本文側の名前まで拾うため、
+/// class の語・id の全体と一致するものだけを対象にする。
+const CLUTTER_HINTS: &[&str] = &[
+    "sidebar",
+    "comments",
+    "comment-list",
+    "commentlist",
+    "comments-area",
+    "comment-respond",
+    "disqus_thread",
+    "replies",
+    "related",
+    "related-posts",
+    "related-articles",
+    "share",
+    "sharing",
+    "share-buttons",
+    "sharedaddy",
+    "social",
+    "social-share",
+    "newsletter",
+    "newsletter-signup",
+    "subscribe",
+    "promo",
+    "sponsor",
+    "sponsored",
+    "advert",
+    "advertisement",
+    "breadcrumb",
+    "breadcrumbs",
+    "pagination",
+    "pager",
+    "popup",
+];
+
+/// 段落として採点する最小の文字数。
+const MIN_PARAGRAPH_LEN: usize = 25;
+
+/// スコアを加算する祖先の段数。
+const MAX_ANCESTORS: usize = 5;
+
+/// 文書から本文のルート要素を選ぶ。
+///
+/// 段落相当の要素が見つからない、または `<body>` より内側に本文らしい
+/// コンテナがない場合は `None` を返す。
+pub fn extract_main_content(document: &Html) -> Option<ElementRef<'_>> {
+    let stats = text_stats(document.tree.root());
+    let link_density = |node: NodeRef<Node>| stats[&node.id()].link_density();
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        if !is_paragraph_like(node, elem.name()) {
+            continue;
+        }
+        let text = stats[&node.id()];
+        if text.chars < MIN_PARAGRAPH_LEN {
+            continue;
+        }
+        let score = 1.0 + text.commas as f64 + (text.chars as f64 / 100.0).min(3.0);
+
+        for (level, ancestor) in node
+            .ancestors()
+            .filter(|a| a.value().is_element())
+            .take(MAX_ANCESTORS)
+            .enumerate()
+        {
+            let divider = match level {
+                0 => 1.0,
+                1 => 2.0,
+                n => n as f64 * 3.0,
+            };
+            let entry = scores
+                .entry(ancestor.id())
+                .or_insert_with(|| initial_score(ancestor));
+            *entry += score / divider;
+        }
+    }
+
+    // 同点なら文書順で先の（外側の）コンテナを選ぶよう、文書順に比較する
+    let mut best: Option<(NodeRef<Node>, f64)> = None;
+    for node in document.tree.root().descendants() {
+        let Some(&raw) = scores.get(&node.id()) else {
+            continue;
+        };
+        let score = raw * (1.0 - link_density(node));
+        if best.is_none_or(|(_, s)| score > s) {
+            best = Some((node, score));
+        }
+    }
+    let (mut top, top_score) = best?;
+
+    // 親のスコアが上回るなら、本文は兄弟にも分かれているとみなして親を選ぶ
+    // （Readability と同じく、スコアが 3 分の 1 を下回る親で打ち切る）
+    let threshold = top_score / 3.0;
+    let mut last = top_score;
+    let mut parent = top.parent();
+    while let Some(p) = parent {
+        if is_root_tag(p) {
+            break;
+        }
+        let Some(&raw) = scores.get(&p.id()) else {
+            parent = p.parent();
+            continue;
+        };
+        let score = raw * (1.0 - link_density(p));
+        if score < threshold {
+            break;
+        }
+        if score > last {
+            top = p;
+            break;
+        }
+        last = score;
+        parent = p.parent();
+    }
+
+    // 唯一の子要素として包まれているだけなら外側を選ぶ（見出しなどを含めるため、
+    // 同じ親に見出しがある場合も外側へ広げる）
+    while let Some(p) = top.parent() {
+        if is_root_tag(p) {
+            break;
+        }
+        let siblings: Vec<_> = p
+            .children()
+            .filter(|c| c.value().is_element() && c.id() != top.id())
+            .collect();
+        let only_child = siblings.is_empty();
+        let titled = siblings.iter().any(|s| {
+            s.value()
+                .as_element()
+                .is_some_and(|e| matches!(e.name(), "h1" | "h2" | "header"))
+        });
+        if !(only_child || (titled && link_density(p) < 0.3)) {
+            break;
+        }
+        top = p;
+    }
+
+    if is_root_tag(top) {
+        return None;
+    }
+    ElementRef::wrap(top)
+}
+
+/// 本文のルート `root` の中から取り除く付随要素。
+///
+/// ルートのテキストの半分以上を含む要素は、名前が付随要素らしくても
+/// 本文とみなして残す。
+pub(crate) fn clutter_nodes(root: NodeRef<Node>) -> HashSet<NodeId> {
+    let stats = text_stats(root);
+    let total = stats[&root.id()].visible;
+    let mut clutter = HashSet::new();
+    let mut stack: Vec<_> = root.children().collect();
+    while let Some(node) = stack.pop() {
+        let text = stats[&node.id()];
+        if is_clutter(node, text) && text.visible * 2 < total.max(1) {
+            clutter.insert(node.id());
+        } else {
+            stack.extend(node.children());
+        }
+    }
+    clutter
+}
+
+/// class / id が付随要素を示すコンテナと、リンクばかりのリスト
+/// （関連記事の一覧など）か。
+fn is_clutter(node: NodeRef<Node>, text: TextStats) -> bool {
+    let Some(elem) = node.value().as_element() else {
+        return false;
+    };
+    let tag = elem.name();
+    // nav / header / footer / aside そのものは drop_interactive_shell の担当
+    if !matches!(
+        tag,
+        "div" | "section" | "aside" | "ul" | "ol" | "footer" | "table" | "form"
+    ) {
+        return false;
+    }
+    let hints = hint_text(elem);
+    if hints
+        .split_ascii_whitespace()
+        .any(|t| CLUTTER_HINTS.contains(&t))
+    {
+        return true;
+    }
+    matches!(tag, "ul" | "ol")
+        && !POSITIVE_HINTS.iter().any(|h| hints.contains(h))
+        && text.link_density() > 0.5
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+fn is_root_tag(node: NodeRef<Node>) -> bool {
+    node.value()
+        .as_element()
+        .is_none_or(|e| matches!(e.name(), "body" | "html"))
+}
+
+/// 採点対象の段落相当要素か。ブロック要素を子に持たない `div` も含める。
+fn is_paragraph_like(node: NodeRef<Node>, tag: &str) -> bool {
+    match tag {
+        "p" | "pre" | "td" | "blockquote" => true,
+        "div" => !node.children().any(|c| {
+            c.value().as_element().is_some_and(|e| {
+                matches!(
+                    e.name(),
+                    "p" | "div"
+                        | "ul"
+                        | "ol"
+                        | "table"
+                        | "pre"
+                        | "blockquote"
+                        | "section"
+                        | "article"
+                        | "h1"
+                        | "h2"
+                        | "h3"
+                        | "h4"
+                        | "h5"
+                        | "h6"
+                )
+            })
+        }),
+        _ => false,
+    }
+}
+
+/// コンテナの初期スコア: タグの種類と class / id の語による補正。
+fn initial_score(node: NodeRef<Node>) -> f64 {
+    let Some(elem) = node.value().as_element() else {
+        return 0.0;
+    };
+    let tag_score = match elem.name() {
+        "article" | "main" => 10.0,
+        "div" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    let hints = hint_text(elem);
+    let mut weight = 0.0;
+    if NEGATIVE_HINTS.iter().any(|h| hints.contains(h)) {
+        weight -= 25.0;
+    }
+    if POSITIVE_HINTS.iter().any(|h| hints.contains(h)) {
+        weight += 25.0;
+    }
+    tag_score + weight
+}
+
+/// class と id を小文字で連結した文字列。
+fn hint_text(elem: &scraper::node::Element) -> String {
+    let mut hints = String::new();
+    if let Some(class) = elem.attr("class") {
+        hints.push_str(class);
+    }
+    if let Some(id) = elem.id() {
+        hints.push(' ');
+        hints.push_str(id);
+    }
+    hints.make_ascii_lowercase();
+    hints
+}
+
+/// 要素の子孫のテキスト量。
+#[derive(Clone, Copy, Default)]
+struct TextStats {
+    /// 文字数（`script` / `style` の中身を除く。以下 `commas` まで同じ）。
+    chars: usize,
+    /// 空白以外の文字数。
+    visible: usize,
+    /// 読点の数。
+    commas: usize,
+    /// テキストノードごとに前後の空白を除いた文字数の合計。
+    trimmed: usize,
+    /// `trimmed` のうちリンク内にある文字数。
+    linked: usize,
+}
+
+impl TextStats {
+    fn add(&mut self, other: Self) {
+        self.chars += other.chars;
+        self.visible += other.visible;
+        self.commas += other.commas;
+        self.trimmed += other.trimmed;
+        self.linked += other.linked;
+    }
+
+    /// テキストのうちリンク内にある割合（0.0〜1.0）。
+    fn link_density(self) -> f64 {
+        if self.trimmed == 0 {
+            0.0
+        } else {
+            self.linked as f64 / self.trimmed as f64
+        }
+    }
+}
+
+/// `root` 以下の全ノードのテキスト量を後順走査 1 回で集計する。
+fn text_stats(root: NodeRef<Node>) -> HashMap<NodeId, TextStats> {
+    let mut stats: HashMap<NodeId, TextStats> = HashMap::new();
+    for edge in root.traverse() {
+        let Edge::Close(node) = edge else {
+            continue;
+        };
+        let mut s = TextStats::default();
+        match node.value() {
+            Node::Text(t) => {
+                s.trimmed = t.trim().chars().count();
+                let in_script = node.parent().is_some_and(|p| {
+                    p.value()
+                        .as_element()
+                        .is_some_and(|e| matches!(e.name(), "script" | "style"))
+                });
+                if !in_script {
+                    s.chars = t.chars().count();
+                    s.visible = t.chars().filter(|c| !c.is_whitespace()).count();
+                    s.commas = t.matches([',', '、', '，']).count();
+                }
+            }
+            _ => {
+                for child in node.children() {
+                    s.add(stats[&child.id()]);
+                }
+                if node.value().as_element().is_some_and(|e| e.name() == "a") {
+                    s.linked = s.trimmed;
+                }
+            }
+        }
+        stats.insert(node.id(), s);
+    }
+    stats
+}
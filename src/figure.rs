@@ -0,0 +1,122 @@
+//! `<figure>` と `<figcaption>` の変換
+//!
+//! 説明（`figcaption`）は HTML 上の位置にかかわらず図の内容の後に、斜体の
+//! 1 行として出力する。ただしリストや表などを含む説明は斜体にできないため、
+//! 通常のブロックとして出力する。図の画像がちょうど 1 つなら、その画像の
+//! `alt`（`alt` があれば `title`）を説明文で補う。
+
+use ego_tree::NodeRef;
+use ego_tree::iter::Edge;
+use scraper::Node;
+
+/// 説明の中で中身に置き換える（行内として扱う）ブロック要素。
+const CAPTION_BLOCKS: &[&str] = &["p", "div"];
+
+/// 斜体の 1 行にまとめられないブロック要素。
+const BLOCK_CONTENT: &[&str] = &[
+    "ul",
+    "ol",
+    "dl",
+    "table",
+    "blockquote",
+    "pre",
+    "hr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+];
+
+/// テキストにするとき前後を空白で区切る要素。
+const SEPARATED: &[&str] = &["br", "li", "dt", "dd", "tr", "td", "th", "caption"];
+
+/// [`caption_content`] が返す説明の中身の 1 要素。
+pub(crate) enum CaptionPart<'a> {
+    Node(NodeRef<'a, Node>),
+    /// 平らにしたブロックの区切り（空白 1 つ）。
+    Break,
+}
+
+/// `figure` の直下の最初の `figcaption`。
+pub(crate) fn caption(figure: NodeRef<'_, Node>) -> Option<NodeRef<'_, Node>> {
+    figure.children().find(|child| {
+        child
+            .value()
+            .as_element()
+            .is_some_and(|e| e.name() == "figcaption")
+    })
+}
+
+/// 画像を補う説明文（空白を畳んだテキスト）。説明の外にある画像
+/// （`img` / `svg`）がちょうど 1 つで、説明文が空でないときだけ返す。
+pub(crate) fn image_caption(figure: NodeRef<Node>, caption: NodeRef<Node>) -> Option<String> {
+    let images = figure
+        .descendants()
+        .filter(|node| {
+            node.value()
+                .as_element()
+                .is_some_and(|e| matches!(e.name(), "img" | "svg"))
+                && !node.ancestors().any(|a| a.id() == caption.id())
+        })
+        .count();
+    if images != 1 {
+        return None;
+    }
+    let text = caption_text(caption);
+    (!text.is_empty()).then_some(text)
+}
+
+/// 説明文のテキスト（空白を畳む）。ブロック同士の間は空白で区切る。
+pub(crate) fn caption_text(caption: NodeRef<Node>) -> String {
+    let mut text = String::new();
+    for edge in caption.traverse() {
+        let (Edge::Open(node) | Edge::Close(node)) = edge;
+        match node.value() {
+            Node::Text(t) if matches!(edge, Edge::Open(_)) => text.push_str(t),
+            Node::Element(e)
+                if node.id() != caption.id()
+                    && [CAPTION_BLOCKS, BLOCK_CONTENT, SEPARATED]
+                        .iter()
+                        .any(|tags| tags.contains(&e.name())) =>
+            {
+                text.push(' ');
+            }
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 説明がリストや表など、斜体の 1 行にできないブロックを含むか。
+pub(crate) fn has_block_content(caption: NodeRef<Node>) -> bool {
+    caption.descendants().any(|node| {
+        node.value()
+            .as_element()
+            .is_some_and(|e| BLOCK_CONTENT.contains(&e.name()))
+    })
+}
+
+/// 説明の中身を行内の並びとして返す。段落などのブロック要素は、斜体が
+/// 段落をまたがないよう、前後を区切りにして中身に置き換える。
+pub(crate) fn caption_content(caption: NodeRef<'_, Node>) -> Vec<CaptionPart<'_>> {
+    let mut content = Vec::new();
+    let mut stack: Vec<_> = caption.children().rev().map(CaptionPart::Node).collect();
+    while let Some(part) = stack.pop() {
+        match part {
+            CaptionPart::Node(node)
+                if node
+                    .value()
+                    .as_element()
+                    .is_some_and(|e| CAPTION_BLOCKS.contains(&e.name())) =>
+            {
+                stack.push(CaptionPart::Break);
+                stack.extend(node.children().rev().map(CaptionPart::Node));
+                stack.push(CaptionPart::Break);
+            }
+            part => content.push(part),
+        }
+    }
+    content
+}
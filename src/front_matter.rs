@@ -0,0 +1,199 @@
+//! front matter（YAML / TOML）の生成
+//!
+//! [`DocumentMetadata`] から [`ConversionOptions::front_matter_fields`] の
+//! 項目を順に書き出す。値は常に引用符付きの文字列（タグは文字列の配列）とし、
+//! YAML と TOML で同じエスケープ規則を使う。構造化データだけは入れ子の値として、
+//! YAML ではフロー形式（JSON と同じ表記）、TOML ではインラインテーブルで書き出す。
+//!
+//! TOML も YAML もキーの重複を許さないため、同じキーは最初の値だけを書き出す。
+//! 項目のキー名（改名で衝突しうる）と構造化データ内のオブジェクトのキーの両方が対象。
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::json::{self, JsonValue};
+use crate::metadata::DocumentMetadata;
+use crate::options::{ConversionOptions, FrontMatterField, FrontMatterFormat, MetadataField};
+
+/// front matter を生成する。形式が `None` か、書き出す値が 1 つもなければ空文字列。
+///
+/// `source` はファイル変換 API でのみ渡す入力パス。
+pub(crate) fn render(
+    meta: &DocumentMetadata,
+    source: Option<&Path>,
+    opts: &ConversionOptions,
+) -> String {
+    let (fence, separator) = match opts.front_matter {
+        FrontMatterFormat::Yaml => ("---", ": "),
+        FrontMatterFormat::Toml => ("+++", " = "),
+        FrontMatterFormat::None => return String::new(),
+    };
+
+    let defaults: Vec<FrontMatterField>;
+    let fields = if opts.front_matter_fields.is_empty() {
+        defaults = MetadataField::DEFAULT
+            .iter()
+            .map(|&f| FrontMatterField::new(f))
+            .collect();
+        &defaults
+    } else {
+        &opts.front_matter_fields
+    };
+
+    let mut body = String::new();
+    let mut written: HashSet<&str> = HashSet::new();
+    for entry in fields {
+        if written.contains(entry.key.as_str()) {
+            continue;
+        }
+        let value = match entry.field {
+            MetadataField::Tags => {
+                if meta.keywords.is_empty() {
+                    continue;
+                }
+                let items: Vec<String> = meta.keywords.iter().map(|k| quote(k)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            MetadataField::StructuredData => {
+                let data = &meta.structured_data;
+                if data.is_empty() {
+                    continue;
+                }
+                let items: Vec<JsonValue> = data
+                    .json_ld
+                    .iter()
+                    .chain(&data.microdata)
+                    .chain(&data.microformats)
+                    .map(unique_keys)
+                    .collect();
+                match opts.front_matter {
+                    FrontMatterFormat::Toml => {
+                        let items: Vec<String> = items.iter().filter_map(toml_value).collect();
+                        format!("[{}]", items.join(", "))
+                    }
+                    _ => JsonValue::Array(items).to_string(),
+                }
+            }
+            MetadataField::Source => match source {
+                Some(path) => quote(&path.to_string_lossy()),
+                None => continue,
+            },
+            field => match scalar(meta, field) {
+                Some(v) => quote(v),
+                None => continue,
+            },
+        };
+        let _ = writeln!(body, "{}{separator}{value}", key(&entry.key));
+        written.insert(&entry.key);
+    }
+
+    if body.is_empty() {
+        return String::new();
+    }
+    format!("{fence}\n{body}{fence}\n")
+}
+
+/// front matter と本文をつなぐ。本文が空なら front matter だけを返す。
+pub(crate) fn prepend(front_matter: &str, markdown: &str) -> String {
+    if front_matter.is_empty() {
+        return markdown.to_string();
+    }
+    if markdown.is_empty() {
+        return front_matter.to_string();
+    }
+    format!("{front_matter}\n{markdown}")
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+fn scalar(meta: &DocumentMetadata, field: MetadataField) -> Option<&str> {
+    let value = match field {
+        MetadataField::Title => &meta.title,
+        MetadataField::Description => &meta.description,
+        MetadataField::CanonicalUrl => &meta.canonical_url,
+        MetadataField::Date => {
+            if meta.published.is_some() {
+                &meta.published
+            } else {
+                &meta.modified
+            }
+        }
+        MetadataField::Modified => &meta.modified,
+        MetadataField::Author => &meta.author,
+        MetadataField::Lang => &meta.lang,
+        MetadataField::SiteName => &meta.site_name,
+        MetadataField::Image => &meta.image,
+        MetadataField::Tags | MetadataField::Source | MetadataField::StructuredData => {
+            return None;
+        }
+    };
+    value.as_deref()
+}
+
+/// 英数字・`_`・`-` だけのキーはそのまま、それ以外は引用符で囲む
+/// （YAML のプレーンスカラー・TOML のベアキーとして安全な範囲）。
+fn key(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if bare { name.to_string() } else { quote(name) }
+}
+
+/// YAML のダブルクォート文字列・TOML の基本文字列・JSON の文字列に共通する
+/// エスケープで囲む。
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    let _ = json::write_quoted(&mut out, value);
+    out
+}
+
+/// オブジェクトの重複キーを最初の値だけに絞ったコピー（[`JsonValue::get`] と同じ優先順）。
+fn unique_keys(value: &JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Array(items) => JsonValue::Array(items.iter().map(unique_keys).collect()),
+        JsonValue::Object(entries) => {
+            let mut seen = HashSet::new();
+            JsonValue::Object(
+                entries
+                    .iter()
+                    .filter(|(k, _)| seen.insert(k.as_str()))
+                    .map(|(k, v)| (k.clone(), unique_keys(v)))
+                    .collect(),
+            )
+        }
+        other => other.clone(),
+    }
+}
+
+/// TOML のインライン値。TOML に null はないため、null（と有限でない数値）は
+/// 書き出さず、配列・テーブルからもその要素を省く。
+fn toml_value(value: &JsonValue) -> Option<String> {
+    Some(match value {
+        JsonValue::Null => return None,
+        JsonValue::Bool(b) => b.to_string(),
+        JsonValue::Number(n) if !n.is_finite() => return None,
+        JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => format!("{}", *n as i64),
+        JsonValue::Number(n) => format!("{n:?}"),
+        JsonValue::String(s) => quote(s),
+        JsonValue::Array(items) => {
+            let items: Vec<String> = items.iter().filter_map(toml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        JsonValue::Object(entries) => {
+            let mut out = String::from("{");
+            for (k, v) in entries {
+                let Some(v) = toml_value(v) else {
+                    continue;
+                };
+                if out.len() > 1 {
+                    out.push(',');
+                }
+                let _ = write!(out, " {} = {v}", key(k));
+            }
+            out.push_str(if out.len() > 1 { " }" } else { "}" });
+            out
+        }
+    })
+}
@@ -0,0 +1,445 @@
+//! SPA のハイドレーション用データからの本文の復元
+//!
+//! クライアント描画のページ（Next.js、Nuxt、Gatsby など）は、本文を DOM ではなく
+//! `<script type="application/json">` や `window.__NUXT__ = {...}` の JSON に
+//! 入れて配信する。`<script>` は変換で丸ごと読み飛ばすため、そのままでは
+//! Markdown がほぼ空になる。
+//!
+//! ここではそれらの JSON を読み、HTML らしい文字列と、リッチテキストの
+//! 構造（Contentful の Rich Text、Sanity の Portable Text）を文書順に集めて
+//! HTML 断片にする。JavaScript の式は評価しないため、JSON として
+//! 読めない埋め込み（関数呼び出しで包んだ `__NUXT__` など）は対象外。
+//!
+//! DOM の本文が空のシェルならその断片で置き換え、そうでなければ DOM に
+//! まだ表示されていない断片だけを書き足す。
+
+use std::collections::HashSet;
+
+use scraper::Html;
+
+use crate::json::JsonValue;
+use crate::options::ConversionOptions;
+
+/// DOM 側の本文。ハイドレーション用データの断片と比べるため、表示される
+/// 文字列を空白を 1 つにまとめて持つ。
+pub(crate) struct DomBody {
+    text: String,
+    has_image: bool,
+}
+
+impl DomBody {
+    pub(crate) fn of(document: &Html, opts: &ConversionOptions) -> Self {
+        let mut body = DomBody {
+            text: String::new(),
+            has_image: false,
+        };
+        body.push(document, opts);
+        body
+    }
+
+    /// 文字列も画像もない、空のアプリケーションルートだけの本文か。
+    pub(crate) fn is_empty(&self) -> bool {
+        self.text.is_empty() && !self.has_image
+    }
+
+    /// `fragment` の文字列がすでに本文に表示されているか。サーバー描画の
+    /// ページは、同じ本文をハイドレーション用データにも入れて配信する。
+    pub(crate) fn contains(&self, fragment: &Html, opts: &ConversionOptions) -> bool {
+        self.text.contains(&DomBody::of(fragment, opts).text)
+    }
+
+    /// スキップ対象の要素（`<script>` など）の中は数えない。
+    fn push(&mut self, document: &Html, opts: &ConversionOptions) {
+        let mut stack = vec![document.tree.root()];
+        while let Some(node) = stack.pop() {
+            match node.value() {
+                scraper::Node::Text(text) => {
+                    for word in text.split_whitespace() {
+                        if !self.text.is_empty() {
+                            self.text.push(' ');
+                        }
+                        self.text.push_str(word);
+                    }
+                }
+                scraper::Node::Element(elem) => {
+                    if opts.tag_rules.is_skip(elem.name()) {
+                        continue;
+                    }
+                    self.has_image |= elem.name() == "img";
+                    stack.extend(node.children().rev());
+                }
+                _ => stack.extend(node.children().rev()),
+            }
+        }
+    }
+}
+
+/// ハイドレーション用データから本文の HTML 断片を文書順に集める。
+pub(crate) fn payload_fragments(document: &Html) -> Vec<String> {
+    let mut fragments = Vec::new();
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        if elem.name() != "script" {
+            continue;
+        }
+        let text: String = node
+            .children()
+            .filter_map(|c| c.value().as_text().map(|t| &**t))
+            .collect();
+        let payload = if is_json_script(elem) {
+            JsonValue::parse(text.trim())
+        } else if elem.attr("type").is_none_or(is_javascript) {
+            assignment_payload(&text)
+        } else {
+            None
+        };
+        if let Some(payload) = payload {
+            collect(&payload, &mut fragments);
+        }
+    }
+
+    // 同じ本文が複数の場所（ページとキャッシュなど）に入っていることが多い
+    let mut seen = HashSet::new();
+    fragments.retain(|f| seen.insert(f.clone()));
+    fragments
+}
+
+// ─── 埋め込みの検出 ───────────────────────────────────────────────────────
+
+/// `application/json`（`__NEXT_DATA__`、`__NUXT_DATA__` など）。
+/// JSON-LD は構造化データとして別に扱う。
+fn is_json_script(elem: &scraper::node::Element) -> bool {
+    elem.attr("type").is_some_and(|t| {
+        t.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .eq_ignore_ascii_case("application/json")
+    })
+}
+
+fn is_javascript(ty: &str) -> bool {
+    let ty = ty.trim();
+    ty.is_empty()
+        || ty.eq_ignore_ascii_case("text/javascript")
+        || ty.eq_ignore_ascii_case("application/javascript")
+        || ty.eq_ignore_ascii_case("module")
+}
+
+/// `window.__NUXT__ = {...};` のような、グローバル変数への JSON の代入。
+fn assignment_payload(script: &str) -> Option<JsonValue> {
+    let s = script.trim();
+    let s = ["window.", "self.", "globalThis.", "var ", "let ", "const "]
+        .iter()
+        .find_map(|p| s.strip_prefix(p))
+        .unwrap_or(s);
+    let (name, value) = s.split_once('=')?;
+    let name = name.trim();
+    let is_ident = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'$');
+    if !is_ident {
+        return None;
+    }
+    let value = value.trim().trim_end_matches(';').trim_end();
+    if !value.starts_with(['{', '[']) {
+        return None;
+    }
+    JsonValue::parse(value)
+}
+
+// ─── 本文の収集 ───────────────────────────────────────────────────────────
+
+/// JSON を文書順に辿り、本文らしい値を HTML 断片として集める。
+fn collect(value: &JsonValue, out: &mut Vec<String>) {
+    match value {
+        JsonValue::String(s) if looks_like_html(s) => out.push(s.trim().to_string()),
+        JsonValue::Array(items) => {
+            if items.iter().any(is_portable_text_block) {
+                out.push(portable_text(items));
+                return;
+            }
+            for item in items {
+                collect(item, out);
+            }
+        }
+        JsonValue::Object(entries) => {
+            if value.get("nodeType").and_then(JsonValue::as_str) == Some("document") {
+                let mut html = String::new();
+                rich_text(value, false, &mut html);
+                out.push(html);
+                return;
+            }
+            for (_, v) in entries {
+                collect(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 本文を表す HTML らしい文字列か。既知の要素のタグと、タグ以外のテキストを含むこと。
+fn looks_like_html(s: &str) -> bool {
+    const TAGS: &[&str] = &[
+        "p",
+        "div",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "ul",
+        "ol",
+        "li",
+        "a",
+        "strong",
+        "em",
+        "b",
+        "i",
+        "br",
+        "img",
+        "table",
+        "blockquote",
+        "pre",
+        "code",
+        "section",
+        "article",
+        "figure",
+    ];
+    let has_tag = s.match_indices('<').any(|(i, _)| {
+        let rest = &s[i + 1..];
+        let name_len = rest
+            .bytes()
+            .take_while(|b| b.is_ascii_alphanumeric())
+            .count();
+        let name = &rest[..name_len];
+        TAGS.iter().any(|t| t.eq_ignore_ascii_case(name))
+            && matches!(
+                rest.as_bytes().get(name_len),
+                Some(b'>' | b' ' | b'/' | b'\n')
+            )
+    });
+    has_tag && {
+        let fragment = Html::parse_fragment(s);
+        fragment
+            .tree
+            .root()
+            .descendants()
+            .any(|n| n.value().as_text().is_some_and(|t| !t.trim().is_empty()))
+    }
+}
+
+// ─── Contentful Rich Text ─────────────────────────────────────────────────
+
+/// `in_list_item`: リスト項目の直下。段落を `<p>` で包まず `<br>` で区切る
+/// （`<li><p>` は項目の記号と本文が別の行に分かれるため）。
+fn rich_text(node: &JsonValue, in_list_item: bool, out: &mut String) {
+    let node_type = node
+        .get("nodeType")
+        .and_then(JsonValue::as_str)
+        .unwrap_or("");
+    if node_type == "paragraph" && in_list_item {
+        if !out.ends_with("<li>") {
+            out.push_str("<br>");
+        }
+        for child in node
+            .get("content")
+            .map(JsonValue::as_slice)
+            .unwrap_or_default()
+        {
+            rich_text(child, false, out);
+        }
+        return;
+    }
+    if node_type == "text" {
+        let text = node.get("value").and_then(JsonValue::as_str).unwrap_or("");
+        let marks: Vec<&str> = node
+            .get("marks")
+            .map(JsonValue::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|m| m.get("type").and_then(JsonValue::as_str))
+            .filter_map(|m| match m {
+                "bold" => Some("strong"),
+                "italic" => Some("em"),
+                "code" => Some("code"),
+                "strikethrough" => Some("s"),
+                "subscript" => Some("sub"),
+                "superscript" => Some("sup"),
+                _ => None,
+            })
+            .collect();
+        write_marked(out, text, &marks, None);
+        return;
+    }
+
+    let tag = match node_type {
+        "paragraph" => Some("p"),
+        "heading-1" => Some("h1"),
+        "heading-2" => Some("h2"),
+        "heading-3" => Some("h3"),
+        "heading-4" => Some("h4"),
+        "heading-5" => Some("h5"),
+        "heading-6" => Some("h6"),
+        "unordered-list" => Some("ul"),
+        "ordered-list" => Some("ol"),
+        "list-item" => Some("li"),
+        "blockquote" => Some("blockquote"),
+        "table" => Some("table"),
+        "table-row" => Some("tr"),
+        "table-cell" => Some("td"),
+        "table-header-cell" => Some("th"),
+        "hyperlink" => Some("a"),
+        "hr" => {
+            out.push_str("<hr>");
+            return;
+        }
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        out.push('<');
+        out.push_str(tag);
+        if tag == "a"
+            && let Some(uri) = node
+                .get("data")
+                .and_then(|d| d.get("uri"))
+                .and_then(JsonValue::as_str)
+        {
+            out.push_str(" href=\"");
+            escape(out, uri);
+            out.push('"');
+        }
+        out.push('>');
+    }
+    for child in node
+        .get("content")
+        .map(JsonValue::as_slice)
+        .unwrap_or_default()
+    {
+        rich_text(child, node_type == "list-item", out);
+    }
+    if let Some(tag) = tag {
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+    }
+}
+
+// ─── Sanity Portable Text ─────────────────────────────────────────────────
+
+fn is_portable_text_block(item: &JsonValue) -> bool {
+    item.get("_type").and_then(JsonValue::as_str) == Some("block")
+        && matches!(item.get("children"), Some(JsonValue::Array(_)))
+}
+
+fn portable_text(blocks: &[JsonValue]) -> String {
+    let mut out = String::new();
+    // 連続するリスト項目を 1 つのリストにまとめる
+    let mut open_list: Option<&str> = None;
+    for block in blocks.iter().filter(|b| is_portable_text_block(b)) {
+        let list = match block.get("listItem").and_then(JsonValue::as_str) {
+            Some("number") => Some("ol"),
+            Some(_) => Some("ul"),
+            None => None,
+        };
+        if open_list != list {
+            if let Some(tag) = open_list {
+                out.push_str(&format!("</{tag}>"));
+            }
+            if let Some(tag) = list {
+                out.push_str(&format!("<{tag}>"));
+            }
+            open_list = list;
+        }
+        let tag = if list.is_some() {
+            "li"
+        } else {
+            block
+                .get("style")
+                .and_then(JsonValue::as_str)
+                .filter(|s| matches!(*s, "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote"))
+                .unwrap_or("p")
+        };
+        out.push_str(&format!("<{tag}>"));
+        let mark_defs = block
+            .get("markDefs")
+            .map(JsonValue::as_slice)
+            .unwrap_or_default();
+        for span in block
+            .get("children")
+            .map(JsonValue::as_slice)
+            .unwrap_or_default()
+        {
+            let text = span.get("text").and_then(JsonValue::as_str).unwrap_or("");
+            let mut href = None;
+            let mut marks = Vec::new();
+            for mark in span
+                .get("marks")
+                .map(JsonValue::as_slice)
+                .unwrap_or_default()
+            {
+                match mark.as_str() {
+                    Some("strong") => marks.push("strong"),
+                    Some("em") => marks.push("em"),
+                    Some("code") => marks.push("code"),
+                    Some("strike-through") => marks.push("s"),
+                    Some(key) => {
+                        // markDefs のキー（リンクなど）
+                        href = href.or_else(|| {
+                            mark_defs
+                                .iter()
+                                .find(|d| d.get("_key").and_then(JsonValue::as_str) == Some(key))
+                                .and_then(|d| d.get("href"))
+                                .and_then(JsonValue::as_str)
+                        });
+                    }
+                    None => {}
+                }
+            }
+            write_marked(&mut out, text, &marks, href);
+        }
+        out.push_str(&format!("</{tag}>"));
+    }
+    if let Some(tag) = open_list {
+        out.push_str(&format!("</{tag}>"));
+    }
+    out
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+/// 強調などの要素で囲んだテキスト。リンクは強調の内側に置く。
+fn write_marked(out: &mut String, text: &str, marks: &[&str], href: Option<&str>) {
+    for mark in marks {
+        out.push_str(&format!("<{mark}>"));
+    }
+    match href {
+        Some(href) => {
+            out.push_str("<a href=\"");
+            escape(out, href);
+            out.push_str("\">");
+            escape(out, text);
+            out.push_str("</a>");
+        }
+        None => escape(out, text),
+    }
+    for mark in marks.iter().rev() {
+        out.push_str(&format!("</{mark}>"));
+    }
+}
+
+fn escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
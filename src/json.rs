@@ -0,0 +1,312 @@
+//! 構造化データ用の最小限の JSON 値
+//!
+//! JSON-LD の読み取りと、microdata / microformats の結果の表現に使う。
+//! パーサーは RFC 8259 の文法に従い、入れ子の深さを [`MAX_DEPTH`] で
+//! 制限するため、悪意のある入力でもスタックオーバーフローしない。
+//! JSON-LD 用の [`JsonValue::parse_lenient`] だけは、手書きの JSON-LD に
+//! よくある文字列中の生の改行・タブなどの制御文字を受け付ける。
+
+use std::fmt::{self, Write as _};
+
+/// 入れ子の最大の深さ。これより深い JSON はパースに失敗する。
+const MAX_DEPTH: usize = 128;
+
+/// JSON の値。オブジェクトはキーの出現順を保つ。
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// キーと値の組（出現順）。重複したキーもそのまま保持する。
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// JSON 文字列をパースする。不正な JSON なら `None`。
+    pub fn parse(text: &str) -> Option<Self> {
+        Parser::new(text, false).parse()
+    }
+
+    /// [`parse`](Self::parse) と同じだが、文字列中の生の制御文字（改行・タブなど）
+    /// をそのまま文字として受け付ける。ブラウザーや検索エンジンが読める
+    /// JSON-LD を取りこぼさないため。
+    pub(crate) fn parse_lenient(text: &str) -> Option<Self> {
+        Parser::new(text, true).parse()
+    }
+
+    /// オブジェクトのキーに対応する値（最初のもの）。
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// 文字列なら中身を返す。
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 配列なら要素を、それ以外は自身だけを返す（JSON-LD の単数・複数の揺れ用）。
+    pub fn as_slice(&self) -> &[JsonValue] {
+        match self {
+            Self::Array(items) => items,
+            other => std::slice::from_ref(other),
+        }
+    }
+}
+
+/// コンパクトな JSON として書き出す。
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(n) if n.is_finite() => write!(f, "{n}"),
+            Self::Number(_) => f.write_str("null"),
+            Self::String(s) => write_quoted(f, s),
+            Self::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(entries) => {
+                f.write_char('{')?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_quoted(f, k)?;
+                    write!(f, ":{v}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// JSON の文字列リテラルとして書き出す。
+pub(crate) fn write_quoted(out: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if c.is_control() => write!(out, "\\u{:04X}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+// ─── パーサー ─────────────────────────────────────────────────────────────
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// 文字列中の生の制御文字を受け付けるか。
+    lenient: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, lenient: bool) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos: 0,
+            lenient,
+        }
+    }
+
+    fn parse(mut self) -> Option<JsonValue> {
+        let value = self.value(0)?;
+        self.skip_ws();
+        (self.pos == self.bytes.len()).then_some(value)
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> Option<()> {
+        (self.peek() == Some(b)).then(|| self.pos += 1)
+    }
+
+    fn literal(&mut self, word: &str, value: JsonValue) -> Option<JsonValue> {
+        let end = self.pos + word.len();
+        (self.bytes.get(self.pos..end) == Some(word.as_bytes())).then(|| {
+            self.pos = end;
+            value
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> Option<JsonValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        self.skip_ws();
+        match self.peek()? {
+            b'{' => self.object(depth),
+            b'[' => self.array(depth),
+            b'"' => self.string().map(JsonValue::String),
+            b't' => self.literal("true", JsonValue::Bool(true)),
+            b'f' => self.literal("false", JsonValue::Bool(false)),
+            b'n' => self.literal("null", JsonValue::Null),
+            _ => self.number(),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Option<JsonValue> {
+        self.eat(b'{')?;
+        let mut entries = Vec::new();
+        self.skip_ws();
+        if self.eat(b'}').is_some() {
+            return Some(JsonValue::Object(entries));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.string()?;
+            self.skip_ws();
+            self.eat(b':')?;
+            let value = self.value(depth + 1)?;
+            entries.push((key, value));
+            self.skip_ws();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Some(JsonValue::Object(entries));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Option<JsonValue> {
+        self.eat(b'[')?;
+        let mut items = Vec::new();
+        self.skip_ws();
+        if self.eat(b']').is_some() {
+            return Some(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_ws();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Some(JsonValue::Array(items));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn number(&mut self) -> Option<JsonValue> {
+        let start = self.pos;
+        self.eat(b'-');
+        let digits = |p: &mut Self| {
+            let s = p.pos;
+            while p.peek().is_some_and(|b| b.is_ascii_digit()) {
+                p.pos += 1;
+            }
+            p.pos > s
+        };
+        if !digits(self) {
+            return None;
+        }
+        if self.eat(b'.').is_some() && !digits(self) {
+            return None;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return None;
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+        text.parse().ok().map(JsonValue::Number)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.eat(b'"')?;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.peek()?, b'"' | b'\\') {
+                if self.peek()? < 0x20 && !self.lenient {
+                    return None;
+                }
+                self.pos += 1;
+            }
+            // 入力は &str 由来で、区切りは ASCII なので常に UTF-8 境界
+            out.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).ok()?);
+            if self.eat(b'"').is_some() {
+                return Some(out);
+            }
+            self.pos += 1; // '\\'
+            let escaped = self.peek()?;
+            self.pos += 1;
+            match escaped {
+                b'"' => out.push('"'),
+                b'\\' => out.push('\\'),
+                b'/' => out.push('/'),
+                b'b' => out.push('\u{8}'),
+                b'f' => out.push('\u{c}'),
+                b'n' => out.push('\n'),
+                b'r' => out.push('\r'),
+                b't' => out.push('\t'),
+                b'u' => {
+                    let high = self.hex4()?;
+                    let c = if (0xD800..0xDC00).contains(&high) {
+                        // サロゲートペア
+                        self.eat(b'\\')?;
+                        self.eat(b'u')?;
+                        let low = self.hex4()?;
+                        if !(0xDC00..0xE000).contains(&low) {
+                            return None;
+                        }
+                        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))?
+                    } else {
+                        char::from_u32(high).unwrap_or('\u{FFFD}')
+                    };
+                    out.push(c);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// `\u` に続く 16 進数 4 桁。`+` などの符号は受け付けない。
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.bytes.get(self.pos..self.pos + 4)?;
+        let mut value = 0;
+        for &b in digits {
+            value = value * 16 + (b as char).to_digit(16)?;
+        }
+        self.pos += 4;
+        Some(value)
+    }
+}
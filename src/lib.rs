@@ -22,18 +22,54 @@
 
 pub mod options;
 
+mod assets;
+#[cfg(feature = "parallel")]
+mod batch;
+mod data_uri;
+mod emoji;
+mod extract;
+mod figure;
+mod front_matter;
+mod hydration;
+mod json;
+mod metadata;
+mod profile;
 mod renderer;
+mod simhash;
+#[cfg(feature = "parallel")]
+mod site;
+mod structured;
+mod svg;
 mod traversal;
+mod url;
 mod utils;
 
 #[doc(hidden)]
 pub mod alloc_counter;
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-pub use options::{ConversionMode, ConversionOptions};
+#[cfg(feature = "parallel")]
+pub use batch::{
+    BatchOptions, BatchReport, BoilerplateBlock, DanglingLink, html_files_to_markdown_batch,
+};
+pub use extract::extract_main_content;
+pub use json::JsonValue;
+pub use metadata::{DocumentMetadata, extract_metadata};
+pub use options::{
+    ConversionMode, ConversionOptions, CssSelector, DataUriPolicy, EmojiPolicy, FrontMatterField,
+    FrontMatterFormat, ImageDimensions, LinkFilter, LinkPolicy, LinkStyle, MetadataField,
+    SelectorError, SourceProfile, SvgPolicy, TagRules, UrlResolution,
+};
+/// [`extract_main_content`] / [`extract_metadata`] が受け取る・返す DOM 型
+/// （[`scraper::Html`] / [`scraper::ElementRef`]）のため、使用している scraper を
+/// 再エクスポートする。
+pub use scraper;
+pub use simhash::{markdown_simhash, simhash_similarity};
+pub use structured::{StructuredData, extract_structured_data};
 
 // ── エラー型 ───────────────────────────────────────────────────────────────
 
@@ -54,6 +90,16 @@ pub struct ConvertResult {
     pub dest: PathBuf,
 }
 
+/// Markdown と文書メタデータの組。[`html_to_markdown_document`] が返す。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkdownDocument {
+    /// 変換した Markdown。[`html_to_markdown_with`] の結果と同じ
+    /// （[`ConversionOptions::front_matter`] の指定があれば front matter を含む）。
+    pub markdown: String,
+    /// `<head>` や構造化データから読み取ったメタデータ。
+    pub metadata: DocumentMetadata,
+}
+
 // ── 文字列変換 API ─────────────────────────────────────────────────────────
 
 /// HTML 文字列を Markdown 文字列に変換する（既定モード: `balanced`）。
@@ -93,7 +139,37 @@ pub fn html_to_markdown(html: &str) -> String {
 /// please be aware of the memory overhead compared to stream-based parsers like `lol_html``.
 pub fn html_to_markdown_with(html: &str, opts: &ConversionOptions) -> String {
     let document = scraper::Html::parse_document(html);
-    traversal::traverse(&document, opts)
+    if opts.front_matter == FrontMatterFormat::None {
+        return traversal::traverse(&document, opts);
+    }
+    convert(&document, opts, false, None).output()
+}
+
+/// HTML 文字列を Markdown に変換し、タイトルなどのメタデータも返す。
+///
+/// パースは 1 回だけで、同じ DOM から Markdown とメタデータを作る。
+///
+/// # Example
+///
+/// ```rust
+/// use mdka::options::ConversionOptions;
+///
+/// let doc = mdka::html_to_markdown_document(
+///     "<html><head><title>Hello</title></head><body><p>Body</p></body></html>",
+///     &ConversionOptions::default(),
+/// );
+/// assert_eq!(doc.markdown, "Body\n");
+/// assert_eq!(doc.metadata.title.as_deref(), Some("Hello"));
+/// ```
+pub fn html_to_markdown_document(html: &str, opts: &ConversionOptions) -> MarkdownDocument {
+    let document = scraper::Html::parse_document(html);
+    let metadata = extract_metadata(&document);
+    let mut rendered = traversal::render(&document, opts, false, None);
+    rendered.front_matter = front_matter::render(&metadata, None, opts);
+    MarkdownDocument {
+        markdown: rendered.output(),
+        metadata,
+    }
 }
 
 // ── 単体ファイル変換 API ───────────────────────────────────────────────────
@@ -187,12 +263,77 @@ fn do_convert_file(
     out_dir: &Path,
     opts: &ConversionOptions,
 ) -> Result<PathBuf, MdkaError> {
-    // out_dir が存在しない場合は自動作成する
-    fs::create_dir_all(out_dir)?;
+    let rendered = render_file(src, None, opts)?;
+    write_rendered(src, out_dir, &rendered)
+}
+
+/// HTML ファイルを読み込んで変換する。書き出しは行わない。
+/// `asset_scope` はローカルの画像を読んでよいディレクトリ（`None` なら
+/// 入力ファイルのディレクトリ）。
+fn render_file(
+    src: &Path,
+    asset_scope: Option<&Path>,
+    opts: &ConversionOptions,
+) -> Result<traversal::Rendered, MdkaError> {
     let html = fs::read_to_string(src)?;
-    let md = html_to_markdown_with(&html, opts);
+    let document = scraper::Html::parse_document(&html);
+    Ok(convert_linked(
+        &document,
+        opts,
+        true,
+        Some(src),
+        asset_scope,
+        HashMap::new(),
+    ))
+}
+
+/// パース済み文書を変換し、指定があれば front matter を付ける。
+/// `source` は front matter の `source` 項目に書き出す入力パス。
+fn convert(
+    document: &scraper::Html,
+    opts: &ConversionOptions,
+    collect_assets: bool,
+    source: Option<&Path>,
+) -> traversal::Rendered {
+    convert_linked(document, opts, collect_assets, source, None, HashMap::new())
+}
+
+/// [`convert`] に加えて、`links` にある `href` を書き換える（サイト変換）。
+/// `asset_scope` は [`render_file`] と同じ。
+fn convert_linked(
+    document: &scraper::Html,
+    opts: &ConversionOptions,
+    collect_assets: bool,
+    source: Option<&Path>,
+    asset_scope: Option<&Path>,
+    links: HashMap<String, String>,
+) -> traversal::Rendered {
+    let mut rendered =
+        traversal::render_linked(document, opts, collect_assets, source, asset_scope, links);
+    if opts.front_matter != FrontMatterFormat::None {
+        rendered.front_matter = front_matter::render(&extract_metadata(document), source, opts);
+    }
+    rendered
+}
+
+/// 変換結果を `out_dir/<入力の stem>.md` へ書き出す。
+fn write_rendered(
+    src: &Path,
+    out_dir: &Path,
+    rendered: &traversal::Rendered,
+) -> Result<PathBuf, MdkaError> {
     let stem = src.file_stem().unwrap_or_default();
-    let dest = out_dir.join(stem).with_extension("md");
-    fs::write(&dest, md)?;
-    Ok(dest)
+    write_rendered_to(&out_dir.join(stem).with_extension("md"), rendered)
+}
+
+/// 変換結果を `dest` へ書き出す。アセットは `dest` と同じディレクトリの
+/// `assets/` へ書き出す。
+fn write_rendered_to(dest: &Path, rendered: &traversal::Rendered) -> Result<PathBuf, MdkaError> {
+    let dir = dest.parent().unwrap_or_else(|| Path::new("."));
+    // 出力先のディレクトリが存在しない場合は自動作成する
+    fs::create_dir_all(dir)?;
+    // 本文が参照するアセット（assets/ 配下）を先に書き出す
+    assets::write_assets(dir, &rendered.assets)?;
+    fs::write(dest, rendered.output())?;
+    Ok(dest.to_path_buf())
 }
@@ -0,0 +1,188 @@
+//! 文書メタデータの抽出
+//!
+//! 変換では `<head>` を読み飛ばすため、タイトルや `<meta>` の情報は
+//! Markdown に残らない。ここでは同じ [`Html`] から `<title>`・`<meta>`・
+//! `<link rel="canonical">`・`<html lang>` を読み取り、[`DocumentMetadata`]
+//! にまとめる。`<meta>` にない項目は JSON-LD などの構造化データで補う。
+
+use scraper::{Html, Node};
+
+use crate::structured::{self, StructuredData};
+
+/// HTML 文書のメタデータ。
+///
+/// 各項目は複数の情報源から優先順に採る。たとえば `title` は `<title>`、
+/// なければ `og:title`、なければ `twitter:title`、なければ構造化データの
+/// `headline` / `name`。見つからない項目は `None`。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentMetadata {
+    /// `<title>` → `og:title` → `twitter:title` → 構造化データ。
+    pub title: Option<String>,
+    /// `<meta name="description">` → `og:description` → `twitter:description` → 構造化データ。
+    pub description: Option<String>,
+    /// `<link rel="canonical">` → `og:url`。
+    pub canonical_url: Option<String>,
+    /// `<html lang>` → `<meta http-equiv="content-language">` → `og:locale`。
+    pub lang: Option<String>,
+    /// `<meta name="author">` → `article:author` → `twitter:creator` → 構造化データ。
+    pub author: Option<String>,
+    /// `article:published_time` → `<meta name="date">` など → 構造化データ。
+    pub published: Option<String>,
+    /// `article:modified_time` → `og:updated_time` → `<meta name="last-modified">` など → 構造化データ。
+    pub modified: Option<String>,
+    /// `og:site_name`。
+    pub site_name: Option<String>,
+    /// `og:image` → `twitter:image` → 構造化データ。
+    pub image: Option<String>,
+    /// `<meta name="keywords">` をカンマで分けたもの。なければ構造化データの `keywords`。
+    pub keywords: Vec<String>,
+    /// `og:*` / `article:*` の `<meta property>` すべて（文書順、名前は原文のまま）。
+    pub open_graph: Vec<(String, String)>,
+    /// `twitter:*` の `<meta name>` すべて（文書順、名前は原文のまま）。
+    pub twitter: Vec<(String, String)>,
+    /// JSON-LD・microdata・microformats（[`extract_structured_data`] の結果）。
+    ///
+    /// [`extract_structured_data`]: crate::extract_structured_data
+    pub structured_data: StructuredData,
+}
+
+/// 文書からメタデータを読み取る。
+///
+/// # Example
+///
+/// ```rust
+/// use mdka::scraper::Html;
+///
+/// let document = Html::parse_document(
+///     r#"<html lang="en"><head><title>Hello</title>
+///     <meta name="description" content="A greeting"></head></html>"#,
+/// );
+/// let meta = mdka::extract_metadata(&document);
+/// assert_eq!(meta.title.as_deref(), Some("Hello"));
+/// assert_eq!(meta.description.as_deref(), Some("A greeting"));
+/// assert_eq!(meta.lang.as_deref(), Some("en"));
+/// ```
+pub fn extract_metadata(document: &Html) -> DocumentMetadata {
+    let mut title: Option<String> = None;
+    let mut html_lang: Option<String> = None;
+    let mut canonical: Option<String> = None;
+    // name / property / http-equiv（小文字化）→ content。文書順で最初のものを使う
+    let mut metas: Vec<(String, String)> = Vec::new();
+    let mut meta = DocumentMetadata::default();
+
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        match elem.name() {
+            "html" if html_lang.is_none() => html_lang = non_empty(elem.attr("lang")),
+            // <svg><title> は文書のタイトルではない
+            "title" if title.is_none() && !in_svg(node) => {
+                let text: String = node
+                    .descendants()
+                    .filter_map(|d| match d.value() {
+                        Node::Text(t) => Some(&**t),
+                        _ => None,
+                    })
+                    .collect();
+                title = non_empty(Some(&text));
+            }
+            "link" if canonical.is_none() => {
+                let is_canonical = elem.attr("rel").is_some_and(|r| {
+                    r.split_ascii_whitespace()
+                        .any(|t| t.eq_ignore_ascii_case("canonical"))
+                });
+                if is_canonical {
+                    canonical = non_empty(elem.attr("href"));
+                }
+            }
+            "meta" => {
+                let Some(content) = non_empty(elem.attr("content")) else {
+                    continue;
+                };
+                let key = elem
+                    .attr("property")
+                    .or_else(|| elem.attr("name"))
+                    .or_else(|| elem.attr("http-equiv"))
+                    .map(str::trim)
+                    .unwrap_or_default();
+                if key.is_empty() {
+                    continue;
+                }
+                let lower = key.to_ascii_lowercase();
+                if lower.starts_with("og:") || lower.starts_with("article:") {
+                    meta.open_graph.push((key.to_string(), content.clone()));
+                } else if lower.starts_with("twitter:") {
+                    meta.twitter.push((key.to_string(), content.clone()));
+                }
+                metas.push((lower, content));
+            }
+            _ => {}
+        }
+    }
+
+    let lookup = |keys: &[&str]| -> Option<String> {
+        keys.iter()
+            .find_map(|k| metas.iter().find(|(name, _)| name == k))
+            .map(|(_, v)| v.clone())
+    };
+
+    meta.title = title.or_else(|| lookup(&["og:title", "twitter:title"]));
+    meta.description = lookup(&["description", "og:description", "twitter:description"]);
+    meta.canonical_url = canonical.or_else(|| lookup(&["og:url"]));
+    meta.lang = html_lang.or_else(|| lookup(&["content-language", "og:locale"]));
+    meta.author = lookup(&["author", "article:author", "twitter:creator"]);
+    meta.published = lookup(&[
+        "article:published_time",
+        "date",
+        "dc.date",
+        "dc.date.issued",
+        "dcterms.created",
+        "pubdate",
+        "publish-date",
+    ]);
+    meta.modified = lookup(&[
+        "article:modified_time",
+        "og:updated_time",
+        "dcterms.modified",
+        "last-modified",
+    ]);
+    meta.site_name = lookup(&["og:site_name"]);
+    meta.image = lookup(&["og:image", "og:image:url", "twitter:image"]);
+    meta.keywords = lookup(&["keywords"])
+        .map(|k| {
+            k.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    meta.structured_data = structured::extract_structured_data(document);
+    if let Some(summary) = structured::summarize(&meta.structured_data) {
+        meta.title = meta.title.or(summary.title);
+        meta.description = meta.description.or(summary.description);
+        meta.author = meta.author.or(summary.author);
+        meta.published = meta.published.or(summary.published);
+        meta.modified = meta.modified.or(summary.modified);
+        meta.image = meta.image.or(summary.image);
+        if meta.keywords.is_empty() {
+            meta.keywords = summary.keywords;
+        }
+    }
+    meta
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+/// 空白を 1 つにまとめ、空なら `None` を返す。
+fn non_empty(value: Option<&str>) -> Option<String> {
+    let collapsed = value?.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!collapsed.is_empty()).then_some(collapsed)
+}
+
+fn in_svg(node: ego_tree::NodeRef<Node>) -> bool {
+    node.ancestors()
+        .any(|a| a.value().as_element().is_some_and(|e| e.name() == "svg"))
+}
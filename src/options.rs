@@ -4,6 +4,8 @@
 //! [`ConversionOptions`] はモードとフラグの組み合わせを保持し、
 //! `Default` は `balanced` モードを返す。
 
+use crate::utils;
+
 /// 変換モード。入力 HTML の性質と用途に応じて選択する。
 ///
 /// | モード      | 用途                                  |
@@ -71,6 +73,812 @@ impl std::fmt::Display for ConversionMode {
     }
 }
 
+/// 入力 HTML の生成元。特定の生成ツールが出力する癖を変換時に取り除く。
+///
+/// [`ConversionMode`] とは独立して選択する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SourceProfile {
+    /// 既定。生成元に固有の処理を行わない。
+    #[default]
+    Generic,
+    /// Google Docs / Microsoft Word / Outlook のエクスポート HTML。
+    /// `<o:p>` などの Office 名前空間要素や `mso-list` の擬似リストを
+    /// 取り除き、`mso-list` のレベルから入れ子のリストを再構成する。
+    /// Google Docs が全体を包む `<b style="font-weight:normal">` も外す。
+    Office,
+    /// HTML メール（ニュースレター・通知メール）。レイアウト用の表を
+    /// 段落に展開し、スペーサー画像・トラッキングピクセル・プレヘッダー
+    /// （受信一覧用の不可視テキスト）を取り除く。`<center>` / `<font>` は
+    /// 中身のテキストとして出力する。
+    Email,
+}
+
+impl SourceProfile {
+    /// プロファイル名を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Generic => "generic",
+            Self::Office => "office",
+            Self::Email => "email",
+        }
+    }
+}
+
+impl std::str::FromStr for SourceProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "generic" => Ok(Self::Generic),
+            "office" => Ok(Self::Office),
+            "email" => Ok(Self::Email),
+            other => Err(format!("unknown source profile: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for SourceProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// インライン `<svg>` の扱い。
+///
+/// | ポリシー   | 出力                                                  |
+/// |------------|-------------------------------------------------------|
+/// | `Skip`     | 何も出力しない（2.2.0 までの挙動）                    |
+/// | `AltText`  | `aria-label` / `<title>` をテキストとして出力         |
+/// | `File`     | `assets/` に `.svg` として書き出し、画像として参照    |
+/// | `DataUri`  | `data:image/svg+xml` の画像として埋め込む             |
+/// | `Raw`      | SVG マークアップをそのまま出力する                    |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SvgPolicy {
+    /// SVG をコンテンツごと除外する。
+    Skip,
+    /// 既定。アクセシブルな名前（`aria-label` または `<title>`）だけを
+    /// テキストとして出力する。アイコンのみのリンクにも名前が付く。
+    #[default]
+    AltText,
+    /// 出力ディレクトリの `assets/` にファイルとして書き出し、画像として参照する。
+    /// ファイルを書き出さない文字列変換 API では `DataUri` と同じ出力になる。
+    File,
+    /// `data:image/svg+xml;base64,…` の画像として埋め込む。
+    DataUri,
+    /// SVG マークアップをインライン HTML としてそのまま出力する。
+    Raw,
+}
+
+impl SvgPolicy {
+    /// ポリシー名を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::AltText => "alt-text",
+            Self::File => "file",
+            Self::DataUri => "data-uri",
+            Self::Raw => "raw",
+        }
+    }
+}
+
+impl std::str::FromStr for SvgPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "alt-text" => Ok(Self::AltText),
+            "file" => Ok(Self::File),
+            "data-uri" => Ok(Self::DataUri),
+            "raw" => Ok(Self::Raw),
+            other => Err(format!("unknown svg policy: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for SvgPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `data:` URI の画像（`<img src="data:image/png;base64,…">`）の扱い。
+///
+/// | ポリシー   | 出力                                                  |
+/// |------------|-------------------------------------------------------|
+/// | `Inline`   | `data:` URI のまま画像として出力する（従来の挙動）    |
+/// | `File`     | 復号して `assets/` に書き出し、画像として参照         |
+/// | `AltText`  | `alt` をテキストとして出力                            |
+/// | `Drop`     | 何も出力しない                                        |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DataUriPolicy {
+    /// 既定。`data:` URI をそのまま画像の URL として書く。
+    #[default]
+    Inline,
+    /// 復号して出力ディレクトリの `assets/` に内容のハッシュを名前にして
+    /// 書き出し、画像として参照する。ファイルを書き出さない文字列変換 API と、
+    /// 画像でない・復号できない URI では `Inline` と同じ出力になる。
+    File,
+    /// 代替テキスト（`alt`）だけをテキストとして出力する。
+    AltText,
+    /// 画像ごと取り除く。
+    Drop,
+}
+
+impl DataUriPolicy {
+    /// ポリシー名を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::File => "file",
+            Self::AltText => "alt-text",
+            Self::Drop => "drop",
+        }
+    }
+}
+
+impl std::str::FromStr for DataUriPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "inline" => Ok(Self::Inline),
+            "file" => Ok(Self::File),
+            "alt-text" => Ok(Self::AltText),
+            "drop" => Ok(Self::Drop),
+            other => Err(format!("unknown data uri policy: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for DataUriPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 画像の `width` / `height` 属性の扱い。
+///
+/// | 値           | `<img src="a.png" alt="A" width="100" height="50">` の出力 |
+/// |--------------|-------------------------------------------------------------|
+/// | `Drop`       | `![A](a.png)`                                               |
+/// | `Attributes` | `![A](a.png){width=100 height=50}`（Pandoc / kramdown）     |
+/// | `Html`       | `<img src="a.png" alt="A" width="100" height="50">`         |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ImageDimensions {
+    /// 既定。寸法を書かない。
+    #[default]
+    Drop,
+    /// 画像の後に属性リスト `{width=… height=…}` を付ける。
+    Attributes,
+    /// 寸法のある画像を `<img>` タグのまま出力する。リンクテキストの中では
+    /// 生の HTML を置けないため、寸法を書かない Markdown の画像にする。
+    Html,
+}
+
+impl ImageDimensions {
+    /// 値の名前を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Attributes => "attributes",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl std::str::FromStr for ImageDimensions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "drop" => Ok(Self::Drop),
+            "attributes" => Ok(Self::Attributes),
+            "html" => Ok(Self::Html),
+            other => Err(format!("unknown image dimensions: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for ImageDimensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 絵文字の画像（`<img class="emoji">`、絵文字 CDN の画像、GitHub の
+/// `<g-emoji>`）の扱い。
+///
+/// | ポリシー    | `<img class="emoji" alt="😄" src="…/1f604.png">` の出力 |
+/// |-------------|----------------------------------------------------------|
+/// | `Unicode`   | `😄`                                                     |
+/// | `Shortcode` | `:smile:`（名前が分かる場合。分からなければ `Unicode`）  |
+/// | `Image`     | `![😄](…/1f604.png)`（従来の挙動）                       |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum EmojiPolicy {
+    /// 既定。絵文字の文字を出力する。`alt` が文字でなければ、画像の
+    /// ファイル名のコードポイント（`1f604.png`）から復元する。
+    #[default]
+    Unicode,
+    /// `:shortcode:` を出力する。名前は `<g-emoji alias>`、Slack の
+    /// `data-stringify-emoji`、`:name:` 形式の `alt` / `title` から取る。
+    Shortcode,
+    /// 通常の画像として出力する。
+    Image,
+}
+
+impl EmojiPolicy {
+    /// ポリシー名を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unicode => "unicode",
+            Self::Shortcode => "shortcode",
+            Self::Image => "image",
+        }
+    }
+}
+
+impl std::str::FromStr for EmojiPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "unicode" => Ok(Self::Unicode),
+            "shortcode" => Ok(Self::Shortcode),
+            "image" => Ok(Self::Image),
+            other => Err(format!("unknown emoji policy: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for EmojiPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 基準 URL に対して、どの相対参照を絶対 URL に書き換えるか。
+///
+/// | 値               | `//cdn.example/a.png` | `/a` / `a` / `../a` / `?q` |
+/// |------------------|-----------------------|----------------------------|
+/// | `All`            | 書き換える            | 書き換える                 |
+/// | `SchemeRelative` | 書き換える            | そのまま                   |
+/// | `PathRelative`   | そのまま              | 書き換える                 |
+/// | `Off`            | そのまま              | そのまま                   |
+///
+/// スキームを持つ URL と、フラグメントだけの参照（`#top`）は常にそのまま残す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum UrlResolution {
+    /// 既定。すべての相対参照を解決する。
+    #[default]
+    All,
+    /// スキーム相対（`//host/path`）の参照にだけスキームを補う。
+    SchemeRelative,
+    /// ホストを含まないパス・クエリの参照だけを解決する。
+    PathRelative,
+    /// 書き換えない。`base_url` と `<base href>` を無視する。
+    Off,
+}
+
+impl UrlResolution {
+    /// 値の名前を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::SchemeRelative => "scheme-relative",
+            Self::PathRelative => "path-relative",
+            Self::Off => "off",
+        }
+    }
+}
+
+impl std::str::FromStr for UrlResolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "scheme-relative" => Ok(Self::SchemeRelative),
+            "path-relative" => Ok(Self::PathRelative),
+            "off" => Ok(Self::Off),
+            other => Err(format!("unknown url resolution: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for UrlResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// [`ConversionOptions::tracking_params`] が空のときに取り除くクエリパラメータ。
+/// 末尾の `*` は前方一致。
+pub const DEFAULT_TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid",
+    "igshid", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "ref", "ref_src",
+];
+
+/// リンクの書き方。
+///
+/// | 値                    | 本文                | 定義                               |
+/// |-----------------------|---------------------|------------------------------------|
+/// | `Inline`              | `[text](url)`       | なし                               |
+/// | `Reference`           | `[text][1]`         | 文書の末尾に `[1]: url`            |
+/// | `ReferencePerSection` | `[text][1]`         | 次の `h1` / `h2` の前と文書の末尾  |
+/// | `Footnote`            | `text[^1]`          | 文書の末尾に `[^1]: <url>`         |
+///
+/// 同じ URL へのリンクは 1 つの定義を共有する（`title` は最初のリンクのもの）。
+/// 番号は文書全体で通し番号。`href` が空のリンクは常にインラインで書く。
+/// 脚注の本文は段落なので、絶対 URL は自動リンク、相対 URL はテキストとして
+/// 書き、`title` は ` — ` の後に続ける。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum LinkStyle {
+    /// 既定。リンクの位置に URL を書く。
+    #[default]
+    Inline,
+    /// 番号付きの参照リンク。定義は文書の末尾にまとめる。
+    Reference,
+    /// 番号付きの参照リンク。定義はトップレベルの節（`h1` / `h2`）ごとに、
+    /// 次の節の見出しの前に書く。
+    ReferencePerSection,
+    /// 脚注。リンクのテキストの後に脚注番号を付け、URL は脚注に書く。
+    Footnote,
+}
+
+impl LinkStyle {
+    /// 値の名前を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::Reference => "reference",
+            Self::ReferencePerSection => "reference-per-section",
+            Self::Footnote => "footnote",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "inline" => Ok(Self::Inline),
+            "reference" => Ok(Self::Reference),
+            "reference-per-section" => Ok(Self::ReferencePerSection),
+            "footnote" => Ok(Self::Footnote),
+            other => Err(format!("unknown link style: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for LinkStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// どのリンクを Markdown のリンクとして残すか。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum LinkFilter {
+    /// 既定。すべてのリンクを残す。
+    #[default]
+    Keep,
+    /// 外部のページへのリンクだけを残し、他はテキストにする。
+    /// スキーム（`https:`、`mailto:` など）か `//` で始まる URL のうち、
+    /// 基準 URL と同じホストではないものを外部とみなす。
+    ExternalOnly,
+    /// すべてのリンクをテキストにする（URL を書かない）。
+    TextOnly,
+    /// リンクをテキストごと取り除く。
+    Drop,
+}
+
+impl LinkFilter {
+    /// 値の名前を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::ExternalOnly => "external-only",
+            Self::TextOnly => "text-only",
+            Self::Drop => "drop",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "external-only" => Ok(Self::ExternalOnly),
+            "text-only" => Ok(Self::TextOnly),
+            "drop" => Ok(Self::Drop),
+            other => Err(format!("unknown link filter: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for LinkFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// リンクの扱い。
+///
+/// `filter` で残すリンクを選び、残したリンクのうち `strip_dead` で
+/// 移動先のないリンクをテキストにし、`autolink` で URL をそのまま
+/// テキストにしたリンクを `<https://…>` にする。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinkPolicy {
+    /// どのリンクを残すか。
+    pub filter: LinkFilter,
+    /// `href` のない・空の・`#` だけの・`javascript:` のリンクをテキストにする。
+    pub strip_dead: bool,
+    /// テキストが `href` と同じリンクを `<url>` の自動リンクにする。
+    pub autolink: bool,
+}
+
+impl LinkPolicy {
+    /// ビルダー: 残すリンクを設定する。
+    pub fn filter(mut self, v: LinkFilter) -> Self {
+        self.filter = v;
+        self
+    }
+
+    /// ビルダー: 移動先のないリンクをテキストにするかを設定する。
+    pub fn strip_dead(mut self, v: bool) -> Self {
+        self.strip_dead = v;
+        self
+    }
+
+    /// ビルダー: 自動リンクを使うかを設定する。
+    pub fn autolink(mut self, v: bool) -> Self {
+        self.autolink = v;
+        self
+    }
+}
+
+/// 出力の先頭に付ける front matter の形式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum FrontMatterFormat {
+    /// 既定。front matter を付けない。
+    #[default]
+    None,
+    /// `---` で囲んだ YAML（Jekyll / Hugo / Astro など）。
+    Yaml,
+    /// `+++` で囲んだ TOML（Hugo / Zola など）。
+    Toml,
+}
+
+impl FrontMatterFormat {
+    /// 形式名を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+        }
+    }
+}
+
+impl std::str::FromStr for FrontMatterFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "yaml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            other => Err(format!("unknown front matter format: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for FrontMatterFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// front matter に書き出せる項目。値は [`DocumentMetadata`] から採る。
+///
+/// [`DocumentMetadata`]: crate::DocumentMetadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MetadataField {
+    /// `title`
+    Title,
+    /// `description`
+    Description,
+    /// `canonical_url`
+    CanonicalUrl,
+    /// 公開日時（`published`、なければ `modified`）。
+    Date,
+    /// `modified`
+    Modified,
+    /// `author`
+    Author,
+    /// `lang`
+    Lang,
+    /// `site_name`
+    SiteName,
+    /// `image`
+    Image,
+    /// `keywords` を文字列の配列として書き出す。
+    Tags,
+    /// 入力ファイルのパス。ファイル変換 API でのみ書き出す。
+    Source,
+    /// JSON-LD・microdata・microformats のすべての項目を、入れ子の値の配列
+    /// として書き出す（YAML はフロー形式、TOML はインラインテーブル）。
+    StructuredData,
+}
+
+impl MetadataField {
+    /// [`ConversionOptions::front_matter_fields`] が空のときに書き出す項目。
+    pub const DEFAULT: &'static [MetadataField] = &[
+        Self::Title,
+        Self::Description,
+        Self::CanonicalUrl,
+        Self::Date,
+        Self::Tags,
+        Self::Source,
+    ];
+
+    /// 項目名（既定のキー名）を文字列で返す。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Description => "description",
+            Self::CanonicalUrl => "canonical_url",
+            Self::Date => "date",
+            Self::Modified => "modified",
+            Self::Author => "author",
+            Self::Lang => "lang",
+            Self::SiteName => "site_name",
+            Self::Image => "image",
+            Self::Tags => "tags",
+            Self::Source => "source",
+            Self::StructuredData => "structured_data",
+        }
+    }
+}
+
+impl std::str::FromStr for MetadataField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "title" => Ok(Self::Title),
+            "description" => Ok(Self::Description),
+            "canonical_url" => Ok(Self::CanonicalUrl),
+            "date" => Ok(Self::Date),
+            "modified" => Ok(Self::Modified),
+            "author" => Ok(Self::Author),
+            "lang" => Ok(Self::Lang),
+            "site_name" => Ok(Self::SiteName),
+            "image" => Ok(Self::Image),
+            "tags" => Ok(Self::Tags),
+            "source" => Ok(Self::Source),
+            "structured_data" => Ok(Self::StructuredData),
+            other => Err(format!("unknown metadata field: {other}")),
+        }
+    }
+}
+
+impl std::fmt::Display for MetadataField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// front matter の 1 項目: 書き出す値と、そのキー名。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatterField {
+    pub field: MetadataField,
+    /// 出力するキー名。既定は [`MetadataField::as_str`]。
+    pub key: String,
+}
+
+impl FrontMatterField {
+    /// 既定のキー名で項目を生成する。
+    pub fn new(field: MetadataField) -> Self {
+        Self {
+            field,
+            key: field.as_str().to_string(),
+        }
+    }
+
+    /// キー名を変える（例: `date` → `pubDate`）。
+    pub fn renamed(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+}
+
+/// `title` または `date=pubDate` の形式を受け付ける。
+impl std::str::FromStr for FrontMatterField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((field, key)) => {
+                let key = key.trim();
+                if key.is_empty() {
+                    return Err(format!("empty front matter key: {s}"));
+                }
+                Ok(Self::new(field.trim().parse()?).renamed(key))
+            }
+            None => Ok(Self::new(s.trim().parse()?)),
+        }
+    }
+}
+
+impl std::fmt::Display for FrontMatterField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.key == self.field.as_str() {
+            f.write_str(&self.key)
+        } else {
+            write!(f, "{}={}", self.field, self.key)
+        }
+    }
+}
+
+/// 変換範囲の指定に使う CSS セレクタ。
+///
+/// scraper のセレクタエンジンでパースし、元の文字列も保持する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssSelector {
+    source: String,
+    selector: scraper::Selector,
+}
+
+impl CssSelector {
+    /// セレクタ文字列をパースする。カンマ区切りの複数セレクタも受け付ける。
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        match scraper::Selector::parse(selector) {
+            Ok(parsed) => Ok(Self {
+                source: selector.to_string(),
+                selector: parsed,
+            }),
+            Err(err) => Err(SelectorError {
+                selector: selector.to_string(),
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    /// パース前のセレクタ文字列を返す。
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub(crate) fn matches(&self, elem: &scraper::ElementRef) -> bool {
+        self.selector.matches(elem)
+    }
+}
+
+impl std::str::FromStr for CssSelector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for CssSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// [`CssSelector::parse`] のエラー。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    selector: String,
+    message: String,
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid selector `{}`: {}", self.selector, self.message)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// タグ分類の追加・上書き。
+///
+/// 既定の分類（`script` などのスキップ対象、`nav` などのシェル要素、
+/// `div` などのラッパー）はそのまま有効で、ここで指定したタグを
+/// 追加・除外する。タグ名は大小文字を区別しない。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagRules {
+    /// 中身ごとスキップするタグ（既定の `script` / `style` / `iframe` などに追加）。
+    pub skip: Vec<String>,
+    /// 既定ではスキップするが、変換対象に戻すタグ（例: `iframe`）。
+    pub keep: Vec<String>,
+    /// `drop_interactive_shell` で除外するシェル要素に追加するタグ。
+    pub shell: Vec<String>,
+    /// `unwrap_unknown_wrappers` でアンラップするラッパーに追加するタグ。
+    pub wrapper: Vec<String>,
+    /// `div` と同じブロックとして出力するタグ（例: `my-card`）。
+    pub block: Vec<String>,
+}
+
+impl TagRules {
+    /// ビルダー: スキップするタグを追加する。
+    pub fn skip_tag(mut self, tag: impl Into<String>) -> Self {
+        self.skip.push(tag.into());
+        self
+    }
+
+    /// ビルダー: 既定のスキップ対象から外すタグを追加する。
+    pub fn keep_tag(mut self, tag: impl Into<String>) -> Self {
+        self.keep.push(tag.into());
+        self
+    }
+
+    /// ビルダー: シェル要素として扱うタグを追加する。
+    pub fn shell_tag(mut self, tag: impl Into<String>) -> Self {
+        self.shell.push(tag.into());
+        self
+    }
+
+    /// ビルダー: ラッパーとして扱うタグを追加する。
+    pub fn wrapper_tag(mut self, tag: impl Into<String>) -> Self {
+        self.wrapper.push(tag.into());
+        self
+    }
+
+    /// ビルダー: ブロックとして扱うタグを追加する。
+    pub fn block_tag(mut self, tag: impl Into<String>) -> Self {
+        self.block.push(tag.into());
+        self
+    }
+
+    /// 中身ごとスキップするか（既定の分類を含む）。
+    pub(crate) fn is_skip(&self, tag: &str) -> bool {
+        if contains_tag(&self.skip, tag) {
+            return true;
+        }
+        utils::is_skip_tag(tag) && !contains_tag(&self.keep, tag)
+    }
+
+    /// シェル要素か（既定の分類を含む）。
+    pub(crate) fn is_shell(&self, tag: &str) -> bool {
+        utils::is_shell_tag(tag) || contains_tag(&self.shell, tag)
+    }
+
+    /// アンラップ候補のラッパーか（既定の分類を含む）。
+    pub(crate) fn is_wrapper(&self, tag: &str) -> bool {
+        (utils::is_wrapper_tag(tag) && !utils::is_structural_tag(tag))
+            || contains_tag(&self.wrapper, tag)
+    }
+
+    /// ブロックとして扱うタグか。既定の分類は持たない
+    /// （`div` や `p` などはレンダラーが直接扱う）。
+    pub(crate) fn is_block(&self, tag: &str) -> bool {
+        contains_tag(&self.block, tag)
+    }
+}
+
+fn contains_tag(tags: &[String], tag: &str) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
 /// 変換オプション。モードとフラグの組み合わせを保持する。
 ///
 /// `Default` は `balanced` モードの推奨設定を返す。
@@ -79,6 +887,9 @@ impl std::fmt::Display for ConversionMode {
 pub struct ConversionOptions {
     /// 変換モード。
     pub mode: ConversionMode,
+    /// 入力 HTML の生成元。モードとは独立しており、`for_mode` は常に
+    /// [`SourceProfile::Generic`] を設定する。
+    pub profile: SourceProfile,
 
     // ── 属性保持フラグ ──────────────────────────────────────────────────
     /// `id` 属性を保持するか。有効な場合、非空の `id` を持つ要素の直前に
@@ -118,8 +929,93 @@ pub struct ConversionOptions {
     pub drop_presentation_attrs: bool,
     /// `nav`, `header`, `footer`, `aside` などのシェル要素を除外するか。
     pub drop_interactive_shell: bool,
+    /// 描画されない要素（`hidden` 属性、`aria-hidden="true"`、
+    /// `display:none` / `visibility:hidden`、閉じた `<dialog>`、
+    /// `sr-only` などのスクリーンリーダー専用クラス）を除外するか。
+    pub drop_hidden_content: bool,
+    /// `drop_hidden_content` が有効でも、スクリーンリーダー専用テキスト
+    /// （`sr-only` / `visually-hidden` など）は残すか。
+    pub keep_screen_reader_text: bool,
     /// 意味を持たないラッパー要素をアンラップするか。
     pub unwrap_unknown_wrappers: bool,
+    /// `<span style="…">` の太字・斜体・取り消し線・等幅フォントを
+    /// `**` / `*` / `~~` / `` ` `` として出力するか。エディタが生成する
+    /// HTML（Google Docs、Word など）はこの形でしか書式を表さない。
+    pub inline_style_semantics: bool,
+    /// 同じ親の中に他の表示コンテンツがない場合に限り、`<noscript>` の中身を
+    /// 変換するか。遅延読み込み画像の代替やサーバー描画の本文を復元する。
+    /// アプリケーションの殻の「JavaScript を有効にしてください」も同じ形を
+    /// とるため、明示的に有効にした場合だけ復元する。
+    pub recover_noscript: bool,
+    /// 宣言的 Shadow DOM（`<template shadowrootmode>`）の中身を変換するか。
+    /// それ以外の `<template>` は常にスキップする。
+    pub expand_shadow_roots: bool,
+    /// JSON-LD の Recipe・FAQPage が表示上の HTML より詳しい場合に、
+    /// 材料と手順（FAQ は質問と回答）を本文の末尾へ書き足すか。
+    /// `include_selectors` の指定があるときは書き足さない。
+    pub render_structured_data: bool,
+    /// SPA のハイドレーション用データ（`__NEXT_DATA__`、
+    /// `window.__NUXT__ = {...}` などの JSON）から HTML とリッチテキストを
+    /// 探して変換するか。DOM の本文が空のシェルなら置き換え、そうでなければ
+    /// DOM に表示されていない内容だけを末尾に書き足す。
+    pub recover_hydration_payloads: bool,
+
+    // ── 変換範囲 ────────────────────────────────────────────────────────
+    /// 空でなければ、いずれかに一致する要素の部分木だけを文書順に変換する。
+    /// 一致した要素の子孫で再び一致したものは、外側の部分木の一部として扱う。
+    pub include_selectors: Vec<CssSelector>,
+    /// いずれかに一致する要素を中身ごと除外する。`include_selectors` で
+    /// 選んだ部分木の内側にも適用する。
+    pub exclude_selectors: Vec<CssSelector>,
+    /// 本文らしいコンテナを採点して選び（[`extract_main_content`]）、
+    /// その中身だけを変換するか。選んだ本文の中のサイドバー・コメント・
+    /// 関連記事・共有ボタンも取り除く。`include_selectors` の指定が優先する。
+    ///
+    /// [`extract_main_content`]: crate::extract_main_content
+    pub extract_main_content: bool,
+    /// タグ分類の追加・上書き。既定は空で、組み込みの分類だけを使う。
+    pub tag_rules: TagRules,
+
+    // ── 要素別ポリシー ──────────────────────────────────────────────────
+    /// インライン `<svg>` の扱い。
+    pub svg_policy: SvgPolicy,
+    /// `data:` URI の画像の扱い。
+    pub data_uri_policy: DataUriPolicy,
+    /// 画像の `width` / `height` の書き方。
+    pub image_dimensions: ImageDimensions,
+    /// 絵文字の画像の扱い。
+    pub emoji_policy: EmojiPolicy,
+
+    // ── リンク・画像 ────────────────────────────────────────────────────
+    /// 相対 URL を解決する基準 URL（変換元ページの URL）。文書に
+    /// `<base href>` があれば、それをこの URL に対して解決したものを使う。
+    /// どちらもなければ URL は書き換えない。
+    pub base_url: Option<String>,
+    /// リンク・画像の URL のうち、基準 URL に対して解決する相対参照の種類。
+    pub url_resolution: UrlResolution,
+    /// リンク・画像の URL を整理する（リダイレクタの展開、トラッキング用の
+    /// クエリパラメータの除去、パーセントエンコーディングの正規化）。
+    pub clean_urls: bool,
+    /// `clean_urls` で取り除くクエリパラメータ。末尾の `*` は前方一致。
+    /// 空なら [`DEFAULT_TRACKING_PARAMS`]。
+    pub tracking_params: Vec<String>,
+    /// リンクの書き方（インライン・参照・脚注）。
+    pub link_style: LinkStyle,
+    /// 残すリンクの選択と、移動先のないリンク・自動リンクの扱い。
+    pub link_policy: LinkPolicy,
+    /// 相対パスで参照されたローカルの画像を出力ディレクトリの `assets/` へ
+    /// 内容のハッシュを名前にして複製し、参照を書き換える。ファイル変換 API
+    /// のみで働き、文字列の変換では無視する。
+    pub collect_local_assets: bool,
+
+    // ── 出力 ────────────────────────────────────────────────────────────
+    /// 出力の先頭に付ける front matter の形式。値は [`extract_metadata`] で
+    /// 読み取る。書き出す値が 1 つもなければ付けない。
+    ///
+    /// [`extract_metadata`]: crate::extract_metadata
+    pub front_matter: FrontMatterFormat,
+    /// front matter に書き出す項目と順序。空なら [`MetadataField::DEFAULT`]。
+    pub front_matter_fields: Vec<FrontMatterField>,
 }
 
 impl Default for ConversionOptions {
@@ -135,6 +1031,7 @@ impl ConversionOptions {
         match mode {
             ConversionMode::Balanced => Self {
                 mode,
+                profile: SourceProfile::Generic,
                 preserve_ids: true, // アンカー用途のみ
                 preserve_classes: false,
                 preserve_data_attrs: false,
@@ -142,10 +1039,35 @@ impl ConversionOptions {
                 preserve_unknown_attrs: false,
                 drop_presentation_attrs: true,
                 drop_interactive_shell: false,
+                drop_hidden_content: true,
+                keep_screen_reader_text: false,
                 unwrap_unknown_wrappers: false,
+                inline_style_semantics: true,
+                recover_noscript: false,
+                expand_shadow_roots: true,
+                render_structured_data: false,
+                recover_hydration_payloads: false,
+                include_selectors: Vec::new(),
+                exclude_selectors: Vec::new(),
+                extract_main_content: false,
+                tag_rules: TagRules::default(),
+                svg_policy: SvgPolicy::AltText,
+                data_uri_policy: DataUriPolicy::Inline,
+                image_dimensions: ImageDimensions::Drop,
+                emoji_policy: EmojiPolicy::Unicode,
+                base_url: None,
+                url_resolution: UrlResolution::All,
+                clean_urls: false,
+                tracking_params: Vec::new(),
+                link_style: LinkStyle::Inline,
+                link_policy: LinkPolicy::default(),
+                collect_local_assets: false,
+                front_matter: FrontMatterFormat::None,
+                front_matter_fields: Vec::new(),
             },
             ConversionMode::Strict => Self {
                 mode,
+                profile: SourceProfile::Generic,
                 preserve_ids: true,
                 preserve_classes: true,
                 preserve_data_attrs: true,
@@ -153,10 +1075,35 @@ impl ConversionOptions {
                 preserve_unknown_attrs: true,
                 drop_presentation_attrs: false,
                 drop_interactive_shell: false,
+                drop_hidden_content: false,
+                keep_screen_reader_text: false,
                 unwrap_unknown_wrappers: false,
+                inline_style_semantics: true,
+                recover_noscript: false,
+                expand_shadow_roots: true,
+                render_structured_data: false,
+                recover_hydration_payloads: false,
+                include_selectors: Vec::new(),
+                exclude_selectors: Vec::new(),
+                extract_main_content: false,
+                tag_rules: TagRules::default(),
+                svg_policy: SvgPolicy::AltText,
+                data_uri_policy: DataUriPolicy::Inline,
+                image_dimensions: ImageDimensions::Drop,
+                emoji_policy: EmojiPolicy::Unicode,
+                base_url: None,
+                url_resolution: UrlResolution::All,
+                clean_urls: false,
+                tracking_params: Vec::new(),
+                link_style: LinkStyle::Inline,
+                link_policy: LinkPolicy::default(),
+                collect_local_assets: false,
+                front_matter: FrontMatterFormat::None,
+                front_matter_fields: Vec::new(),
             },
             ConversionMode::Minimal => Self {
                 mode,
+                profile: SourceProfile::Generic,
                 preserve_ids: false,
                 preserve_classes: false,
                 preserve_data_attrs: false,
@@ -164,10 +1111,41 @@ impl ConversionOptions {
                 preserve_unknown_attrs: false,
                 drop_presentation_attrs: true,
                 drop_interactive_shell: true,
+                drop_hidden_content: true,
+                keep_screen_reader_text: false,
                 unwrap_unknown_wrappers: true,
+                inline_style_semantics: false,
+                recover_noscript: false,
+                expand_shadow_roots: true,
+                render_structured_data: false,
+                recover_hydration_payloads: false,
+                include_selectors: Vec::new(),
+                exclude_selectors: Vec::new(),
+                extract_main_content: false,
+                tag_rules: TagRules::default(),
+                svg_policy: SvgPolicy::AltText,
+                // 埋め込み画像の巨大な data: URI はトークンを浪費する
+                data_uri_policy: DataUriPolicy::AltText,
+                image_dimensions: ImageDimensions::Drop,
+                emoji_policy: EmojiPolicy::Unicode,
+                base_url: None,
+                url_resolution: UrlResolution::All,
+                clean_urls: false,
+                tracking_params: Vec::new(),
+                link_style: LinkStyle::Inline,
+                // 内部リンクの URL は LLM 向けの要約ではほぼ意味を持たない
+                link_policy: LinkPolicy {
+                    filter: LinkFilter::ExternalOnly,
+                    strip_dead: true,
+                    autolink: true,
+                },
+                collect_local_assets: false,
+                front_matter: FrontMatterFormat::None,
+                front_matter_fields: Vec::new(),
             },
             ConversionMode::Semantic => Self {
                 mode,
+                profile: SourceProfile::Generic,
                 preserve_ids: true,
                 preserve_classes: false,
                 preserve_data_attrs: false,
@@ -175,10 +1153,35 @@ impl ConversionOptions {
                 preserve_unknown_attrs: false,
                 drop_presentation_attrs: true,
                 drop_interactive_shell: false,
+                drop_hidden_content: true,
+                keep_screen_reader_text: true,
                 unwrap_unknown_wrappers: true,
+                inline_style_semantics: true,
+                recover_noscript: false,
+                expand_shadow_roots: true,
+                render_structured_data: false,
+                recover_hydration_payloads: false,
+                include_selectors: Vec::new(),
+                exclude_selectors: Vec::new(),
+                extract_main_content: false,
+                tag_rules: TagRules::default(),
+                svg_policy: SvgPolicy::AltText,
+                data_uri_policy: DataUriPolicy::Inline,
+                image_dimensions: ImageDimensions::Drop,
+                emoji_policy: EmojiPolicy::Unicode,
+                base_url: None,
+                url_resolution: UrlResolution::All,
+                clean_urls: false,
+                tracking_params: Vec::new(),
+                link_style: LinkStyle::Inline,
+                link_policy: LinkPolicy::default(),
+                collect_local_assets: false,
+                front_matter: FrontMatterFormat::None,
+                front_matter_fields: Vec::new(),
             },
             ConversionMode::Preserve => Self {
                 mode,
+                profile: SourceProfile::Generic,
                 preserve_ids: true,
                 preserve_classes: true,
                 preserve_data_attrs: true,
@@ -186,7 +1189,31 @@ impl ConversionOptions {
                 preserve_unknown_attrs: true,
                 drop_presentation_attrs: false,
                 drop_interactive_shell: false,
+                drop_hidden_content: false,
+                keep_screen_reader_text: false,
                 unwrap_unknown_wrappers: false,
+                inline_style_semantics: true,
+                recover_noscript: false,
+                expand_shadow_roots: true,
+                render_structured_data: false,
+                recover_hydration_payloads: false,
+                include_selectors: Vec::new(),
+                exclude_selectors: Vec::new(),
+                extract_main_content: false,
+                tag_rules: TagRules::default(),
+                svg_policy: SvgPolicy::Raw,
+                data_uri_policy: DataUriPolicy::Inline,
+                image_dimensions: ImageDimensions::Html,
+                emoji_policy: EmojiPolicy::Image,
+                base_url: None,
+                url_resolution: UrlResolution::All,
+                clean_urls: false,
+                tracking_params: Vec::new(),
+                link_style: LinkStyle::Inline,
+                link_policy: LinkPolicy::default(),
+                collect_local_assets: false,
+                front_matter: FrontMatterFormat::None,
+                front_matter_fields: Vec::new(),
             },
         }
     }
@@ -197,6 +1224,12 @@ impl ConversionOptions {
         self
     }
 
+    /// ビルダー: 入力 HTML の生成元を設定する。
+    pub fn profile(mut self, profile: SourceProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
     /// ビルダー: `id` 属性の保持を設定する。
     pub fn preserve_ids(mut self, v: bool) -> Self {
         self.preserve_ids = v;
@@ -219,4 +1252,148 @@ impl ConversionOptions {
         self.drop_interactive_shell = v;
         self
     }
+
+    /// ビルダー: 非表示要素の除外を設定する。
+    pub fn drop_hidden_content(mut self, v: bool) -> Self {
+        self.drop_hidden_content = v;
+        self
+    }
+
+    /// ビルダー: スクリーンリーダー専用テキストの保持を設定する。
+    pub fn keep_screen_reader_text(mut self, v: bool) -> Self {
+        self.keep_screen_reader_text = v;
+        self
+    }
+
+    /// ビルダー: インラインスタイル由来の強調の出力を設定する。
+    pub fn inline_style_semantics(mut self, v: bool) -> Self {
+        self.inline_style_semantics = v;
+        self
+    }
+
+    /// ビルダー: `<noscript>` の中身の復元を設定する。
+    pub fn recover_noscript(mut self, v: bool) -> Self {
+        self.recover_noscript = v;
+        self
+    }
+
+    /// ビルダー: 宣言的 Shadow DOM の展開を設定する。
+    pub fn expand_shadow_roots(mut self, v: bool) -> Self {
+        self.expand_shadow_roots = v;
+        self
+    }
+
+    /// ビルダー: ハイドレーション用データからの本文の復元を設定する。
+    pub fn recover_hydration_payloads(mut self, v: bool) -> Self {
+        self.recover_hydration_payloads = v;
+        self
+    }
+
+    /// ビルダー: 構造化データからの本文の補完を設定する。
+    pub fn render_structured_data(mut self, v: bool) -> Self {
+        self.render_structured_data = v;
+        self
+    }
+
+    /// ビルダー: インライン `<svg>` の扱いを設定する。
+    pub fn svg_policy(mut self, v: SvgPolicy) -> Self {
+        self.svg_policy = v;
+        self
+    }
+
+    /// ビルダー: `data:` URI の画像の扱いを設定する。
+    pub fn data_uri_policy(mut self, v: DataUriPolicy) -> Self {
+        self.data_uri_policy = v;
+        self
+    }
+
+    /// ビルダー: 画像の寸法の書き方を設定する。
+    pub fn image_dimensions(mut self, v: ImageDimensions) -> Self {
+        self.image_dimensions = v;
+        self
+    }
+
+    /// ビルダー: 絵文字の画像の扱いを設定する。
+    pub fn emoji_policy(mut self, v: EmojiPolicy) -> Self {
+        self.emoji_policy = v;
+        self
+    }
+
+    /// ビルダー: 相対 URL を解決する基準 URL を設定する。
+    pub fn base_url(mut self, v: impl Into<String>) -> Self {
+        self.base_url = Some(v.into());
+        self
+    }
+
+    /// ビルダー: 解決する相対参照の種類を設定する。
+    pub fn url_resolution(mut self, v: UrlResolution) -> Self {
+        self.url_resolution = v;
+        self
+    }
+
+    /// ビルダー: URL を整理するかを設定する。
+    pub fn clean_urls(mut self, v: bool) -> Self {
+        self.clean_urls = v;
+        self
+    }
+
+    /// ビルダー: 取り除くクエリパラメータを設定する。
+    pub fn tracking_params(mut self, v: Vec<String>) -> Self {
+        self.tracking_params = v;
+        self
+    }
+
+    /// ビルダー: リンクの扱いを設定する。
+    pub fn link_policy(mut self, v: LinkPolicy) -> Self {
+        self.link_policy = v;
+        self
+    }
+
+    /// ビルダー: リンクの書き方を設定する。
+    pub fn link_style(mut self, v: LinkStyle) -> Self {
+        self.link_style = v;
+        self
+    }
+
+    /// ビルダー: ローカルの画像を複製するかを設定する。
+    pub fn collect_local_assets(mut self, v: bool) -> Self {
+        self.collect_local_assets = v;
+        self
+    }
+
+    /// ビルダー: 変換する部分木のセレクタを設定する。
+    pub fn include_selectors(mut self, v: Vec<CssSelector>) -> Self {
+        self.include_selectors = v;
+        self
+    }
+
+    /// ビルダー: 除外する要素のセレクタを設定する。
+    pub fn exclude_selectors(mut self, v: Vec<CssSelector>) -> Self {
+        self.exclude_selectors = v;
+        self
+    }
+
+    /// ビルダー: タグ分類の追加・上書きを設定する。
+    pub fn tag_rules(mut self, v: TagRules) -> Self {
+        self.tag_rules = v;
+        self
+    }
+
+    /// ビルダー: 本文抽出を設定する。
+    pub fn extract_main_content(mut self, v: bool) -> Self {
+        self.extract_main_content = v;
+        self
+    }
+
+    /// ビルダー: front matter の形式を設定する。
+    pub fn front_matter(mut self, v: FrontMatterFormat) -> Self {
+        self.front_matter = v;
+        self
+    }
+
+    /// ビルダー: front matter に書き出す項目を設定する。
+    pub fn front_matter_fields(mut self, v: Vec<FrontMatterField>) -> Self {
+        self.front_matter_fields = v;
+        self
+    }
 }
@@ -0,0 +1,245 @@
+//! 生成元プロファイル固有の前処理
+//!
+//! [`SourceProfile`] ごとに、生成ツールが出力する癖のある要素を判定する。
+//! トラバーサルは Enter 時と Leave 時の両方でこの判定を呼ぶため、
+//! 判定は要素とその子孫だけから決まる純粋な関数にしておく。
+
+use ego_tree::NodeRef;
+use scraper::Node;
+
+use crate::options::SourceProfile;
+use crate::utils;
+
+/// プロファイルが要素に対して指示する処理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProfileAction {
+    /// 要素を中身ごと出力しない。
+    Drop,
+    /// タグ自体は出力せず、子だけを辿る。
+    Unwrap,
+    /// 段落と同じブロックとして出力する。
+    Block,
+    /// 段落を擬似リストの項目として出力する（`level` は 1 始まり）。
+    ListItem { level: usize, ordered: bool },
+}
+
+/// `profile` が `node` に固有の処理を指示するか。`None` なら通常通り処理する。
+pub(crate) fn classify(node: NodeRef<Node>, profile: SourceProfile) -> Option<ProfileAction> {
+    match profile {
+        SourceProfile::Generic => None,
+        SourceProfile::Office => office(node),
+        SourceProfile::Email => email(node),
+    }
+}
+
+// ─── Office（Word / Outlook / Google Docs） ─────────────────────────────────
+
+fn office(node: NodeRef<Node>) -> Option<ProfileAction> {
+    let elem = node.value().as_element()?;
+    let tag = elem.name();
+
+    // 名前空間付き要素: <o:p>（空段落の &nbsp;）と <v:*>（VML 図形）は
+    // 中身ごと捨てる。<w:sdt> や <st1:*> などは本文を包むだけなので外す
+    if let Some((prefix, _)) = tag.split_once(':') {
+        return Some(if prefix == "o" || prefix == "v" {
+            ProfileAction::Drop
+        } else {
+            ProfileAction::Unwrap
+        });
+    }
+
+    match tag {
+        // 擬似リストの行頭記号（"·" や "1." とその後の空白）
+        "span" if is_mso_list_ignore(elem) => Some(ProfileAction::Drop),
+        // Google Docs がクリップボード全体を包む太字でない <b>
+        "b" if is_docs_wrapper(elem) => Some(ProfileAction::Unwrap),
+        "p" => mso_list_level(elem).map(|level| ProfileAction::ListItem {
+            level,
+            ordered: is_ordered_marker(node),
+        }),
+        _ => None,
+    }
+}
+
+/// `style` に `mso-list: Ignore` を持つ要素か。
+fn is_mso_list_ignore(elem: &scraper::node::Element) -> bool {
+    elem.attr("style").is_some_and(|style| {
+        utils::style_declarations(style).any(|(prop, value)| {
+            prop.eq_ignore_ascii_case("mso-list") && value.eq_ignore_ascii_case("ignore")
+        })
+    })
+}
+
+/// `<b id="docs-internal-guid-…">` または `<b style="font-weight:normal">`。
+fn is_docs_wrapper(elem: &scraper::node::Element) -> bool {
+    if elem
+        .id()
+        .is_some_and(|id| id.starts_with("docs-internal-guid"))
+    {
+        return true;
+    }
+    elem.attr("style").is_some_and(|style| {
+        utils::style_declarations(style).any(|(prop, value)| {
+            prop.eq_ignore_ascii_case("font-weight")
+                && (value.eq_ignore_ascii_case("normal") || value == "400")
+        })
+    })
+}
+
+/// Word の擬似リスト段落の階層（1 始まり）。
+///
+/// `style="mso-list: l0 level2 lfo1"` の `levelN` を読む。レベルの指定が
+/// なく `class="MsoListParagraph…"` だけを持つ段落は 1 段目とみなす。
+fn mso_list_level(elem: &scraper::node::Element) -> Option<usize> {
+    let from_style = elem.attr("style").and_then(|style| {
+        utils::style_declarations(style)
+            .filter(|(prop, _)| prop.eq_ignore_ascii_case("mso-list"))
+            .find_map(|(_, value)| {
+                value.split_whitespace().find_map(|part| {
+                    let digits = part.strip_prefix("level")?;
+                    digits.parse::<usize>().ok().filter(|&n| n > 0)
+                })
+            })
+    });
+    if from_style.is_some() {
+        return from_style;
+    }
+    elem.classes()
+        .any(|cls| cls.starts_with("MsoListParagraph"))
+        .then_some(1)
+}
+
+/// 段落内の `mso-list: Ignore` の記号が番号（`1.` / `a)` / `iv.` など）か。
+fn is_ordered_marker(paragraph: NodeRef<Node>) -> bool {
+    let Some(marker) = paragraph.descendants().find(|d| {
+        d.value()
+            .as_element()
+            .is_some_and(|e| e.name() == "span" && is_mso_list_ignore(e))
+    }) else {
+        return false;
+    };
+    let text: String = marker
+        .descendants()
+        .filter_map(|d| d.value().as_text().map(|t| &**t))
+        .collect();
+    let text = text.trim_matches(|c: char| c.is_whitespace() || c == '\u{a0}');
+    let Some(body) = text.strip_suffix(['.', ')']) else {
+        return false;
+    };
+    !body.is_empty() && body.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+// ─── HTML メール ───────────────────────────────────────────────────────────
+
+/// トラッキングピクセルの配信元とみなす `src` の部分文字列（小文字）。
+const TRACKER_PATTERNS: &[&str] = &[
+    "/track/open",
+    "/trk/open",
+    "/wf/open",
+    "list-manage.com/track",
+    "google-analytics.com/collect",
+    "doubleclick.net",
+    "mailtrack.io",
+    "/open.aspx",
+    "/beacon",
+];
+
+/// スペーサー画像とみなすファイル名（小文字）。
+const SPACER_FILES: &[&str] = &[
+    "spacer.gif",
+    "spacer.png",
+    "blank.gif",
+    "clear.gif",
+    "pixel.gif",
+    "transparent.gif",
+    "s.gif",
+];
+
+/// プレヘッダーを示す慣用クラス（小文字、部分一致）。
+const PREHEADER_CLASSES: &[&str] = &["preheader", "preview-text", "previewtext"];
+
+fn email(node: NodeRef<Node>) -> Option<ProfileAction> {
+    let elem = node.value().as_element()?;
+    if is_preheader(elem) {
+        return Some(ProfileAction::Drop);
+    }
+    match elem.name() {
+        // HTML メールの表はほぼすべてレイアウト用。セルを段落として並べ、
+        // 行や表自体は出力しない
+        "table" | "thead" | "tbody" | "tfoot" | "tr" => Some(ProfileAction::Unwrap),
+        "td" | "th" => Some(ProfileAction::Block),
+        // 見た目だけの旧式タグ。中身のテキストとして出力する
+        "center" => Some(ProfileAction::Block),
+        "font" => Some(ProfileAction::Unwrap),
+        "img" if is_tracking_image(elem) => Some(ProfileAction::Drop),
+        _ => None,
+    }
+}
+
+/// 受信一覧のプレビュー用に埋め込まれ、本文には表示されないテキストか。
+///
+/// プレヘッダー用のクラス、`display:none`、`mso-hide:all` を見る。
+/// `max-height:0`・`font-size:0`・`opacity:0` は `overflow:hidden` と
+/// 組み合わさったときだけ隠しているとみなす。MJML などはインライン
+/// ブロックの隙間を消すためにカラムを包むセルへ `font-size:0px` を付け、
+/// 本文はその中にあるため。
+fn is_preheader(elem: &scraper::node::Element) -> bool {
+    if elem.classes().any(|cls| {
+        let cls = cls.to_ascii_lowercase();
+        PREHEADER_CLASSES.iter().any(|c| cls.contains(c))
+    }) {
+        return true;
+    }
+    let Some(style) = elem.attr("style") else {
+        return false;
+    };
+    let (mut zero, mut clipped) = (false, false);
+    for (prop, value) in utils::style_declarations(style) {
+        let value = value.to_ascii_lowercase();
+        match prop.to_ascii_lowercase().as_str() {
+            "display" if value == "none" => return true,
+            "mso-hide" if value == "all" => return true,
+            "max-height" | "font-size" | "opacity" if is_zero_length(&value) => zero = true,
+            "overflow" if value == "hidden" => clipped = true,
+            _ => {}
+        }
+    }
+    zero && clipped
+}
+
+/// 1×1 以下の画像、スペーサー画像、トラッキングピクセルか。
+fn is_tracking_image(elem: &scraper::node::Element) -> bool {
+    let tiny = |v: Option<&str>| v.and_then(parse_px).is_some_and(|n| n <= 1.0);
+    if tiny(elem.attr("width")) || tiny(elem.attr("height")) {
+        return true;
+    }
+    if let Some(style) = elem.attr("style") {
+        let tiny_style = utils::style_declarations(style).any(|(prop, value)| {
+            (prop.eq_ignore_ascii_case("width") || prop.eq_ignore_ascii_case("height"))
+                && parse_px(value).is_some_and(|n| n <= 1.0)
+        });
+        if tiny_style {
+            return true;
+        }
+    }
+    let Some(src) = elem.attr("src") else {
+        return false;
+    };
+    let src = src.to_ascii_lowercase();
+    let path = src.split(['?', '#']).next().unwrap_or("");
+    let file = path.rsplit('/').next().unwrap_or("");
+    SPACER_FILES.contains(&file) || TRACKER_PATTERNS.iter().any(|p| src.contains(p))
+}
+
+/// `1` / `1px` / `0.5px` を数値として読む。`%` など他の単位は `None`。
+fn parse_px(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value).trim();
+    number.parse().ok()
+}
+
+/// `0` / `0px` / `0em` / `0%` などの長さ 0（単位は問わない）。
+fn is_zero_length(value: &str) -> bool {
+    let number = value.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%');
+    number.parse::<f32>().is_ok_and(|n| n == 0.0)
+}
@@ -1,6 +1,11 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Write;
 
-use crate::utils;
+use crate::assets::{Asset, AssetRoot};
+use crate::options::{ImageDimensions, LinkFilter, LinkPolicy, LinkStyle, UrlResolution};
+use crate::url::{self, BaseUrl};
+use crate::utils::{self, StyleEmphasis};
 
 #[derive(Debug, Clone)]
 pub enum ListKind {
@@ -13,12 +18,25 @@ pub struct ListContext {
     pub kind: ListKind,
 }
 
+/// 最も外側のリンクの出力方法（[`LinkPolicy`] による）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkAction {
+    /// Markdown のリンクにする。
+    Link,
+    /// テキストだけを書く（キャプチャしない）。
+    Text,
+    /// テキストごと捨てる。
+    Drop,
+}
+
 #[derive(Debug)]
 enum InlineCapture {
     Link {
         href: String,
         title: Option<String>,
         buf: String,
+        /// 次の文字の前に出す、畳み込み済みの空白があるか。
+        space: bool,
     },
     None,
 }
@@ -35,10 +53,64 @@ pub struct MarkdownRenderer {
     inline_capture: InlineCapture,
     capture_depth: usize,
     link_depth: usize,
+    /// 開いている擬似リスト（Word の `mso-list` 段落）の階層数。0 なら閉じている。
+    pseudo_list_depth: usize,
+    /// 擬似リストを開いた時点の `list_stack` の長さ。擬似リストの階層はこの上に積む。
+    pseudo_list_base: usize,
+    /// アセットを書き出す変換（ファイル変換 API）か否か。
+    collect_assets: bool,
+    assets: Vec<Asset>,
+    /// ローカルの画像を探す場所。`None` なら複製しない。
+    asset_root: Option<AssetRoot>,
+    /// リンク・画像の相対 URL を解決する基準 URL。
+    base_url: Option<BaseUrl>,
+    url_resolution: UrlResolution,
+    /// URL から取り除くクエリパラメータ。`None` なら URL を整理しない。
+    tracking_params: Option<Vec<String>>,
+    /// `href` の書き換え表（サイト変換で、入力同士のリンクを出力へ向ける）。
+    links: HashMap<String, String>,
+    link_style: LinkStyle,
+    link_policy: LinkPolicy,
+    /// 処理中の最も外側のリンクの出力方法。
+    link_action: LinkAction,
+    /// 参照リンク・脚注の定義（URL と title）。添字 + 1 が番号。
+    link_definitions: Vec<(String, Option<String>)>,
+    /// URL → 定義の番号。
+    link_numbers: HashMap<String, usize>,
+    /// 書き出し済みの定義の数（`ReferencePerSection`）。
+    written_definitions: usize,
+    image_dimensions: ImageDimensions,
+    /// 処理中の `<figure>` の唯一の画像を補う説明文。画像の出力で消費する。
+    figure_caption: Option<String>,
+    /// 処理中の `<figcaption>` の本文が始まる `output` 上の位置（`*` の直後）。
+    caption_start: Option<usize>,
+}
+
+/// 画像の `width` / `height` 属性。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImageSize<'a> {
+    pub width: Option<&'a str>,
+    pub height: Option<&'a str>,
+}
+
+impl<'a> ImageSize<'a> {
+    /// 要素の `width` / `height` 属性。
+    pub fn of(elem: &'a scraper::node::Element) -> Self {
+        Self {
+            width: elem.attr("width"),
+            height: elem.attr("height"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        [self.width, self.height]
+            .iter()
+            .all(|v| v.is_none_or(|v| v.trim().is_empty()))
+    }
 }
 
 impl MarkdownRenderer {
-    pub fn new(capacity: usize) -> Self {
+    pub fn new(capacity: usize, collect_assets: bool) -> Self {
         Self {
             output: String::with_capacity(capacity),
             list_stack: Vec::with_capacity(8),
@@ -51,6 +123,94 @@ impl MarkdownRenderer {
             inline_capture: InlineCapture::None,
             capture_depth: 0,
             link_depth: 0,
+            pseudo_list_depth: 0,
+            pseudo_list_base: 0,
+            collect_assets,
+            assets: Vec::new(),
+            asset_root: None,
+            base_url: None,
+            url_resolution: UrlResolution::All,
+            tracking_params: None,
+            links: HashMap::new(),
+            link_style: LinkStyle::Inline,
+            link_policy: LinkPolicy::default(),
+            link_action: LinkAction::Link,
+            link_definitions: Vec::new(),
+            link_numbers: HashMap::new(),
+            written_definitions: 0,
+            image_dimensions: ImageDimensions::Drop,
+            figure_caption: None,
+            caption_start: None,
+        }
+    }
+
+    /// `href` の書き換え表を設定する。表にある `href` は基準 URL で解決しない。
+    pub fn set_links(&mut self, links: HashMap<String, String>) {
+        self.links = links;
+    }
+
+    /// リンクの書き方と扱いを設定する。
+    pub fn set_link_style(&mut self, style: LinkStyle, policy: LinkPolicy) {
+        self.link_style = style;
+        self.link_policy = policy;
+    }
+
+    /// 解決済みの `href` のリンクをどう出力するか。
+    fn link_action(&self, href: Option<&str>) -> LinkAction {
+        let policy = self.link_policy;
+        if policy.filter == LinkFilter::Drop {
+            return LinkAction::Drop;
+        }
+        let dead = href.is_none_or(url::is_dead_link);
+        let keep = match policy.filter {
+            LinkFilter::TextOnly => false,
+            LinkFilter::ExternalOnly => {
+                href.is_some_and(|h| !dead && url::is_external(h, self.base_url.as_ref()))
+            }
+            _ => true,
+        };
+        if keep && !(dead && policy.strip_dead) {
+            LinkAction::Link
+        } else {
+            LinkAction::Text
+        }
+    }
+
+    /// リンク・画像の相対 URL を解決する基準 URL を設定する。
+    pub fn set_base_url(&mut self, base: Option<BaseUrl>, resolution: UrlResolution) {
+        self.base_url = base;
+        self.url_resolution = resolution;
+    }
+
+    /// 画像の寸法の書き方を設定する。
+    pub fn set_image_dimensions(&mut self, v: ImageDimensions) {
+        self.image_dimensions = v;
+    }
+
+    /// 次に出力する画像を補う `<figure>` の説明文を設定する。
+    pub fn set_figure_caption(&mut self, caption: Option<String>) {
+        self.figure_caption = caption;
+    }
+
+    /// URL を整理する設定。`None` なら整理しない。
+    pub fn set_url_cleaning(&mut self, tracking_params: Option<Vec<String>>) {
+        self.tracking_params = tracking_params;
+    }
+
+    /// `href` / `src` の値を基準 URL に対して解決し、設定があれば整理する。
+    fn resolve_url<'u>(&self, url: &'u str) -> Cow<'u, str> {
+        let resolved = self
+            .base_url
+            .as_ref()
+            .and_then(|base| base.resolve(url, self.url_resolution))
+            .map_or(Cow::Borrowed(url), Cow::Owned);
+        match self
+            .tracking_params
+            .as_deref()
+            .and_then(|params| url::clean_url(&resolved, params))
+        {
+            Some(cleaned) => Cow::Owned(cleaned),
+            None => resolved,
         }
     }
 
@@ -83,6 +243,7 @@ impl MarkdownRenderer {
     }
 
     fn begin_block(&mut self) {
+        self.close_pseudo_list();
         // プレフィックスはここでは出力しない。
         // コンテンツ書き込み時に emit_pending_prefix() が担う。
         self.ensure_newlines(2);
@@ -123,10 +284,10 @@ impl MarkdownRenderer {
             return;
         }
         if self.capture_depth > 0 {
-            if let InlineCapture::Link { buf, .. } = &mut self.inline_capture {
-                let mut _ls = false;
+            if let InlineCapture::Link { buf, space, .. } = &mut self.inline_capture {
                 let mut _al = false;
-                utils::write_normalised(text, buf, &mut _ls, false, &mut _al);
+                let at_start = buf.is_empty();
+                utils::write_normalised(text, buf, space, at_start, &mut _al);
             }
             return;
         }
@@ -147,6 +308,178 @@ impl MarkdownRenderer {
         }
     }
 
+    // ─── 画像・インライン HTML ────────────────────────────────────────────
+
+    /// `![alt](src "title")` を出力する。リンクキャプチャ中はリンクテキスト側へ
+    /// 書き込むため、`<a><img></a>` は `[![alt](src)](href)` になる。
+    /// 寸法は [`ImageDimensions`] に従って書く。
+    pub fn push_image(&mut self, alt: &str, src: &str, title: Option<&str>, size: ImageSize) {
+        // <figure> の唯一の画像は、alt（alt があれば title）を説明文で補う
+        let caption = self.figure_caption.take();
+        let (alt, title) = match caption.as_deref() {
+            Some(c) if alt.trim().is_empty() => (c, title),
+            Some(c) if title.is_none_or(|t| t.trim().is_empty()) => (alt, Some(c)),
+            _ => (alt, title),
+        };
+        if self.image_dimensions == ImageDimensions::Html && !size.is_empty() {
+            let mut html = String::from("<img");
+            utils::push_html_attr(&mut html, "src", src);
+            utils::push_html_attr(&mut html, "alt", alt);
+            for (name, value) in [
+                ("title", title),
+                ("width", size.width),
+                ("height", size.height),
+            ] {
+                if let Some(value) = value {
+                    utils::push_html_attr(&mut html, name, value);
+                }
+            }
+            html.push('>');
+            // リンクテキストの中では寸法のない Markdown の画像にする
+            if self.push_inline_html(&html) {
+                return;
+            }
+        }
+        let mut md = String::with_capacity(alt.len() + src.len() + 6);
+        md.push_str("![");
+        utils::push_alt_text(&mut md, alt);
+        md.push_str("](");
+        utils::push_link_destination(&mut md, src);
+        if let Some(t) = title {
+            utils::push_link_title(&mut md, t);
+        }
+        md.push(')');
+        if self.image_dimensions == ImageDimensions::Attributes {
+            utils::push_attribute_list(
+                &mut md,
+                &[
+                    ("width", size.width.unwrap_or("")),
+                    ("height", size.height.unwrap_or("")),
+                ],
+            );
+        }
+        self.push_inline_markdown(&md);
+    }
+
+    /// 組み立て済みの行内 Markdown をエスケープせずに出力する。リンク
+    /// キャプチャ中はリンクテキスト側へ書き込む。
+    pub fn push_inline_markdown(&mut self, md: &str) {
+        if self.capture_marker(md, true) {
+            return;
+        }
+        self.emit_pending_prefix();
+        self.flush_space();
+        self.output.push_str(md);
+        self.newlines_emitted = 0;
+        self.at_line_start = false;
+        self.last_was_space = false;
+    }
+
+    /// 行内 HTML 断片をそのまま出力する。リンクキャプチャ中とコードブロック内は
+    /// 出力できないため何もせず `false` を返す。
+    pub fn push_inline_html(&mut self, html: &str) -> bool {
+        if self.capture_depth > 0 || self.in_pre {
+            return false;
+        }
+        self.emit_pending_prefix();
+        self.flush_space();
+        self.push_raw(html);
+        true
+    }
+
+    // ─── インラインスタイル由来の強調 ──────────────────────────────────────
+
+    /// `style` 属性から読み取った強調の開始記号を出力する。
+    /// 等幅（`code`）は他の強調と併用せず、コードスパンだけにする。
+    pub fn open_emphasis(&mut self, e: StyleEmphasis) {
+        if self.in_pre || e.is_empty() {
+            return;
+        }
+        if self.capture_depth > 0 {
+            for marker in emphasis_markers(e) {
+                self.capture_marker(marker, true);
+            }
+            return;
+        }
+        self.emit_pending_prefix();
+        self.flush_space();
+        for marker in emphasis_markers(e) {
+            self.output.push_str(marker);
+        }
+        self.newlines_emitted = 0;
+        self.at_line_start = false;
+    }
+
+    /// [`open_emphasis`](Self::open_emphasis) と逆順に終了記号を出力する。
+    pub fn close_emphasis(&mut self, e: StyleEmphasis) {
+        if self.in_pre || e.is_empty() {
+            return;
+        }
+        for marker in emphasis_markers(e).iter().rev() {
+            self.close_marker(marker);
+        }
+    }
+
+    /// `strong` / `em` の開始記号を出力する。
+    fn open_marker(&mut self, marker: &str) {
+        if self.capture_marker(marker, true) {
+            return;
+        }
+        self.flush_space();
+        self.output.push_str(marker);
+        self.newlines_emitted = 0;
+        self.at_line_start = false;
+    }
+
+    /// 強調の終了記号を出力する。
+    fn close_marker(&mut self, marker: &str) {
+        if self.capture_marker(marker, false) {
+            return;
+        }
+        self.output.push_str(marker);
+        self.newlines_emitted = 0;
+    }
+
+    /// リンクキャプチャ中なら、行内の記号 `marker` をリンクテキスト側へ書き込んで
+    /// `true` を返す。開始側（`opening`）では保留中の空白を先に出す。
+    fn capture_marker(&mut self, marker: &str, opening: bool) -> bool {
+        if self.capture_depth == 0 {
+            return false;
+        }
+        if let InlineCapture::Link { buf, space, .. } = &mut self.inline_capture {
+            if opening && *space && !buf.is_empty() {
+                buf.push(' ');
+                *space = false;
+            }
+            buf.push_str(marker);
+        }
+        true
+    }
+
+    // ─── アセット ──────────────────────────────────────────────────────────
+
+    /// アセットをファイルとして書き出す変換か否か。
+    pub fn collects_assets(&self) -> bool {
+        self.collect_assets
+    }
+
+    /// アセットを登録する。同じ内容（同じファイル名）は 1 件に集約する。
+    pub fn add_asset(&mut self, asset: Asset) {
+        if !self.assets.iter().any(|a| a.file_name == asset.file_name) {
+            self.assets.push(asset);
+        }
+    }
+
+    /// 相対パスで参照されたローカルの画像を探す場所を設定する。
+    pub fn set_asset_root(&mut self, root: Option<AssetRoot>) {
+        self.asset_root = root;
+    }
+
+    /// 蓄積したアセットを取り出す。
+    pub fn take_assets(&mut self) -> Vec<Asset> {
+        std::mem::take(&mut self.assets)
+    }
+
     // ─── アンカー（id 属性） ──────────────────────────────────────────────
 
     /// `preserve_ids` が有効かつ非空の `id` を持つ要素の「先頭コンテンツ」として
@@ -186,6 +519,92 @@ impl MarkdownRenderer {
         self.push_raw(&anchor);
     }
 
+    /// 改行・インデント・リスト記号を出力して項目を始める。
+    fn begin_list_item(&mut self) {
+        self.ensure_newlines(1);
+        self.emit_pending_prefix();
+        let depth = self.list_stack.len().saturating_sub(1);
+        for _ in 0..depth {
+            self.output.push_str("  ");
+        }
+        if let Some(ctx) = self.list_stack.last_mut() {
+            match &mut ctx.kind {
+                ListKind::Unordered => self.output.push_str("- "),
+                ListKind::Ordered { counter } => {
+                    let n = *counter;
+                    *counter += 1;
+                    push_usize(&mut self.output, n);
+                    self.output.push_str(". ");
+                }
+            }
+        }
+        self.newlines_emitted = 0;
+        self.at_line_start = false;
+        self.last_was_space = false;
+    }
+
+    /// 段落相当のブロックとして扱う要素（メールのレイアウト用セルなど）。
+    pub fn open_block(&mut self) {
+        self.begin_block();
+    }
+
+    pub fn close_block(&mut self) {
+        self.end_block();
+    }
+
+    // ─── 擬似リスト ────────────────────────────────────────────────────────
+    //
+    // Word のリストは `<ul>`/`<ol>` ではなく、`mso-list` の階層を持つ
+    // 連続した段落として出力される。`list_stack` の末尾に擬似リストの
+    // 階層を積み、階層の増減に合わせて伸縮させる。擬似リスト以外の
+    // ブロックや本物のリスト（`ul` / `ol` / `li` の開始・終了）に出会った
+    // 時点で擬似リストは閉じ、積む前の長さ（`pseudo_list_base`）に戻す。
+
+    /// 階層 `level`（1 始まり）の擬似リスト項目を始める。
+    pub fn enter_pseudo_list_item(&mut self, level: usize, ordered: bool) {
+        if self.pseudo_list_depth == 0 {
+            if self.list_stack.is_empty() {
+                self.begin_block();
+            }
+            self.pseudo_list_base = self.list_stack.len();
+        }
+        let base = self.pseudo_list_base;
+        self.list_stack.truncate(base + level);
+        while self.list_stack.len() < base + level {
+            self.list_stack.push(ListContext {
+                kind: ListKind::Unordered,
+            });
+        }
+        self.pseudo_list_depth = level;
+        // 同じ階層で番号の有無が変わったら別のリストとして数え直す
+        if let Some(ctx) = self.list_stack.last_mut() {
+            let same_kind = matches!(
+                (&ctx.kind, ordered),
+                (ListKind::Ordered { .. }, true) | (ListKind::Unordered, false)
+            );
+            if !same_kind {
+                ctx.kind = if ordered {
+                    ListKind::Ordered { counter: 1 }
+                } else {
+                    ListKind::Unordered
+                };
+            }
+        }
+        self.begin_list_item();
+    }
+
+    pub fn leave_pseudo_list_item(&mut self) {
+        self.ensure_newlines(1);
+    }
+
+    /// 開いている擬似リストを閉じる。
+    fn close_pseudo_list(&mut self) {
+        if self.pseudo_list_depth > 0 {
+            self.list_stack.truncate(self.pseudo_list_base);
+            self.pseudo_list_depth = 0;
+        }
+    }
+
     // ─── 要素 Enter ────────────────────────────────────────────────────────
 
     pub fn enter_element(&mut self, elem: &scraper::node::Element, preserve_ids: bool) {
@@ -199,6 +618,9 @@ impl MarkdownRenderer {
         }
         match tag {
             "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if matches!(tag, "h1" | "h2") && self.link_style == LinkStyle::ReferencePerSection {
+                    self.flush_section_definitions();
+                }
                 self.begin_block();
                 self.emit_pending_prefix();
                 let level = (tag.as_bytes()[1] - b'0') as usize;
@@ -211,10 +633,20 @@ impl MarkdownRenderer {
                 self.last_was_space = false;
             }
             "p" | "div" | "article" | "section" | "main" | "header" | "footer" | "nav"
-            | "aside" | "figure" | "figcaption" => {
+            | "aside" | "figure" => {
                 self.begin_block();
             }
+            // 図の説明は斜体の 1 行にする
+            "figcaption" => {
+                self.begin_block();
+                self.emit_pending_prefix();
+                self.output.push('*');
+                self.caption_start = Some(self.output.len());
+                self.newlines_emitted = 0;
+                self.at_line_start = false;
+            }
             "ul" => {
+                self.close_pseudo_list();
                 if self.list_stack.is_empty() {
                     self.begin_block();
                 }
@@ -223,6 +655,7 @@ impl MarkdownRenderer {
                 });
             }
             "ol" => {
+                self.close_pseudo_list();
                 if self.list_stack.is_empty() {
                     self.begin_block();
                 }
@@ -235,26 +668,8 @@ impl MarkdownRenderer {
                 });
             }
             "li" => {
-                self.ensure_newlines(1);
-                self.emit_pending_prefix();
-                let depth = self.list_stack.len().saturating_sub(1);
-                for _ in 0..depth {
-                    self.output.push_str("  ");
-                }
-                if let Some(ctx) = self.list_stack.last_mut() {
-                    match &mut ctx.kind {
-                        ListKind::Unordered => self.output.push_str("- "),
-                        ListKind::Ordered { counter } => {
-                            let n = *counter;
-                            *counter += 1;
-                            push_usize(&mut self.output, n);
-                            self.output.push_str(". ");
-                        }
-                    }
-                }
-                self.newlines_emitted = 0;
-                self.at_line_start = false;
-                self.last_was_space = false;
+                self.close_pseudo_list();
+                self.begin_list_item();
             }
             "blockquote" => {
                 self.begin_block();
@@ -294,55 +709,60 @@ impl MarkdownRenderer {
                 self.newlines_emitted = 0;
                 self.at_line_start = false;
             }
-            "strong" | "b" => {
-                self.flush_space();
-                self.output.push_str("**");
-                self.newlines_emitted = 0;
-                self.at_line_start = false;
-            }
-            "em" | "i" => {
-                self.flush_space();
-                self.output.push('*');
-                self.newlines_emitted = 0;
-                self.at_line_start = false;
-            }
+            "strong" | "b" => self.open_marker("**"),
+            "em" | "i" => self.open_marker("*"),
             "a" => {
                 self.link_depth += 1;
                 if self.link_depth == 1 {
-                    let href = elem.attr("href").unwrap_or("").to_string();
-                    let title = elem.attr("title").map(|t| t.to_string());
-                    self.flush_space();
-                    self.inline_capture = InlineCapture::Link {
-                        href,
-                        title,
-                        buf: String::new(),
-                    };
-                    // `anchor_before`（下記 §要素 Enter）が `"a"` を判定基準に
-                    // 使っている。ここでガード状態を立てる他のタグを追加する場合は
-                    // `anchor_before` も更新すること — さもないと、その要素自身の
-                    // `id` のアンカーが自分自身のガードに引っかかって消える
-                    // （RFC 006 Slice D、過去に "a" と "pre" 自体で二度発生）。
-                    self.capture_depth += 1;
+                    let href = elem.attr("href").map(|href| match self.links.get(href) {
+                        Some(rewritten) => rewritten.clone(),
+                        None => self.resolve_url(href).into_owned(),
+                    });
+                    self.link_action = self.link_action(href.as_deref());
+                    // テキストにするリンクはキャプチャせず、中身を通常のテキストとして書く。
+                    // 捨てるリンクの前の空白は、後続のテキストとの区切りに残す
+                    if self.link_action == LinkAction::Link {
+                        self.flush_space();
+                    }
+                    if self.link_action != LinkAction::Text {
+                        let title = elem.attr("title").map(|t| t.to_string());
+                        self.inline_capture = InlineCapture::Link {
+                            href: href.unwrap_or_default(),
+                            title,
+                            buf: String::new(),
+                            space: false,
+                        };
+                        // `anchor_before`（下記 §要素 Enter）が `"a"` を判定基準に
+                        // 使っている。ここでガード状態を立てる他のタグを追加する場合は
+                        // `anchor_before` も更新すること — さもないと、その要素自身の
+                        // `id` のアンカーが自分自身のガードに引っかかって消える
+                        // （RFC 006 Slice D、過去に "a" と "pre" 自体で二度発生）。
+                        self.capture_depth += 1;
+                    }
                 }
             }
             "img" => {
-                let src = elem.attr("src").unwrap_or("");
+                // src のない画像（レスポンシブ画像）は srcset の最大の候補を使う
+                let src = elem
+                    .attr("src")
+                    .filter(|s| !s.trim().is_empty())
+                    .or_else(|| elem.attr("srcset").and_then(url::largest_srcset_candidate))
+                    .unwrap_or("");
+                // ローカルの画像は複製し、基準 URL では解決しない
+                let local = self
+                    .asset_root
+                    .as_ref()
+                    .and_then(|root| root.local_image(src));
+                let src = match local {
+                    Some(asset) => {
+                        let href = asset.href();
+                        self.add_asset(asset);
+                        Cow::Owned(href)
+                    }
+                    None => self.resolve_url(src),
+                };
                 let alt = elem.attr("alt").unwrap_or("");
-                let title = elem.attr("title");
-                self.flush_space();
-                self.output.push_str("![");
-                self.output.push_str(alt);
-                self.output.push_str("](");
-                self.output.push_str(src);
-                if let Some(t) = title {
-                    self.output.push_str(" \"");
-                    self.output.push_str(t);
-                    self.output.push('"');
-                }
-                self.output.push(')');
-                self.newlines_emitted = 0;
-                self.at_line_start = false;
-                self.last_was_space = false;
+                self.push_image(alt, &src, elem.attr("title"), ImageSize::of(elem));
             }
             "hr" => {
                 self.begin_block();
@@ -371,14 +791,40 @@ impl MarkdownRenderer {
         match tag {
             "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.end_block(),
             "p" | "div" | "article" | "section" | "main" | "header" | "footer" | "nav"
-            | "aside" | "figure" | "figcaption" => self.end_block(),
+            | "aside" => self.end_block(),
+            "figure" => {
+                self.figure_caption = None;
+                self.end_block();
+            }
+            "figcaption" => {
+                // 先頭の空白は斜体にならないため取り除き、空なら記号ごと消す
+                if let Some(start) = self.caption_start.take() {
+                    let spaces =
+                        self.output[start..].len() - self.output[start..].trim_start().len();
+                    self.output.replace_range(start..start + spaces, "");
+                    if self.output.len() == start {
+                        self.output.pop();
+                        self.newlines_emitted =
+                            self.output.len() - self.output.trim_end_matches('\n').len();
+                    } else {
+                        self.output.push('*');
+                        self.newlines_emitted = 0;
+                    }
+                }
+                self.last_was_space = false;
+                self.end_block();
+            }
             "ul" | "ol" => {
+                self.close_pseudo_list();
                 self.list_stack.pop();
                 if self.list_stack.is_empty() {
                     self.end_block();
                 }
             }
-            "li" => self.ensure_newlines(1),
+            "li" => {
+                self.close_pseudo_list();
+                self.ensure_newlines(1);
+            }
             "blockquote" => {
                 self.blockquote_depth = self.blockquote_depth.saturating_sub(1);
                 self.end_block();
@@ -396,29 +842,18 @@ impl MarkdownRenderer {
                 self.output.push('`');
                 self.newlines_emitted = 0;
             }
-            "strong" | "b" => {
-                self.output.push_str("**");
-                self.newlines_emitted = 0;
-            }
-            "em" | "i" => {
-                self.output.push('*');
-                self.newlines_emitted = 0;
-            }
+            "strong" | "b" => self.close_marker("**"),
+            "em" | "i" => self.close_marker("*"),
             "a" => {
-                if self.link_depth == 1 {
+                if self.link_depth == 1 && self.link_action != LinkAction::Text {
                     self.capture_depth = self.capture_depth.saturating_sub(1);
                     let captured = std::mem::replace(&mut self.inline_capture, InlineCapture::None);
-                    if let InlineCapture::Link { href, title, buf } = captured {
-                        self.output.push('[');
-                        self.output.push_str(&buf);
-                        self.output.push_str("](");
-                        self.output.push_str(&href);
-                        if let Some(t) = &title {
-                            self.output.push_str(" \"");
-                            self.output.push_str(t);
-                            self.output.push('"');
-                        }
-                        self.output.push(')');
+                    if let InlineCapture::Link {
+                        href, title, buf, ..
+                    } = captured
+                        && self.link_action == LinkAction::Link
+                    {
+                        self.write_link(&buf, href, title);
                         self.newlines_emitted = 0;
                         self.at_line_start = false;
                         self.last_was_space = false;
@@ -430,6 +865,101 @@ impl MarkdownRenderer {
         }
     }
 
+    /// 捕捉したリンクのテキスト `buf` を `link_style` に従って書き出す。
+    fn write_link(&mut self, buf: &str, href: String, title: Option<String>) {
+        if self.link_policy.autolink
+            && let Some(url) = autolink_target(buf, &href)
+        {
+            self.output.push('<');
+            self.output.push_str(url);
+            self.output.push('>');
+            return;
+        }
+        if self.link_style == LinkStyle::Inline || href.is_empty() {
+            self.output.push('[');
+            self.output.push_str(buf);
+            self.output.push_str("](");
+            utils::push_link_destination(&mut self.output, &href);
+            if let Some(t) = &title {
+                utils::push_link_title(&mut self.output, t);
+            }
+            self.output.push(')');
+            return;
+        }
+        let number = match self.link_numbers.get(&href) {
+            Some(&n) => n,
+            None => {
+                self.link_definitions.push((href.clone(), title));
+                let n = self.link_definitions.len();
+                self.link_numbers.insert(href, n);
+                n
+            }
+        };
+        if self.link_style == LinkStyle::Footnote {
+            self.output.push_str(buf);
+            self.output.push_str("[^");
+            push_usize(&mut self.output, number);
+            self.output.push(']');
+        } else {
+            self.output.push('[');
+            self.output.push_str(buf);
+            self.output.push_str("][");
+            push_usize(&mut self.output, number);
+            self.output.push(']');
+        }
+    }
+
+    /// まだ書き出していないリンクの定義を 1 行ずつ返す（末尾に改行を含む）。
+    pub fn take_link_definitions(&mut self) -> String {
+        let mut out = String::new();
+        for (i, (href, title)) in self
+            .link_definitions
+            .iter()
+            .enumerate()
+            .skip(self.written_definitions)
+        {
+            out.push('[');
+            if self.link_style == LinkStyle::Footnote {
+                out.push('^');
+            }
+            push_usize(&mut out, i + 1);
+            out.push_str("]: ");
+            if self.link_style == LinkStyle::Footnote {
+                // 脚注の本文は段落なので、リンク定義の構文は使えない
+                utils::push_footnote_url(&mut out, href);
+                if let Some(t) = title {
+                    utils::push_footnote_title(&mut out, t);
+                }
+            } else {
+                utils::push_link_destination(&mut out, href);
+                if let Some(t) = title {
+                    utils::push_link_title(&mut out, t);
+                }
+            }
+            out.push('\n');
+        }
+        self.written_definitions = self.link_definitions.len();
+        out
+    }
+
+    /// 節の見出しの前に、その節のリンクの定義を書き出す。
+    /// 引用・リストの中の見出しでは書き出さない。
+    fn flush_section_definitions(&mut self) {
+        if self.written_definitions == self.link_definitions.len()
+            || self.blockquote_depth > 0
+            || !self.list_stack.is_empty()
+            || self.capture_depth > 0
+        {
+            return;
+        }
+        let definitions = self.take_link_definitions();
+        self.begin_block();
+        self.output.push_str(&definitions);
+        self.newlines_emitted = 1;
+        self.at_line_start = true;
+        self.last_was_space = false;
+    }
+
     fn flush_space(&mut self) {
         if self.last_was_space && !self.at_line_start {
             self.output.push(' ');
@@ -448,6 +978,41 @@ impl MarkdownRenderer {
     }
 }
 
+/// テキスト `buf`（Markdown のエスケープ済み）が `href` と同じなら、
+/// 自動リンク `<…>` の中身。`mailto:` はテキストがアドレスだけでもよい。
+fn autolink_target<'h>(buf: &str, href: &'h str) -> Option<&'h str> {
+    // 自動リンクはスキームを持つ絶対 URL だけ。空白や `<>` は含められない
+    if !href.contains(':') || href.contains(|c: char| c.is_whitespace() || c == '<' || c == '>') {
+        return None;
+    }
+    let mut text = String::with_capacity(buf.len());
+    let mut chars = buf.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+    if text == href {
+        return Some(href);
+    }
+    href.get(..7)
+        .filter(|s| s.eq_ignore_ascii_case("mailto:"))
+        .filter(|_| text == href[7..])
+        .map(|_| &href[7..])
+}
+
+/// 強調の開始記号を外側から順に返す。
+fn emphasis_markers(e: StyleEmphasis) -> Vec<&'static str> {
+    if e.code {
+        return vec!["`"];
+    }
+    [(e.strong, "**"), (e.em, "*"), (e.strike, "~~")]
+        .into_iter()
+        .filter_map(|(on, marker)| on.then_some(marker))
+        .collect()
+}
+
 /// usize を String へ直接書き込む（`format!` によるアロケーション回避）。
 #[inline]
 fn push_usize(s: &mut String, n: usize) {
@@ -0,0 +1,65 @@
+//! 変換結果の近似重複判定（SimHash）
+//!
+//! Markdown を語の 3-gram（シングル）に分け、各シングルの 64-bit ハッシュを
+//! ビットごとに多数決した値を指紋とする。内容の似た文書ほど指紋のハミング
+//! 距離が小さくなるため、`1 − 距離 / 64` を類似度として使う。
+
+use crate::assets::content_hash;
+
+/// シングル（連続する語の組）の語数。
+const SHINGLE_WORDS: usize = 3;
+
+/// Markdown の SimHash 指紋を求める。
+///
+/// 語は空白で区切り、小文字化して比較する。空の文書は `0` になる。
+///
+/// # Example
+///
+/// ```rust
+/// let a = mdka::markdown_simhash("The quick brown fox jumps over the lazy dog.");
+/// let b = mdka::markdown_simhash("The quick brown fox jumps over the lazy dog!");
+/// assert!(mdka::simhash_similarity(a, b) > 0.8);
+/// ```
+pub fn markdown_simhash(markdown: &str) -> u64 {
+    let words: Vec<String> = markdown.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return 0;
+    }
+
+    let mut votes = [0i64; 64];
+    let width = SHINGLE_WORDS.min(words.len());
+    for shingle in words.windows(width) {
+        let h = mix(content_hash(shingle.join(" ").as_bytes()));
+        for (bit, vote) in votes.iter_mut().enumerate() {
+            if h >> bit & 1 == 1 {
+                *vote += 1;
+            } else {
+                *vote -= 1;
+            }
+        }
+    }
+
+    votes
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v > 0)
+        .fold(0u64, |acc, (bit, _)| acc | 1 << bit)
+}
+
+/// 2 つの指紋の類似度（0.0〜1.0）。一致するビットの割合。
+///
+/// 無関係な文書の指紋は独立な乱数に近く、半分ほどのビットが偶然一致する
+/// ため、類似度は 0.5 前後（標準偏差およそ 0.06）になる。
+pub fn simhash_similarity(a: u64, b: u64) -> f64 {
+    1.0 - f64::from((a ^ b).count_ones()) / 64.0
+}
+
+/// FNV-1a は短い入力で上位ビットが偏るため、ビットごとの多数決の前に
+/// splitmix64 の最終段で攪拌する。
+fn mix(mut h: u64) -> u64 {
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
@@ -0,0 +1,373 @@
+//! サイト変換: 入力ファイル同士のリンクの書き換え
+//!
+//! バッチ変換の入力をひとつのサイトとみなし、入力の共通の親ディレクトリからの
+//! 相対パスを保ったまま書き出す。変換の前にすべての入力を読んで、出力先の
+//! パスと、`id` などのフラグメントが変換後にどのアンカーに当たるかを索引にする。
+//! 変換ではその索引から文書ごとのリンクの対応表を作り、レンダラーに渡す。
+//!
+//! 見出しのアンカーは GitHub などの Markdown ビューアと同じ規則（小文字化、
+//! 記号の除去、空白をハイフンに、重複には `-1`、`-2` …）で見出しのテキストから作る。
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use rayon::prelude::*;
+use scraper::{ElementRef, Html};
+
+use crate::options::ConversionOptions;
+use crate::url;
+
+/// 変換する文書の `href` → 書き換え後の `href`。
+pub(crate) type LinkMap = HashMap<String, String>;
+
+/// フラグメント → 変換後のフラグメント。
+type Anchors = HashMap<String, Option<String>>;
+
+/// 入力ファイルの集合の索引。
+pub(crate) struct Site {
+    /// 入力の共通の親ディレクトリ。`/` で始まるリンクもここを起点に解決する。
+    root: PathBuf,
+    /// 入力ごとの正規化したパス。読めない入力は `None`。
+    inputs: Vec<Option<PathBuf>>,
+    pages: HashMap<PathBuf, Page>,
+}
+
+struct Page {
+    /// `out_dir` からの出力パス。
+    output: PathBuf,
+    /// `id` / `<a name>` / 見出しのアンカー → 変換後のフラグメント。
+    /// 変換後に指す先がない（見出しより前の要素で `id` を出力しない）ときは `None`。
+    anchors: Anchors,
+}
+
+/// 入力の中に見つからないリンク先。
+pub(crate) struct Dangling {
+    pub href: String,
+    pub missing_fragment: bool,
+}
+
+impl Site {
+    /// すべての入力を読んで索引を作る。
+    pub fn index<P>(paths: &[P], opts: &ConversionOptions) -> Self
+    where
+        P: AsRef<Path> + Sync,
+    {
+        let scanned: Vec<Option<(PathBuf, Anchors)>> = paths
+            .par_iter()
+            .map(|path| {
+                let path = path.as_ref().canonicalize().ok()?;
+                let html = std::fs::read_to_string(&path).ok()?;
+                let anchors = page_anchors(&Html::parse_document(&html), opts);
+                Some((path, anchors))
+            })
+            .collect();
+
+        let root = common_dir(scanned.iter().flatten().map(|(p, _)| p.as_path()));
+        let inputs = scanned
+            .iter()
+            .map(|s| s.as_ref().map(|(p, _)| p.clone()))
+            .collect();
+        let pages = scanned
+            .into_iter()
+            .flatten()
+            .map(|(path, anchors)| {
+                let output = path
+                    .strip_prefix(&root)
+                    .unwrap_or(&path)
+                    .with_extension("md");
+                (path, Page { output, anchors })
+            })
+            .collect();
+        Self {
+            root,
+            inputs,
+            pages,
+        }
+    }
+
+    /// 入力の共通の親ディレクトリ（正規化済み）。
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `index` 番目の入力の、`out_dir` からの出力パス。読めなかった入力は `None`。
+    pub fn output_path(&self, index: usize) -> Option<&Path> {
+        let input = self.inputs.get(index)?.as_ref()?;
+        Some(&self.pages.get(input)?.output)
+    }
+
+    /// `index` 番目の入力（`document`）のリンクの対応表と、見つからないリンク先。
+    pub fn links(&self, index: usize, document: &Html) -> (LinkMap, Vec<Dangling>) {
+        let mut links = LinkMap::new();
+        let mut dangling: Vec<Dangling> = Vec::new();
+        let Some(source) = self.inputs.get(index).and_then(Option::as_ref) else {
+            return (links, dangling);
+        };
+        for elem in document
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+        {
+            if elem.value().name() != "a" {
+                continue;
+            }
+            let Some(href) = elem.value().attr("href") else {
+                continue;
+            };
+            if links.contains_key(href) || dangling.iter().any(|d| d.href == href) {
+                continue;
+            }
+            let (rewritten, missing) = self.resolve(source, href);
+            if let Some(rewritten) = rewritten {
+                links.insert(href.to_string(), rewritten);
+            }
+            if let Some(missing_fragment) = missing {
+                dangling.push(Dangling {
+                    href: href.to_string(),
+                    missing_fragment,
+                });
+            }
+        }
+        (links, dangling)
+    }
+
+    /// `source` の中の `href` の書き換え後の値と、リンク先が見つからない場合は
+    /// フラグメントだけが見つからないか否か。サイトの外へのリンクは `(None, None)`。
+    fn resolve(&self, source: &Path, href: &str) -> (Option<String>, Option<bool>) {
+        let href = href.trim();
+        let (rest, fragment) = match href.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment).filter(|f| !f.is_empty())),
+            None => (href, None),
+        };
+        let path = rest.split('?').next().unwrap_or("");
+        if is_external(rest) || (path.is_empty() && fragment.is_none()) {
+            return (None, None);
+        }
+
+        let decoded = url::percent_decode(path);
+        let target = if path.is_empty() {
+            source.to_path_buf()
+        } else if let Some(absolute) = decoded.strip_prefix('/') {
+            normalize(&self.root.join(absolute))
+        } else {
+            normalize(&source.parent().unwrap_or(Path::new("")).join(&decoded))
+        };
+        let Some((target, page)) = self.find_page(&target, path.ends_with('/')) else {
+            // 入力ではないが実在するファイル（PDF や画像など）は対象外
+            let missing = (!target.exists()).then_some(false);
+            return (None, missing);
+        };
+
+        let same_page = target == source;
+        let mut rewritten = if same_page {
+            String::new()
+        } else {
+            let from = &self.pages[source].output;
+            encode_path(&relative_path(from, &page.output))
+        };
+        let mut missing = None;
+        if let Some(fragment) = fragment {
+            match page.anchors.get(&url::percent_decode(fragment)) {
+                Some(Some(anchor)) => {
+                    rewritten.push('#');
+                    rewritten.push_str(anchor);
+                }
+                Some(None) => {}
+                // HTML では該当する要素のない `#top` はページの先頭を指す
+                None if fragment.eq_ignore_ascii_case("top") => {}
+                None => {
+                    missing = Some(true);
+                    rewritten.push('#');
+                    rewritten.push_str(fragment);
+                }
+            }
+        }
+        let rewritten = (!rewritten.is_empty()).then_some(rewritten);
+        (rewritten, missing)
+    }
+
+    /// `target` に当たる入力。ディレクトリなら `index.html` / `index.htm`。
+    fn find_page(&self, target: &Path, is_dir: bool) -> Option<(PathBuf, &Page)> {
+        if !is_dir && let Some(page) = self.pages.get(target) {
+            return Some((target.to_path_buf(), page));
+        }
+        ["index.html", "index.htm"].iter().find_map(|index| {
+            let path = target.join(index);
+            let page = self.pages.get(&path)?;
+            Some((path, page))
+        })
+    }
+}
+
+// ─── アンカー ─────────────────────────────────────────────────────────────
+
+/// 文書のフラグメント → 変換後のフラグメント。
+///
+/// - 見出しの `id` と、見出しから作るアンカー自身 → 見出しのアンカー
+/// - それ以外の要素の `id` → `preserve_ids` が有効ならそのまま（`<a id>` が出力される）。
+///   無効なら要素の中の最初の見出し、なければ直前の見出しのアンカー
+/// - `<a name>` → 上と同じく、要素の中か直前の見出しのアンカー
+fn page_anchors(document: &Html, opts: &ConversionOptions) -> Anchors {
+    let mut headings = HashMap::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        if !matches!(elem.name(), "h1" | "h2" | "h3" | "h4" | "h5" | "h6") {
+            continue;
+        }
+        let skipped = node.ancestors().any(|a| {
+            a.value()
+                .as_element()
+                .is_some_and(|e| opts.tag_rules.is_skip(e.name()))
+        });
+        if skipped {
+            continue;
+        }
+        let text: String = node
+            .descendants()
+            .filter_map(|d| d.value().as_text().map(|t| &**t))
+            .collect();
+        let base = slugify(&text);
+        let count = counts.entry(base.clone()).or_insert(0);
+        let slug = if *count == 0 {
+            base
+        } else {
+            format!("{base}-{count}")
+        };
+        *count += 1;
+        headings.insert(node.id(), slug);
+    }
+
+    let mut anchors = HashMap::new();
+    let mut previous: Option<&String> = None;
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        let heading = headings.get(&node.id());
+        if heading.is_some() {
+            previous = heading;
+        }
+        let id = elem.attr("id").filter(|id| !id.is_empty());
+        let name = elem
+            .attr("name")
+            .filter(|n| elem.name() == "a" && !n.is_empty());
+        for (fragment, is_id) in [(id, true), (name, false)] {
+            let Some(fragment) = fragment else {
+                continue;
+            };
+            if anchors.contains_key(fragment) {
+                continue;
+            }
+            let anchor = match heading {
+                Some(slug) => Some(slug.clone()),
+                None if is_id && opts.preserve_ids => Some(fragment.to_string()),
+                None => node
+                    .descendants()
+                    .find_map(|d| headings.get(&d.id()))
+                    .or(previous)
+                    .cloned(),
+            };
+            anchors.insert(fragment.to_string(), anchor);
+        }
+    }
+    for slug in headings.into_values() {
+        anchors.entry(slug.clone()).or_insert(Some(slug));
+    }
+    anchors
+}
+
+/// 見出しのテキストからアンカーを作る。
+fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        for c in word.chars() {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                slug.extend(c.to_lowercase());
+            }
+        }
+    }
+    slug
+}
+
+// ─── パス ─────────────────────────────────────────────────────────────────
+
+/// スキームを持つか、`//` で始まる（サイトの外を指しうる）参照か。
+fn is_external(reference: &str) -> bool {
+    if reference.starts_with("//") {
+        return true;
+    }
+    reference.split_once(':').is_some_and(|(scheme, _)| {
+        let mut bytes = scheme.bytes();
+        bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+            && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+    })
+}
+
+/// すべてのパスの親ディレクトリに共通する最も深いディレクトリ。
+pub(crate) fn common_dir<'a>(paths: impl Iterator<Item = &'a Path>) -> PathBuf {
+    let mut common: Option<PathBuf> = None;
+    for path in paths {
+        let dir = path.parent().unwrap_or(path);
+        common = Some(match common {
+            None => dir.to_path_buf(),
+            Some(c) => c
+                .components()
+                .zip(dir.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    common.unwrap_or_default()
+}
+
+/// `.` と `..` を字句的に取り除く。
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// 出力ファイル `from` から出力ファイル `to` への `/` 区切りの相対パス。
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from
+        .parent()
+        .map(|d| d.components().collect())
+        .unwrap_or_default();
+    let to: Vec<_> = to.components().collect();
+    let shared = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - shared];
+    parts.extend(
+        to[shared..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
+
+/// パスに使えない文字（空白など）をパーセントエンコードする。
+fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/!$&'()*+,;=:@".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
@@ -0,0 +1,745 @@
+//! 構造化データ（JSON-LD・microdata・microformats2）の抽出と描画
+//!
+//! `<script>` は変換で丸ごと読み飛ばすため、`application/ld+json` に書かれた
+//! Article や Recipe の情報は Markdown に残らない。ここでは同じ [`Html`] から
+//! 3 種類の構造化データを読み取り、[`JsonValue`] として返す。
+//!
+//! Recipe と FAQPage は、構造化データの方が表示上の HTML より情報が多い場合に
+//! 限り、本文の末尾へ Markdown として書き足す（[`append_rich_content`]）。
+
+use ego_tree::NodeRef;
+use scraper::{ElementRef, Html, Node};
+
+use crate::json::JsonValue;
+use crate::options::{ConversionOptions, LinkStyle};
+use crate::{traversal, url};
+
+/// 入れ子の項目をたどる深さの上限。これより深い項目は再帰せず、
+/// 入れ子でないプロパティと同じ値（テキストや URL）として扱う。
+/// 深く入れ子にした HTML でスタックを使い果たさないため。
+const MAX_ITEM_DEPTH: usize = 32;
+
+/// 文書に埋め込まれた構造化データ。各項目は文書順。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructuredData {
+    /// `<script type="application/ld+json">` の各項目。
+    /// 最上位の配列と `@graph` は 1 項目ずつに展開する。
+    pub json_ld: Vec<JsonValue>,
+    /// `itemscope` の項目。`@type` に型（`schema.org` の接頭辞を除いたもの）、
+    /// 各 `itemprop` をキーとするオブジェクト。同名のプロパティは配列にまとめる。
+    pub microdata: Vec<JsonValue>,
+    /// `h-*` の項目。microformats2 の JSON 形式
+    /// （`{"type": [...], "properties": {...}}`）。
+    pub microformats: Vec<JsonValue>,
+}
+
+impl StructuredData {
+    /// 1 つも見つからなければ `true`。
+    pub fn is_empty(&self) -> bool {
+        self.json_ld.is_empty() && self.microdata.is_empty() && self.microformats.is_empty()
+    }
+
+    /// JSON-LD と microdata のうち、`@type` が `type_name` の項目（文書順）。
+    pub fn items_of_type<'a>(&'a self, type_name: &'a str) -> impl Iterator<Item = &'a JsonValue> {
+        self.json_ld
+            .iter()
+            .chain(&self.microdata)
+            .filter(move |item| has_type(item, type_name))
+    }
+}
+
+/// 文書から構造化データを読み取る。
+///
+/// 不正な JSON-LD は読み飛ばす。
+///
+/// # Example
+///
+/// ```rust
+/// use mdka::scraper::Html;
+///
+/// let document = Html::parse_document(
+///     r#"<script type="application/ld+json">
+///     {"@context": "https://schema.org", "@type": "Article", "headline": "Hello"}
+///     </script>"#,
+/// );
+/// let data = mdka::extract_structured_data(&document);
+/// assert_eq!(data.json_ld[0].get("headline").and_then(|v| v.as_str()), Some("Hello"));
+/// ```
+pub fn extract_structured_data(document: &Html) -> StructuredData {
+    let mut data = StructuredData::default();
+    for node in document.tree.root().descendants() {
+        let Some(elem) = node.value().as_element() else {
+            continue;
+        };
+        if elem.name() == "script" {
+            if is_json_ld(elem) {
+                push_json_ld(&mut data.json_ld, node);
+            }
+            continue;
+        }
+        // 他の項目のプロパティ値になっている項目は、その中に含まれる
+        if elem.attr("itemscope").is_some()
+            && (elem.attr("itemprop").is_none()
+                || !has_ancestor(node, |e| e.attr("itemscope").is_some()))
+        {
+            data.microdata.push(microdata_item(node, 0));
+        }
+        if mf_root_types(elem).next().is_some()
+            && !has_ancestor(node, |e| mf_root_types(e).next().is_some())
+        {
+            data.microformats.push(mf_item(node, 0));
+        }
+    }
+    data
+}
+
+/// JSON-LD の `<script>` だけを探す。本文の末尾への書き足しに使う。
+fn extract_json_ld(document: &Html) -> Vec<JsonValue> {
+    let mut items = Vec::new();
+    for node in document.tree.root().descendants() {
+        if node
+            .value()
+            .as_element()
+            .is_some_and(|e| e.name() == "script" && is_json_ld(e))
+        {
+            push_json_ld(&mut items, node);
+        }
+    }
+    items
+}
+
+/// 構造化データから記事の情報を読み取る（メタデータの補完用）。
+///
+/// JSON-LD・microdata では、サイトやパンくずなどを除いた最初の項目、
+/// microformats では最初の `h-entry` を使う。
+pub(crate) struct Summary {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub published: Option<String>,
+    pub modified: Option<String>,
+    pub image: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+pub(crate) fn summarize(data: &StructuredData) -> Option<Summary> {
+    const NOT_CONTENT: &[&str] = &[
+        "WebSite",
+        "Organization",
+        "BreadcrumbList",
+        "Person",
+        "ImageObject",
+        "SiteNavigationElement",
+        "WPHeader",
+        "WPFooter",
+        "WPSideBar",
+    ];
+    let item = data
+        .json_ld
+        .iter()
+        .chain(&data.microdata)
+        .find(|item| !NOT_CONTENT.iter().any(|t| has_type(item, t)));
+    if let Some(item) = item {
+        let get = |key: &str| item.get(key).and_then(text_of);
+        return Some(Summary {
+            title: get("headline").or_else(|| get("name")),
+            description: get("description"),
+            author: get("author").or_else(|| get("creator")),
+            published: get("datePublished").or_else(|| get("dateCreated")),
+            modified: get("dateModified"),
+            image: get("image").or_else(|| get("thumbnailUrl")),
+            keywords: item.get("keywords").map(keywords).unwrap_or_default(),
+        });
+    }
+
+    let entry = data.microformats.iter().find(|item| {
+        item.get("type")
+            .is_some_and(|t| t.as_slice().iter().any(|t| t.as_str() == Some("h-entry")))
+    })?;
+    let props = entry.get("properties")?;
+    let get = |key: &str| props.get(key).and_then(text_of);
+    Some(Summary {
+        title: get("name"),
+        description: get("summary"),
+        author: get("author"),
+        published: get("published"),
+        modified: get("updated"),
+        image: get("photo").or_else(|| get("featured")),
+        keywords: props.get("category").map(keywords).unwrap_or_default(),
+    })
+}
+
+/// 構造化データの Recipe・FAQPage が表示上の本文より詳しければ、
+/// Markdown として本文の末尾に書き足す。
+///
+/// 材料・手順（FAQ は質問）の半分以上がすでに本文にあれば、
+/// 表示上の HTML で十分とみなして何もしない。
+pub(crate) fn append_rich_content(
+    document: &Html,
+    markdown: &mut String,
+    opts: &ConversionOptions,
+) {
+    let items = extract_json_ld(document);
+    if items.is_empty() {
+        return;
+    }
+    // 書き足す文字列自体の変換では再び書き足さない
+    let mut inner = opts.clone();
+    inner.render_structured_data = false;
+    inner.include_selectors.clear();
+    inner.extract_main_content = false;
+    // 番号が本文の参照リンクと重ならないよう、補う部分はインラインで書く
+    inner.link_style = LinkStyle::Inline;
+    inner.base_url = url::document_base(document, opts).map(|b| b.to_string());
+
+    let haystack = normalize(&markdown.replace('\\', ""));
+    // HTML として組み立てて変換器に通す。名前・材料・手順・質問はテキストとして
+    // エスケープし、HTML を含みうる説明と回答は断片ごとに閉じた HTML にする
+    let mut html = String::new();
+    for item in &items {
+        let section = if has_type(item, "Recipe") {
+            recipe_section(item, &haystack)
+        } else if has_type(item, "FAQPage") {
+            faq_section(item, &haystack)
+        } else {
+            None
+        };
+        html.extend(section);
+    }
+    if html.is_empty() {
+        return;
+    }
+    let rendered = traversal::traverse(&Html::parse_fragment(&html), &inner);
+    if !markdown.is_empty() && !rendered.is_empty() {
+        markdown.push('\n');
+    }
+    markdown.push_str(&rendered);
+}
+
+// ─── JSON-LD ──────────────────────────────────────────────────────────────
+
+fn is_json_ld(elem: &scraper::node::Element) -> bool {
+    elem.attr("type").is_some_and(|t| {
+        t.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .eq_ignore_ascii_case("application/ld+json")
+    })
+}
+
+fn push_json_ld(out: &mut Vec<JsonValue>, script: NodeRef<Node>) {
+    let text: String = script
+        .children()
+        .filter_map(|c| c.value().as_text().map(|t| &**t))
+        .collect();
+    let Some(value) = JsonValue::parse_lenient(strip_wrappers(&text)) else {
+        return;
+    };
+    for item in value.as_slice() {
+        match item.get("@graph") {
+            Some(graph) => out.extend(graph.as_slice().iter().cloned()),
+            None => out.push(item.clone()),
+        }
+    }
+}
+
+/// 古い CMS が付けるコメントや CDATA の囲みを外す。
+fn strip_wrappers(text: &str) -> &str {
+    let mut s = text.trim();
+    for (open, close) in [
+        ("<!--", "-->"),
+        ("//<![CDATA[", "//]]>"),
+        ("<![CDATA[", "]]>"),
+    ] {
+        if let Some(inner) = s.strip_prefix(open).and_then(|r| r.strip_suffix(close)) {
+            s = inner.trim();
+        }
+    }
+    s
+}
+
+// ─── microdata ────────────────────────────────────────────────────────────
+
+fn microdata_item(scope: NodeRef<Node>, depth: usize) -> JsonValue {
+    let elem = scope.value().as_element().expect("itemscope element");
+    let mut entries: Vec<(String, JsonValue)> = Vec::new();
+    let types: Vec<JsonValue> = elem
+        .attr("itemtype")
+        .unwrap_or_default()
+        .split_ascii_whitespace()
+        .map(|t| JsonValue::String(schema_name(t).to_string()))
+        .collect();
+    match types.len() {
+        0 => {}
+        1 => entries.push((
+            "@type".into(),
+            types.into_iter().next().unwrap_or(JsonValue::Null),
+        )),
+        _ => entries.push(("@type".into(), JsonValue::Array(types))),
+    }
+    if let Some(id) = elem.attr("itemid") {
+        entries.push(("@id".into(), JsonValue::String(id.to_string())));
+    }
+
+    // 入れ子の itemscope の中は、その項目のプロパティなので辿らない
+    let mut stack: Vec<NodeRef<Node>> = scope.children().rev().collect();
+    while let Some(node) = stack.pop() {
+        let Some(child) = node.value().as_element() else {
+            continue;
+        };
+        let nested = child.attr("itemscope").is_some();
+        if let Some(props) = child.attr("itemprop") {
+            let value = if nested && depth < MAX_ITEM_DEPTH {
+                microdata_item(node, depth + 1)
+            } else {
+                JsonValue::String(microdata_value(node))
+            };
+            for name in props.split_ascii_whitespace() {
+                insert_repeated(&mut entries, name, value.clone());
+            }
+        }
+        if !nested {
+            stack.extend(node.children().rev());
+        }
+    }
+    JsonValue::Object(entries)
+}
+
+/// HTML Living Standard の「プロパティ値」の規則に従う。
+fn microdata_value(node: NodeRef<Node>) -> String {
+    let Some(elem) = node.value().as_element() else {
+        return String::new();
+    };
+    let attr = match elem.name() {
+        "meta" => elem.attr("content"),
+        "audio" | "embed" | "iframe" | "img" | "source" | "track" | "video" => elem.attr("src"),
+        "a" | "area" | "link" => elem.attr("href"),
+        "object" => elem.attr("data"),
+        "data" | "meter" => elem.attr("value"),
+        "time" => elem.attr("datetime"),
+        _ => None,
+    };
+    match attr {
+        Some(value) => value.trim().to_string(),
+        None => collapsed_text(node),
+    }
+}
+
+/// 同じ名前のプロパティが繰り返されたら配列にまとめる。
+fn insert_repeated(entries: &mut Vec<(String, JsonValue)>, name: &str, value: JsonValue) {
+    match entries.iter_mut().find(|(k, _)| k == name) {
+        Some((_, JsonValue::Array(items))) => items.push(value),
+        Some((_, existing)) => {
+            let first = std::mem::replace(existing, JsonValue::Null);
+            *existing = JsonValue::Array(vec![first, value]);
+        }
+        None => entries.push((name.to_string(), value)),
+    }
+}
+
+/// `https://schema.org/Recipe` → `Recipe`。schema.org 以外の語彙は URL のまま。
+fn schema_name(itemtype: &str) -> &str {
+    ["https://schema.org/", "http://schema.org/"]
+        .iter()
+        .find_map(|prefix| itemtype.strip_prefix(prefix))
+        .unwrap_or(itemtype)
+}
+
+// ─── microformats2 ────────────────────────────────────────────────────────
+
+fn mf_root_types(elem: &scraper::node::Element) -> impl Iterator<Item = &str> {
+    elem.classes().filter(|c| is_mf_name(c, "h-"))
+}
+
+/// `h-entry` や `p-name` のような、接頭辞 + 小文字英字とハイフンのクラス名か。
+fn is_mf_name(class: &str, prefix: &str) -> bool {
+    class.strip_prefix(prefix).is_some_and(|rest| {
+        !rest.is_empty()
+            && rest
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    })
+}
+
+fn mf_item(root: NodeRef<Node>, depth: usize) -> JsonValue {
+    let elem = root.value().as_element().expect("h-* element");
+    let types = mf_root_types(elem)
+        .map(|t| JsonValue::String(t.to_string()))
+        .collect();
+    let mut properties: Vec<(String, JsonValue)> = Vec::new();
+    let mut children = Vec::new();
+
+    let mut stack: Vec<NodeRef<Node>> = root.children().rev().collect();
+    while let Some(node) = stack.pop() {
+        let Some(child) = node.value().as_element() else {
+            continue;
+        };
+        let has_root = mf_root_types(child).next().is_some();
+        let nested = has_root && depth < MAX_ITEM_DEPTH;
+        let mut is_property = false;
+        for class in child.classes() {
+            let (name, value) = if let Some(name) = mf_property(class, "p-") {
+                let value = if nested {
+                    nested_mf(node, depth + 1)
+                } else {
+                    JsonValue::String(mf_text_value(node))
+                };
+                (name, value)
+            } else if let Some(name) = mf_property(class, "u-") {
+                let value = if nested {
+                    nested_mf(node, depth + 1)
+                } else {
+                    JsonValue::String(mf_url_value(node))
+                };
+                (name, value)
+            } else if let Some(name) = mf_property(class, "dt-") {
+                (name, JsonValue::String(mf_date_value(node)))
+            } else if let Some(name) = mf_property(class, "e-") {
+                let html = ElementRef::wrap(node)
+                    .map(|e| e.inner_html())
+                    .unwrap_or_default();
+                let value = JsonValue::Object(vec![
+                    ("html".into(), JsonValue::String(html.trim().to_string())),
+                    ("value".into(), JsonValue::String(collapsed_text(node))),
+                ]);
+                (name, value)
+            } else {
+                continue;
+            };
+            is_property = true;
+            push_mf_property(&mut properties, name, value);
+        }
+        if has_root {
+            if nested && !is_property {
+                children.push(mf_item(node, depth + 1));
+            }
+        } else {
+            stack.extend(node.children().rev());
+        }
+    }
+
+    // 暗黙の name: 明示がなければ要素全体のテキスト
+    if !properties.iter().any(|(k, _)| k == "name") {
+        let text = collapsed_text(root);
+        if !text.is_empty() {
+            properties.insert(
+                0,
+                (
+                    "name".into(),
+                    JsonValue::Array(vec![JsonValue::String(text)]),
+                ),
+            );
+        }
+    }
+
+    let mut entries = vec![
+        ("type".to_string(), JsonValue::Array(types)),
+        ("properties".to_string(), JsonValue::Object(properties)),
+    ];
+    if !children.is_empty() {
+        entries.push(("children".into(), JsonValue::Array(children)));
+    }
+    JsonValue::Object(entries)
+}
+
+fn mf_property<'a>(class: &'a str, prefix: &str) -> Option<&'a str> {
+    is_mf_name(class, prefix).then(|| &class[prefix.len()..])
+}
+
+/// プロパティ値になっている入れ子の項目。`value` に要素のテキストを持たせる。
+fn nested_mf(node: NodeRef<Node>, depth: usize) -> JsonValue {
+    let mut item = mf_item(node, depth);
+    if let JsonValue::Object(entries) = &mut item {
+        entries.push(("value".into(), JsonValue::String(collapsed_text(node))));
+    }
+    item
+}
+
+/// microformats2 のプロパティ値はすべて配列。
+fn push_mf_property(properties: &mut Vec<(String, JsonValue)>, name: &str, value: JsonValue) {
+    match properties.iter_mut().find(|(k, _)| k == name) {
+        Some((_, JsonValue::Array(items))) => items.push(value),
+        _ => properties.push((name.to_string(), JsonValue::Array(vec![value]))),
+    }
+}
+
+fn mf_text_value(node: NodeRef<Node>) -> String {
+    let elem = node.value().as_element();
+    let attr = elem.and_then(|e| match e.name() {
+        "abbr" | "link" => e.attr("title"),
+        "data" | "input" => e.attr("value"),
+        "img" | "area" => e.attr("alt"),
+        _ => None,
+    });
+    attr.map(|v| v.trim().to_string())
+        .unwrap_or_else(|| collapsed_text(node))
+}
+
+fn mf_url_value(node: NodeRef<Node>) -> String {
+    let elem = node.value().as_element();
+    let attr = elem.and_then(|e| match e.name() {
+        "a" | "area" | "link" => e.attr("href"),
+        "img" | "audio" | "video" | "source" | "iframe" => e.attr("src"),
+        "object" => e.attr("data"),
+        "abbr" => e.attr("title"),
+        "data" | "input" => e.attr("value"),
+        _ => None,
+    });
+    attr.map(|v| v.trim().to_string())
+        .unwrap_or_else(|| collapsed_text(node))
+}
+
+fn mf_date_value(node: NodeRef<Node>) -> String {
+    let elem = node.value().as_element();
+    let attr = elem.and_then(|e| match e.name() {
+        "time" | "ins" | "del" => e.attr("datetime"),
+        "abbr" => e.attr("title"),
+        "data" | "input" => e.attr("value"),
+        _ => None,
+    });
+    attr.map(|v| v.trim().to_string())
+        .unwrap_or_else(|| collapsed_text(node))
+}
+
+// ─── Recipe / FAQPage の描画 ──────────────────────────────────────────────
+
+fn recipe_section(item: &JsonValue, haystack: &str) -> Option<String> {
+    let ingredients: Vec<String> = item
+        .get("recipeIngredient")
+        .or_else(|| item.get("ingredients"))
+        .map(|v| v.as_slice().iter().filter_map(text_of).collect())
+        .unwrap_or_default();
+    let steps: Vec<String> = item
+        .get("recipeInstructions")
+        .map(instruction_steps)
+        .unwrap_or_default();
+    let needles = if ingredients.is_empty() {
+        &steps
+    } else {
+        &ingredients
+    };
+    if needles.is_empty() || mostly_present(needles, haystack) {
+        return None;
+    }
+
+    // 見出しは文書の言語にかかわらず英語で固定
+    let mut html = String::new();
+    if let Some(name) = item.get("name").and_then(text_of) {
+        push_element(&mut html, "h2", &name);
+    }
+    if let Some(description) = item.get("description").and_then(text_of) {
+        push_markup(&mut html, &description);
+    }
+    if !ingredients.is_empty() {
+        html.push_str("<h3>Ingredients</h3><ul>");
+        for ingredient in &ingredients {
+            push_element(&mut html, "li", ingredient);
+        }
+        html.push_str("</ul>");
+    }
+    if !steps.is_empty() {
+        html.push_str("<h3>Instructions</h3><ol>");
+        for step in &steps {
+            push_element(&mut html, "li", step);
+        }
+        html.push_str("</ol>");
+    }
+    Some(html)
+}
+
+/// `recipeInstructions` は文字列・文字列の配列・HowToStep・HowToSection のいずれか。
+fn instruction_steps(value: &JsonValue) -> Vec<String> {
+    let mut steps = Vec::new();
+    for item in value.as_slice() {
+        match item {
+            JsonValue::String(text) => steps.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(String::from),
+            ),
+            JsonValue::Object(_) => {
+                if let Some(section) = item.get("itemListElement") {
+                    steps.extend(instruction_steps(section));
+                } else if let Some(text) = item
+                    .get("text")
+                    .or_else(|| item.get("name"))
+                    .and_then(text_of)
+                {
+                    steps.push(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    steps
+}
+
+fn faq_section(item: &JsonValue, haystack: &str) -> Option<String> {
+    let questions: Vec<(String, Option<String>)> = item
+        .get("mainEntity")
+        .map(|v| v.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|q| {
+            let name = q.get("name").and_then(text_of)?;
+            let answer = q
+                .get("acceptedAnswer")
+                .or_else(|| q.get("suggestedAnswer"))
+                .and_then(|a| a.as_slice().first())
+                .and_then(|a| a.get("text").and_then(text_of));
+            Some((name, answer))
+        })
+        .collect();
+    let names: Vec<String> = questions.iter().map(|(q, _)| q.clone()).collect();
+    if names.is_empty() || mostly_present(&names, haystack) {
+        return None;
+    }
+
+    let mut html = String::new();
+    let level = match item.get("name").and_then(text_of) {
+        Some(title) => {
+            push_element(&mut html, "h2", &title);
+            "h3"
+        }
+        None => "h2",
+    };
+    for (question, answer) in &questions {
+        push_element(&mut html, level, question);
+        if let Some(answer) = answer {
+            push_markup(&mut html, answer);
+        }
+    }
+    Some(html)
+}
+
+/// テキストを `<tag>` で囲んで書く。`&`・`<`・`>` はエスケープするが、
+/// JSON-LD によくある `&amp;` や `&#8217;` のような文字参照はそのまま残す。
+fn push_element(html: &mut String, tag: &str, text: &str) {
+    html.push('<');
+    html.push_str(tag);
+    html.push('>');
+    for (i, c) in text.char_indices() {
+        match c {
+            '&' if !is_char_reference(&text[i..]) => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            c => html.push(c),
+        }
+    }
+    html.push_str("</");
+    html.push_str(tag);
+    html.push('>');
+}
+
+/// `&name;` / `&#123;` / `&#x7b;` で始まるか。
+fn is_char_reference(s: &str) -> bool {
+    let Some((reference, _)) = s[1..].split_once(';') else {
+        return false;
+    };
+    let valid = match reference.strip_prefix('#') {
+        Some(num) => match num.strip_prefix(['x', 'X']) {
+            Some(hex) => !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+            None => !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit()),
+        },
+        None => !reference.is_empty() && reference.bytes().all(|b| b.is_ascii_alphanumeric()),
+    };
+    valid && reference.len() <= 32
+}
+
+/// HTML を含みうる値（説明・回答）を `<div>` で囲んで書く。断片として
+/// パースし直して書き出すため、閉じていないタグや余分な終了タグが
+/// 周りの見出しやリストを壊すことはない。
+fn push_markup(html: &mut String, value: &str) {
+    let fragment = Html::parse_fragment(value);
+    html.push_str("<div>");
+    html.push_str(&fragment.root_element().inner_html());
+    html.push_str("</div>");
+}
+
+/// `needles` の半分以上が本文に現れるか（空白と大文字小文字を無視）。
+fn mostly_present(needles: &[String], haystack: &str) -> bool {
+    let found = needles
+        .iter()
+        .filter(|n| {
+            let fragment = Html::parse_fragment(n);
+            let plain = normalize(&collapsed_text(fragment.tree.root()));
+            !plain.is_empty() && haystack.contains(&plain)
+        })
+        .count();
+    found * 2 >= needles.len()
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+/// `@type` が `type_name` か（配列・schema.org の URL 表記も許す）。
+fn has_type(item: &JsonValue, type_name: &str) -> bool {
+    item.get("@type").is_some_and(|t| {
+        t.as_slice()
+            .iter()
+            .any(|t| t.as_str().is_some_and(|s| schema_name(s) == type_name))
+    })
+}
+
+/// 値を 1 つの文字列として読む。オブジェクトは `name` → `url` → `@id`、
+/// 配列は最初に読めた要素を使う。
+fn text_of(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => {
+            let collapsed = s.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!collapsed.is_empty()).then_some(collapsed)
+        }
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Array(items) => items.iter().find_map(text_of),
+        JsonValue::Object(_) => ["name", "url", "@id", "value"]
+            .iter()
+            .find_map(|k| value.get(k).and_then(text_of))
+            .or_else(|| {
+                // microformats2 の入れ子項目
+                value
+                    .get("properties")
+                    .and_then(|p| p.get("name"))
+                    .and_then(text_of)
+            }),
+        JsonValue::Null | JsonValue::Bool(_) => None,
+    }
+}
+
+/// `keywords` はカンマ区切りの文字列か、文字列の配列。
+fn keywords(value: &JsonValue) -> Vec<String> {
+    value
+        .as_slice()
+        .iter()
+        .filter_map(|v| v.as_str())
+        .flat_map(|s| s.split(','))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn has_ancestor(node: NodeRef<Node>, pred: impl Fn(&scraper::node::Element) -> bool) -> bool {
+    node.ancestors()
+        .any(|a| a.value().as_element().is_some_and(&pred))
+}
+
+fn collapsed_text(node: NodeRef<Node>) -> String {
+    let mut out = String::new();
+    for text in node.descendants().filter_map(|d| d.value().as_text()) {
+        for word in text.split_whitespace() {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(word);
+        }
+    }
+    out
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,95 @@
+//! インライン `<svg>` の変換
+//!
+//! `svg` の子孫（`path` や `text` など）は Markdown として意味を持たないため
+//! 辿らず、[`SvgPolicy`] に従って要素全体を 1 つの単位として出力する。
+
+use scraper::ElementRef;
+
+use crate::assets::Asset;
+use crate::options::SvgPolicy;
+use crate::renderer::{ImageSize, MarkdownRenderer};
+use crate::utils;
+
+const SVG_NS: &str = "http://www.w3.org/2000/svg";
+
+/// `svg` 要素をポリシーに従って出力する。
+pub(crate) fn render_svg(renderer: &mut MarkdownRenderer, svg: ElementRef, policy: SvgPolicy) {
+    let label = accessible_name(svg);
+    let label = label.as_deref().unwrap_or("");
+    match policy {
+        SvgPolicy::Skip => {}
+        SvgPolicy::AltText => {
+            if !label.is_empty() {
+                renderer.process_text(label);
+            }
+        }
+        SvgPolicy::Raw => {
+            // 行内 HTML として出すため、空行で HTML ブロックが途切れないよう改行を潰す
+            let markup = svg.html().replace(['\r', '\n'], " ");
+            if !renderer.push_inline_html(&markup) && !label.is_empty() {
+                // リンクテキスト内には生の HTML を置けないので名前で代替する
+                renderer.process_text(label);
+            }
+        }
+        SvgPolicy::DataUri => {
+            renderer.push_image(
+                label,
+                &data_uri(&standalone_markup(svg)),
+                None,
+                ImageSize::default(),
+            );
+        }
+        SvgPolicy::File => {
+            let markup = standalone_markup(svg);
+            if renderer.collects_assets() {
+                let asset = Asset::new(markup.into_bytes(), "svg");
+                let href = asset.href();
+                renderer.add_asset(asset);
+                renderer.push_image(label, &href, None, ImageSize::default());
+            } else {
+                renderer.push_image(label, &data_uri(&markup), None, ImageSize::default());
+            }
+        }
+    }
+}
+
+/// アクセシブルな名前: `aria-label`、なければ子要素 `<title>` のテキスト。
+fn accessible_name(svg: ElementRef) -> Option<String> {
+    if let Some(label) = svg.value().attr("aria-label") {
+        let label = collapse_whitespace(label);
+        if !label.is_empty() {
+            return Some(label);
+        }
+    }
+    let title = svg
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|child| child.value().name() == "title")?;
+    let text = collapse_whitespace(&title.text().collect::<String>());
+    (!text.is_empty()).then_some(text)
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 単体の画像として読めるマークアップ。HTML 内の `<svg>` は `xmlns` を
+/// 省略できるが、画像として読み込む SVG には必須なので補う。
+fn standalone_markup(svg: ElementRef) -> String {
+    let markup = svg.html();
+    if svg.value().attr("xmlns").is_some() {
+        return markup;
+    }
+    let mut out = String::with_capacity(markup.len() + SVG_NS.len() + 10);
+    out.push_str("<svg xmlns=\"");
+    out.push_str(SVG_NS);
+    out.push('"');
+    out.push_str(markup.strip_prefix("<svg").unwrap_or(&markup));
+    out
+}
+
+fn data_uri(markup: &str) -> String {
+    let mut uri = String::from("data:image/svg+xml;base64,");
+    utils::write_base64(markup.as_bytes(), &mut uri);
+    uri
+}
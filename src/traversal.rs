@@ -9,33 +9,219 @@
 #[cfg(test)]
 mod tests;
 
-use crate::options::ConversionOptions;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::assets::{Asset, AssetRoot};
+use crate::figure::CaptionPart;
+use crate::options::{ConversionOptions, DEFAULT_TRACKING_PARAMS, DataUriPolicy, EmojiPolicy};
+use crate::profile::{self, ProfileAction};
 use crate::renderer::MarkdownRenderer;
-use crate::utils;
-use scraper::Html;
+use crate::utils::StyleEmphasis;
+use crate::{
+    data_uri, emoji, extract, figure, front_matter, hydration, structured, svg, url, utils,
+};
+use scraper::{ElementRef, Html};
 
 /// トラバーサルイベント：Enter（開きタグ相当）と Leave（閉じタグ相当）
 enum Event<'a> {
     Enter(ego_tree::NodeRef<'a, scraper::Node>),
     Leave(ego_tree::NodeRef<'a, scraper::Node>),
+    /// Enter で開いたスタイル由来の強調を閉じる（要素の Leave の直後）。
+    CloseEmphasis(StyleEmphasis),
+    /// 図の説明の中で平らにしたブロックの区切り（空白 1 つ）。
+    Space,
+}
+
+/// トラバースの結果。Markdown 本文と、本文から参照されるアセット。
+pub(crate) struct Rendered {
+    pub markdown: String,
+    pub assets: Vec<Asset>,
+    /// 本文の前に付ける front matter。トラバース自体は生成せず、呼び出し側が設定する。
+    pub front_matter: String,
+}
+
+impl Rendered {
+    /// front matter と本文をつないだ最終的な出力。
+    pub fn output(&self) -> String {
+        front_matter::prepend(&self.front_matter, &self.markdown)
+    }
 }
 
 /// HTML ドキュメントをトラバースして Markdown 文字列を生成する。
 ///
+/// アセットを書き出さない文字列変換用。詳細は [`render`] を参照。
+pub fn traverse(document: &Html, opts: &ConversionOptions) -> String {
+    render(document, opts, false, None).markdown
+}
+
+/// HTML ドキュメントをトラバースして Markdown とアセットを生成する。
+///
+/// `collect_assets` が `false` のとき、ファイルとして書き出すべき内容
+/// （[`SvgPolicy::File`](crate::options::SvgPolicy::File) など）は
+/// 本文へのインライン埋め込みで代替する。`source` は入力ファイルのパスで、
+/// ローカルの画像（`collect_local_assets`）はその親ディレクトリから探す。
+/// 読み込むのはそのディレクトリの中の画像だけ。
+///
 /// 再帰を使わず `Vec` ベースのスタックで深さ優先探索を行うため、
 /// 10,000段以上のネストでもスタックオーバーフローが発生しない。
 ///
 /// 前処理（タグ除外・ラッパーアンラップ）もこの関数内でインライン実行する。
-pub fn traverse(document: &Html, opts: &ConversionOptions) -> String {
+pub(crate) fn render(
+    document: &Html,
+    opts: &ConversionOptions,
+    collect_assets: bool,
+    source: Option<&Path>,
+) -> Rendered {
+    render_linked(document, opts, collect_assets, source, None, HashMap::new())
+}
+
+/// [`render`] に加えて、`links` にある `href` を書き換える（サイト変換）。
+/// `asset_scope` はローカルの画像を読んでよいディレクトリで、`None` なら
+/// 入力ファイルのディレクトリ。
+pub(crate) fn render_linked(
+    document: &Html,
+    opts: &ConversionOptions,
+    collect_assets: bool,
+    source: Option<&Path>,
+    asset_scope: Option<&Path>,
+    links: HashMap<String, String>,
+) -> Rendered {
     // 元の HTML サイズの半分を初期容量として確保
     let capacity = document.html().len() / 2;
-    let mut renderer = MarkdownRenderer::new(capacity.max(256));
+    let mut renderer = MarkdownRenderer::new(capacity.max(256), collect_assets);
+    let base_url = url::document_base(document, opts);
+    renderer.set_base_url(base_url.clone(), opts.url_resolution);
+    renderer.set_links(links);
+    renderer.set_link_style(opts.link_style, opts.link_policy);
+    renderer.set_image_dimensions(opts.image_dimensions);
+    if collect_assets && opts.collect_local_assets {
+        renderer.set_asset_root(source.and_then(|src| AssetRoot::new(src, asset_scope)));
+    }
+    if opts.clean_urls {
+        let params = if opts.tracking_params.is_empty() {
+            DEFAULT_TRACKING_PARAMS
+                .iter()
+                .map(|p| p.to_string())
+                .collect()
+        } else {
+            opts.tracking_params.clone()
+        };
+        renderer.set_url_cleaning(Some(params));
+    }
 
-    // root() は Document ノードなので子ノードだけを逆順で積む
+    let main_content = if opts.extract_main_content && opts.include_selectors.is_empty() {
+        extract::extract_main_content(document)
+    } else {
+        None
+    };
+    // SPA のハイドレーション用データ。DOM の本文が空のシェルなら置き換え、
+    // そうでなければ DOM にない本文だけを末尾に書き足す
+    let payloads = if opts.recover_hydration_payloads && opts.include_selectors.is_empty() {
+        hydration::payload_fragments(document)
+    } else {
+        Vec::new()
+    };
+    let dom_body = (!payloads.is_empty()).then(|| hydration::DomBody::of(document, opts));
+    let empty_shell = dom_body.as_ref().is_some_and(hydration::DomBody::is_empty);
+    if !opts.include_selectors.is_empty() {
+        // 選ばれた部分木はそれぞれ独立したブロックとして並べる
+        for root in included_roots(document, opts) {
+            renderer.open_block();
+            walk(
+                std::iter::once(root),
+                &mut renderer,
+                opts,
+                &HashSet::new(),
+                false,
+            );
+            renderer.close_block();
+        }
+    } else if empty_shell {
+        // 空のアプリケーションルートのアンカーなどは出力しない
+    } else if let Some(root) = main_content {
+        let clutter = extract::clutter_nodes(*root);
+        walk(root.children(), &mut renderer, opts, &clutter, false);
+    } else {
+        // root() は Document ノードなので子ノードだけを辿る
+        walk(
+            document.tree.root().children(),
+            &mut renderer,
+            opts,
+            &HashSet::new(),
+            false,
+        );
+    }
+
+    for html in &payloads {
+        let fragment = Html::parse_fragment(html);
+        if !empty_shell
+            && dom_body
+                .as_ref()
+                .is_some_and(|dom| dom.contains(&fragment, opts))
+        {
+            continue;
+        }
+        // 同じレンダラーで続けて出力するので、基準 URL や参照リンクの番号は
+        // DOM の本文と共通になる
+        renderer.open_block();
+        walk(
+            fragment.tree.root().children(),
+            &mut renderer,
+            opts,
+            &HashSet::new(),
+            false,
+        );
+        renderer.close_block();
+    }
+
+    let assets = renderer.take_assets();
+    let definitions = renderer.take_link_definitions();
+    let mut markdown = renderer.finish();
+    // 構造化データの Recipe / FAQPage で本文を補う
+    if opts.render_structured_data && opts.include_selectors.is_empty() {
+        structured::append_rich_content(document, &mut markdown, opts);
+    }
+    // 参照リンク・脚注の定義は、補った本文も含めた文書の末尾に置く
+    if !definitions.is_empty() {
+        markdown.push('\n');
+        markdown.push_str(&definitions);
+    }
+    Rendered {
+        markdown,
+        assets,
+        front_matter: String::new(),
+    }
+}
+
+/// `roots` 以下を深さ優先で辿り、`renderer` へ出力する。
+///
+/// `<noscript>` の中身は生テキストとして保持されているため、復元時は
+/// 断片として再パースし、その木に対してこの関数を呼び直す。
+/// `in_noscript` はその再帰が 1 段で止まることを保証する。
+/// `clutter` は本文抽出で取り除く付随要素（[`extract::clutter_nodes`]）。
+fn walk<'a>(
+    roots: impl DoubleEndedIterator<Item = ego_tree::NodeRef<'a, scraper::Node>>,
+    renderer: &mut MarkdownRenderer,
+    opts: &ConversionOptions,
+    clutter: &HashSet<ego_tree::NodeId>,
+    in_noscript: bool,
+) {
+    // 子ノードを逆順で積む
     let mut stack: Vec<Event> = Vec::with_capacity(64);
-    for child in document.tree.root().children().rev() {
-        stack.push(Event::Enter(child));
+    for root in roots.rev() {
+        stack.push(Event::Enter(root));
     }
+    // <noscript> の親ごとの判定。画像ギャラリーでは同じ親に <noscript> と
+    // プレースホルダー画像が並ぶため、親の子孫の走査は 1 度で済ませる
+    let mut noscript_parents: HashMap<ego_tree::NodeId, NoscriptParent> = HashMap::new();
+    let mut noscript_parent = |node: ego_tree::NodeRef<scraper::Node>| {
+        node.parent().map(|parent| {
+            *noscript_parents
+                .entry(parent.id())
+                .or_insert_with(|| NoscriptParent::of(parent, opts))
+        })
+    };
 
     while let Some(event) = stack.pop() {
         match event {
@@ -44,22 +230,156 @@ pub fn traverse(document: &Html, opts: &ConversionOptions) -> String {
                     // scraper (html5ever) はタグ名を小文字正規化済みで保持する
                     let tag = elem.name();
 
+                    // ── 前処理: 既定でスキップされる要素の中身の復元 ──────
+                    // <noscript>: 他に表示されるコンテンツがない場合のみ
+                    if tag == "noscript" && opts.recover_noscript {
+                        if !in_noscript && noscript_parent(node).is_none_or(|p| !p.rendered) {
+                            let markup: String = node
+                                .children()
+                                .filter_map(|c| c.value().as_text().map(|t| &**t))
+                                .collect();
+                            let fragment = Html::parse_fragment(&markup);
+                            // 断片は別の木なので、付随要素の集合は引き継がない
+                            walk(
+                                fragment.tree.root().children(),
+                                renderer,
+                                opts,
+                                &HashSet::new(),
+                                true,
+                            );
+                        }
+                        continue;
+                    }
+                    // 復元される <noscript> の画像が代わりを務める、src のない
+                    // 遅延読み込みのプレースホルダー画像
+                    if tag == "img"
+                        && opts.recover_noscript
+                        && !in_noscript
+                        && is_placeholder_image(elem)
+                        && noscript_parent(node).is_some_and(|p| p.image_fallback && !p.rendered)
+                    {
+                        continue;
+                    }
+                    // <template shadowrootmode>: 宣言的 Shadow DOM。中身は
+                    // scraper が子の Fragment ノードとして保持している
+                    if tag == "template" && opts.expand_shadow_roots && utils::is_shadow_root(elem)
+                    {
+                        for child in node.children().rev() {
+                            stack.push(Event::Enter(child));
+                        }
+                        continue;
+                    }
+
                     // ── 前処理: スキップ判定 ──────────────────────────
                     // 常にスキップ（コンテンツごと無視）
-                    if utils::is_skip_tag(tag) {
+                    if opts.tag_rules.is_skip(tag) {
+                        continue;
+                    }
+                    // exclude_selectors に一致する要素（オプション）
+                    if !opts.exclude_selectors.is_empty()
+                        && ElementRef::wrap(node).is_some_and(|e| {
+                            opts.exclude_selectors.iter().any(|sel| sel.matches(&e))
+                        })
+                    {
+                        continue;
+                    }
+                    // 非表示要素の除外（オプション）。sr-only は別に判定し、
+                    // keep_screen_reader_text が有効なら残す
+                    if opts.drop_hidden_content
+                        && (utils::is_hidden(elem)
+                            || (!opts.keep_screen_reader_text
+                                && utils::is_screen_reader_only(elem)))
+                    {
+                        continue;
+                    }
+                    // 本文抽出時の付随要素（サイドバー・コメントなど）
+                    if clutter.contains(&node.id()) {
                         continue;
                     }
                     // シェル要素の除外（オプション）
-                    if opts.drop_interactive_shell && utils::is_shell_tag(tag) {
+                    if opts.drop_interactive_shell && opts.tag_rules.is_shell(tag) {
+                        continue;
+                    }
+                    // インライン SVG は子孫を辿らず、要素全体をポリシーに従って出力
+                    if tag == "svg" {
+                        if let Some(svg) = ElementRef::wrap(node) {
+                            svg::render_svg(renderer, svg, opts.svg_policy);
+                        }
                         continue;
                     }
+                    // 絵文字の画像・<g-emoji> は文字か :shortcode: にする
+                    if matches!(tag, "img" | "g-emoji")
+                        && opts.emoji_policy != EmojiPolicy::Image
+                        && let Some(e) = ElementRef::wrap(node)
+                        && emoji::is_emoji(e)
+                    {
+                        emoji::render_emoji(renderer, e, opts.emoji_policy);
+                        continue;
+                    }
+                    // data: URI の画像はポリシーに従って出力
+                    if tag == "img"
+                        && opts.data_uri_policy != DataUriPolicy::Inline
+                        && elem.attr("src").is_some_and(data_uri::is_data_uri)
+                    {
+                        data_uri::render_image(renderer, elem, opts.data_uri_policy);
+                        continue;
+                    }
+
+                    // ── 前処理: 生成元プロファイル固有の処理 ───────────────
+                    match profile::classify(node, opts.profile) {
+                        Some(ProfileAction::Drop) => continue,
+                        Some(ProfileAction::Unwrap) => {
+                            for child in node.children().rev() {
+                                stack.push(Event::Enter(child));
+                            }
+                            continue;
+                        }
+                        Some(ProfileAction::Block) => {
+                            renderer.open_block();
+                            stack.push(Event::Leave(node));
+                            for child in node.children().rev() {
+                                stack.push(Event::Enter(child));
+                            }
+                            continue;
+                        }
+                        Some(ProfileAction::ListItem { level, ordered }) => {
+                            renderer.enter_pseudo_list_item(level, ordered);
+                            stack.push(Event::Leave(node));
+                            for child in node.children().rev() {
+                                stack.push(Event::Enter(child));
+                            }
+                            continue;
+                        }
+                        None => {}
+                    }
 
                     // ── 前処理: ラッパーアンラップ ─────────────────────
                     // タグ自体は出力せず、子だけをトラバースする
-                    if opts.unwrap_unknown_wrappers
-                        && utils::is_wrapper_tag(tag)
-                        && !utils::is_structural_tag(tag)
-                    {
+                    if opts.unwrap_unknown_wrappers && opts.tag_rules.is_wrapper(tag) {
+                        for child in node.children().rev() {
+                            stack.push(Event::Enter(child));
+                        }
+                        continue;
+                    }
+
+                    // ── 利用者指定のブロック要素（カスタム要素など） ──────
+                    if opts.tag_rules.is_block(tag) {
+                        renderer.open_block();
+                        stack.push(Event::Leave(node));
+                        for child in node.children().rev() {
+                            stack.push(Event::Enter(child));
+                        }
+                        continue;
+                    }
+
+                    // 空の図の説明は斜体の記号だけになるため出力しない
+                    if tag == "figcaption" && figure::caption_text(node).is_empty() {
+                        continue;
+                    }
+                    // リストや表を含む説明は斜体にせず、通常のブロックとして出力する
+                    if tag == "figcaption" && figure::has_block_content(node) {
+                        renderer.open_block();
+                        stack.push(Event::Leave(node));
                         for child in node.children().rev() {
                             stack.push(Event::Enter(child));
                         }
@@ -67,14 +387,36 @@ pub fn traverse(document: &Html, opts: &ConversionOptions) -> String {
                     }
 
                     // ── 通常処理: 要素を出力 ──────────────────────────
+                    let emphasis = style_emphasis(node, opts);
+                    renderer.open_emphasis(emphasis);
                     renderer.enter_element(elem, opts.preserve_ids);
 
                     // Leave イベントを先にスタックへ（子より後に処理される）
+                    if !emphasis.is_empty() {
+                        stack.push(Event::CloseEmphasis(emphasis));
+                    }
                     stack.push(Event::Leave(node));
 
-                    // 子を逆順でスタックへ
-                    for child in node.children().rev() {
-                        stack.push(Event::Enter(child));
+                    // 子を逆順でスタックへ。図の説明は図の内容の後に出力し、
+                    // 説明の中の段落は行内として扱う
+                    let caption = (tag == "figure").then(|| figure::caption(node)).flatten();
+                    if let Some(caption) = caption {
+                        renderer.set_figure_caption(figure::image_caption(node, caption));
+                        stack.push(Event::Enter(caption));
+                        for child in node.children().rev().filter(|c| c.id() != caption.id()) {
+                            stack.push(Event::Enter(child));
+                        }
+                    } else if tag == "figcaption" {
+                        for part in figure::caption_content(node).into_iter().rev() {
+                            stack.push(match part {
+                                CaptionPart::Node(child) => Event::Enter(child),
+                                CaptionPart::Break => Event::Space,
+                            });
+                        }
+                    } else {
+                        for child in node.children().rev() {
+                            stack.push(Event::Enter(child));
+                        }
                     }
                 }
                 scraper::Node::Text(text) => {
@@ -90,11 +432,148 @@ pub fn traverse(document: &Html, opts: &ConversionOptions) -> String {
             },
             Event::Leave(node) => {
                 if let scraper::Node::Element(elem) = node.value() {
+                    // Enter でプロファイルがブロック・擬似リスト項目として扱った要素
+                    match profile::classify(node, opts.profile) {
+                        Some(ProfileAction::Block) => {
+                            renderer.close_block();
+                            continue;
+                        }
+                        Some(ProfileAction::ListItem { .. }) => {
+                            renderer.leave_pseudo_list_item();
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    if opts.tag_rules.is_block(elem.name()) {
+                        renderer.close_block();
+                        continue;
+                    }
                     renderer.leave_element(elem);
                 }
             }
+            Event::CloseEmphasis(emphasis) => renderer.close_emphasis(emphasis),
+            Event::Space => renderer.process_text(" "),
         }
     }
+}
+
+/// `include_selectors` のいずれかに一致する要素を文書順に集める。
+///
+/// 一致した要素の子孫は探さないため、部分木が重なって二重に出力されることはない。
+fn included_roots<'a>(
+    document: &'a Html,
+    opts: &ConversionOptions,
+) -> Vec<ego_tree::NodeRef<'a, scraper::Node>> {
+    let mut roots = Vec::new();
+    let mut stack = vec![document.tree.root()];
+    while let Some(node) = stack.pop() {
+        let selected = ElementRef::wrap(node)
+            .is_some_and(|e| opts.include_selectors.iter().any(|sel| sel.matches(&e)));
+        if selected {
+            roots.push(node);
+            continue;
+        }
+        for child in node.children().rev() {
+            stack.push(child);
+        }
+    }
+    roots
+}
+
+/// `<span style="…">` が表す強調（`inline_style_semantics` 有効時のみ）。
+///
+/// 空白しか含まない span は、`****` のような空の強調を出さないよう対象外。
+/// Enter で 1 度だけ計算し、[`Event::CloseEmphasis`] で Leave の後に閉じる。
+fn style_emphasis(
+    node: ego_tree::NodeRef<scraper::Node>,
+    opts: &ConversionOptions,
+) -> StyleEmphasis {
+    if !opts.inline_style_semantics {
+        return StyleEmphasis::default();
+    }
+    let Some(elem) = node.value().as_element() else {
+        return StyleEmphasis::default();
+    };
+    if elem.name() != "span" {
+        return StyleEmphasis::default();
+    }
+    let Some(style) = elem.attr("style") else {
+        return StyleEmphasis::default();
+    };
+    let emphasis = utils::style_emphasis(style);
+    if emphasis.is_empty() {
+        return emphasis;
+    }
+    let is_text = |n: ego_tree::NodeRef<scraper::Node>| {
+        n.value().as_text().is_some_and(|t| !t.trim().is_empty())
+    };
+    // 子孫を走査するのは、最初の子が空白以外のテキストでないときだけ
+    let has_text = node.first_child().is_some_and(is_text) || node.descendants().any(is_text);
+    if has_text {
+        emphasis
+    } else {
+        StyleEmphasis::default()
+    }
+}
+
+/// `<noscript>` を子に持つ親についての判定。
+#[derive(Clone, Copy)]
+struct NoscriptParent {
+    /// `<noscript>` の兄弟（同じ親の他の子孫）に表示されるコンテンツがあるか。
+    ///
+    /// 空白以外のテキスト、または `data:` プレースホルダーでない `src` を持つ
+    /// `<img>` があれば、`<noscript>` は単なる代替表示とみなして復元しない。
+    /// 遅延読み込み画像（`<img data-src>` + `<noscript><img src></noscript>`）や
+    /// 空のアプリケーションルートの隣に置かれたサーバー描画テキストが対象。
+    rendered: bool,
+    /// 画像を含む `<noscript>` が子にあるか。あれば、復元時に同じ親の
+    /// プレースホルダー画像を出力しない。`<img data-src class="lazy">` は
+    /// 出力すると `![]()` になるため、復元する画像に任せる。
+    image_fallback: bool,
+}
+
+impl NoscriptParent {
+    fn of(parent: ego_tree::NodeRef<scraper::Node>, opts: &ConversionOptions) -> Self {
+        let image_fallback = parent.children().any(|c| {
+            c.value()
+                .as_element()
+                .is_some_and(|e| e.name() == "noscript")
+                && c.children()
+                    .any(|t| t.value().as_text().is_some_and(|t| t.contains("<img")))
+        });
+        // <noscript> 自体はスキップ対象なので、兄弟の <noscript> も数えない
+        let mut rendered = false;
+        let mut stack: Vec<_> = parent.children().collect();
+        while let Some(node) = stack.pop() {
+            match node.value() {
+                scraper::Node::Text(text) => {
+                    if !text.trim().is_empty() {
+                        rendered = true;
+                        break;
+                    }
+                }
+                scraper::Node::Element(elem) => {
+                    if opts.tag_rules.is_skip(elem.name()) {
+                        continue;
+                    }
+                    if elem.name() == "img" && !is_placeholder_image(elem) {
+                        rendered = true;
+                        break;
+                    }
+                    stack.extend(node.children());
+                }
+                _ => stack.extend(node.children()),
+            }
+        }
+        Self {
+            rendered,
+            image_fallback,
+        }
+    }
+}
 
-    renderer.finish()
+/// `src` がないか `data:` の、遅延読み込みのプレースホルダー画像か。
+fn is_placeholder_image(elem: &scraper::node::Element) -> bool {
+    elem.attr("src")
+        .is_none_or(|src| src.is_empty() || src.starts_with("data:"))
 }
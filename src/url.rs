@@ -0,0 +1,494 @@
+//! 相対 URL の解決（RFC 3986 §5）と URL の整理
+//!
+//! `<a href>` や `<img src>` の相対参照を、変換元ページの URL に対して
+//! 絶対 URL にする。外部クレートは使わず、RFC 3986 の参照解決の
+//! アルゴリズム（§5.2）をそのまま実装している。ホスト名の検証は行わない。
+//!
+//! `clean_urls` では、さらにリダイレクタの展開、トラッキング用の
+//! クエリパラメータの除去、パーセントエンコーディングの正規化（§6.2.2）を行う。
+
+use scraper::Html;
+
+use crate::options::{ConversionOptions, UrlResolution};
+
+/// 相対参照を解決する基準 URL。スキームを持つ絶対 URL だけを受け付ける。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaseUrl {
+    scheme: String,
+    authority: Option<String>,
+    path: String,
+    query: Option<String>,
+}
+
+impl BaseUrl {
+    /// 絶対 URL を読む。スキームがなければ `None`。フラグメントは捨てる。
+    pub fn parse(url: &str) -> Option<Self> {
+        let parts = Parts::parse(url.trim());
+        Some(Self {
+            scheme: parts.scheme?.to_string(),
+            authority: parts.authority.map(str::to_string),
+            path: parts.path.to_string(),
+            query: parts.query.map(str::to_string),
+        })
+    }
+
+    /// `reference` を解決した URL。`mode` の対象外の参照、スキームを持つ
+    /// 絶対 URL、空の参照、フラグメントだけの参照（`#top`）は書き換えず `None`。
+    ///
+    /// フラグメントだけの参照は、RFC 3986 では基準 URL のページを指すが、
+    /// 変換後の Markdown の中の見出しやアンカーを指すものとしてそのまま残す。
+    pub fn resolve(&self, reference: &str, mode: UrlResolution) -> Option<String> {
+        let reference = reference.trim_matches(|c: char| c.is_ascii_whitespace());
+        if reference.is_empty() || reference.starts_with('#') {
+            return None;
+        }
+        let r = Parts::parse(reference);
+        if r.scheme.is_some() {
+            return None;
+        }
+        let scheme_relative = r.authority.is_some();
+        let allowed = match mode {
+            UrlResolution::All => true,
+            UrlResolution::SchemeRelative => scheme_relative,
+            UrlResolution::PathRelative => !scheme_relative,
+            UrlResolution::Off => false,
+        };
+        if !allowed {
+            return None;
+        }
+
+        // §5.2.2（参照にスキームがない場合）
+        let (authority, path, query) = if let Some(authority) = r.authority {
+            (Some(authority), remove_dot_segments(r.path), r.query)
+        } else if r.path.is_empty() {
+            (
+                self.authority.as_deref(),
+                self.path.clone(),
+                r.query.or(self.query.as_deref()),
+            )
+        } else if r.path.starts_with('/') {
+            (
+                self.authority.as_deref(),
+                remove_dot_segments(r.path),
+                r.query,
+            )
+        } else {
+            (
+                self.authority.as_deref(),
+                remove_dot_segments(&self.merge(r.path)),
+                r.query,
+            )
+        };
+        Some(recompose(&self.scheme, authority, &path, query, r.fragment))
+    }
+
+    /// §5.2.3: 基準 URL のパスの最後の `/` までに `path` をつなぐ。
+    fn merge(&self, path: &str) -> String {
+        if self.authority.is_some() && self.path.is_empty() {
+            return format!("/{path}");
+        }
+        let dir = self.path.rfind('/').map_or("", |i| &self.path[..=i]);
+        format!("{dir}{path}")
+    }
+}
+
+impl std::fmt::Display for BaseUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&recompose(
+            &self.scheme,
+            self.authority.as_deref(),
+            &self.path,
+            self.query.as_deref(),
+            None,
+        ))
+    }
+}
+
+/// 文書の基準 URL。最初の `<base href>` を `opts.base_url` に対して
+/// 解決したもの、なければ `opts.base_url`。`url_resolution` が
+/// [`UrlResolution::Off`] のとき、または絶対 URL が得られないときは `None`。
+pub(crate) fn document_base(document: &Html, opts: &ConversionOptions) -> Option<BaseUrl> {
+    if opts.url_resolution == UrlResolution::Off {
+        return None;
+    }
+    let given = opts.base_url.as_deref().and_then(BaseUrl::parse);
+    // <head> は変換で読み飛ばすため、ここで先に探す
+    let declared = document
+        .tree
+        .root()
+        .descendants()
+        .filter_map(|n| n.value().as_element())
+        .find(|e| e.name() == "base" && e.attr("href").is_some())
+        .and_then(|e| e.attr("href"));
+    let Some(href) = declared else {
+        return given;
+    };
+    let resolved = given
+        .as_ref()
+        .and_then(|base| base.resolve(href, UrlResolution::All))
+        .unwrap_or_else(|| href.to_string());
+    BaseUrl::parse(&resolved).or(given)
+}
+
+/// 外部へのリンクか。スキームを持つか `//` で始まり、`base` と同じ
+/// ホストではない URL。
+pub(crate) fn is_external(href: &str, base: Option<&BaseUrl>) -> bool {
+    let r = Parts::parse(href.trim());
+    if r.scheme.is_none() && r.authority.is_none() {
+        return false;
+    }
+    let same_host = base
+        .and_then(|b| b.authority.as_deref())
+        .zip(r.authority)
+        .is_some_and(|(a, b)| a.eq_ignore_ascii_case(b));
+    !same_host
+}
+
+/// 移動先のないリンク（空、`#` だけ、`javascript:`）か。
+pub(crate) fn is_dead_link(href: &str) -> bool {
+    let href = href.trim();
+    href.is_empty()
+        || href == "#"
+        || href
+            .get(..11)
+            .is_some_and(|s| s.eq_ignore_ascii_case("javascript:"))
+}
+
+/// `srcset` の候補のうち、最も大きい（幅または倍率の記述子が最大の）画像の URL。
+/// 記述子のない候補は `1x` とみなす。
+pub(crate) fn largest_srcset_candidate(srcset: &str) -> Option<&str> {
+    let mut best: Option<(&str, f32)> = None;
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        // URL は空白までの連続した文字列。末尾のカンマは記述子のない候補の区切り
+        let end = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let (url, after) = rest.split_at(end);
+        let (url, descriptor) = if url.ends_with(',') {
+            rest = after;
+            (url.trim_end_matches(','), "")
+        } else {
+            let end = after.find(',').unwrap_or(after.len());
+            rest = &after[end..];
+            (url, after[..end].trim())
+        };
+        let size = descriptor
+            .strip_suffix(['w', 'x'])
+            .and_then(|n| n.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if best.is_none_or(|(_, s)| size > s) {
+            best = Some((url, size));
+        }
+    }
+    best.map(|(url, _)| url)
+}
+
+// ─── URL の整理 ───────────────────────────────────────────────────────────
+
+/// URL を整理する。変わらなければ `None`。
+///
+/// 1. 既知のリダイレクタ（Google の `/url?q=`、Facebook の `l.php?u=`、
+///    Outlook の safelinks など）を、包まれた URL に置き換える
+/// 2. `tracking_params` に一致するクエリパラメータを取り除く
+///    （末尾の `*` は前方一致、大小文字を区別しない）
+/// 3. スキームとホストを小文字に、`%xx` を大文字にし、非予約文字の `%xx` を戻す
+///
+/// `http` / `https` 以外のスキーム（`mailto:`、`data:` など）は対象外。
+pub(crate) fn clean_url(url: &str, tracking_params: &[String]) -> Option<String> {
+    let original = url.trim();
+    let mut url = original.to_string();
+    // リダイレクタが入れ子になっていることがある
+    for _ in 0..4 {
+        match unwrap_redirect(&url) {
+            Some(target) => url = target,
+            None => break,
+        }
+    }
+    let parts = Parts::parse(&url);
+    if parts
+        .scheme
+        .is_some_and(|s| !s.eq_ignore_ascii_case("http") && !s.eq_ignore_ascii_case("https"))
+    {
+        return None;
+    }
+    let query = parts
+        .query
+        .map(|q| {
+            q.split('&')
+                .filter(|pair| !pair.is_empty() && !is_tracking_param(pair, tracking_params))
+                .collect::<Vec<_>>()
+                .join("&")
+        })
+        .filter(|q| !q.is_empty());
+
+    let mut out = String::with_capacity(url.len());
+    if let Some(scheme) = parts.scheme {
+        out.push_str(&scheme.to_ascii_lowercase());
+        out.push(':');
+    }
+    if let Some(authority) = parts.authority {
+        out.push_str("//");
+        // userinfo は大小文字を区別する
+        match authority.rsplit_once('@') {
+            Some((userinfo, host)) => {
+                out.push_str(userinfo);
+                out.push('@');
+                out.push_str(&host.to_ascii_lowercase());
+            }
+            None => out.push_str(&authority.to_ascii_lowercase()),
+        }
+    }
+    out.push_str(parts.path);
+    if let Some(query) = query {
+        out.push('?');
+        out.push_str(&query);
+    }
+    if let Some(fragment) = parts.fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    let out = normalize_percent_encoding(&out);
+    (out != original).then_some(out)
+}
+
+/// リダイレクタの URL なら、包まれた `http(s)` の URL。
+fn unwrap_redirect(url: &str) -> Option<String> {
+    let parts = Parts::parse(url);
+    let authority = parts.authority?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let host = host.to_ascii_lowercase();
+    let keys: &[&str] = if (host.starts_with("www.google.") || host.starts_with("google."))
+        && parts.path == "/url"
+    {
+        &["q", "url"]
+    } else if matches!(
+        host.as_str(),
+        "l.facebook.com" | "lm.facebook.com" | "l.messenger.com" | "l.instagram.com"
+    ) {
+        &["u"]
+    } else if host.ends_with(".safelinks.protection.outlook.com") {
+        &["url"]
+    } else if (host == "www.youtube.com" || host == "youtube.com") && parts.path == "/redirect" {
+        &["q"]
+    } else {
+        return None;
+    };
+    parts.query?.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        if !keys.contains(&key) {
+            return None;
+        }
+        let target = percent_decode(&value.replace('+', " "));
+        let target = target.trim();
+        let scheme = target.split_once(':')?.0;
+        (scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+            .then(|| target.to_string())
+    })
+}
+
+/// `key=value` の `key` が `patterns` のいずれかに一致するか。
+fn is_tracking_param(pair: &str, patterns: &[String]) -> bool {
+    let key = percent_decode(pair.split('=').next().unwrap_or_default());
+    patterns.iter().any(|p| match p.strip_suffix('*') {
+        Some(prefix) => key
+            .get(..prefix.len())
+            .is_some_and(|k| k.eq_ignore_ascii_case(prefix)),
+        None => key.eq_ignore_ascii_case(p),
+    })
+}
+
+/// §6.2.2.1 / §6.2.2.2: `%xx` の 16 進を大文字にし、非予約文字
+/// （英数字と `-._~`）を表す `%xx` はその文字に戻す。
+fn normalize_percent_encoding(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = |b: u8| (b as char).to_digit(16);
+        if bytes[i] == b'%'
+            && let (Some(hi), Some(lo)) = (
+                bytes.get(i + 1).and_then(|&b| hex(b)),
+                bytes.get(i + 2).and_then(|&b| hex(b)),
+            )
+        {
+            let b = (hi * 16 + lo) as u8;
+            if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+                out.push(b as char);
+            } else {
+                out.push('%');
+                out.push_str(&s[i + 1..i + 3].to_ascii_uppercase());
+            }
+            i += 3;
+            continue;
+        }
+        // 複数バイトの文字もそのまま写す
+        let len = s[i..].chars().next().map_or(1, char::len_utf8);
+        out.push_str(&s[i..i + len]);
+        i += len;
+    }
+    out
+}
+
+/// 相対パス参照（スキームも `//` も持たず、`/` で始まらない参照）の
+/// パスをデコードしたもの。クエリとフラグメントは捨てる。
+pub(crate) fn relative_path(reference: &str) -> Option<String> {
+    let r = Parts::parse(reference.trim());
+    if r.scheme.is_some() || r.authority.is_some() || r.path.is_empty() || r.path.starts_with('/') {
+        return None;
+    }
+    Some(percent_decode(r.path))
+}
+
+/// スキームを持つ URL か `//` で始まる参照の、ホスト名（ユーザー情報と
+/// ポートを除く）とパス。
+pub(crate) fn host_and_path(url: &str) -> Option<(&str, &str)> {
+    let r = Parts::parse(url.trim());
+    let authority = r.authority?;
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = host
+        .rsplit_once(':')
+        .filter(|(_, port)| port.bytes().all(|b| b.is_ascii_digit()))
+        .map_or(host, |(h, _)| h);
+    (!host.is_empty()).then_some((host, r.path))
+}
+
+/// `%xx` をデコードする。UTF-8 として不正なバイト列は置換文字にする。
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = |b: u8| (b as char).to_digit(16);
+        if bytes[i] == b'%'
+            && let (Some(hi), Some(lo)) = (
+                bytes.get(i + 1).and_then(|&b| hex(b)),
+                bytes.get(i + 2).and_then(|&b| hex(b)),
+            )
+        {
+            out.push((hi * 16 + lo) as u8);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ─── 内部ヘルパー ─────────────────────────────────────────────────────────
+
+/// URI 参照の構成要素（RFC 3986 付録 B）。
+struct Parts<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> Parts<'a> {
+    fn parse(s: &'a str) -> Self {
+        let (rest, fragment) = match s.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (s, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.split_once(':') {
+            Some((scheme, rest)) if is_scheme(scheme) => (Some(scheme), rest),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        Self {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+pub(crate) fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+}
+
+/// §5.2.4: `.` と `..` のセグメントを取り除く。
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest;
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") {
+            input = &input[3..];
+            pop_segment(&mut output);
+        } else if input == "/.." {
+            input = "/";
+            pop_segment(&mut output);
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
+
+/// 出力の最後のセグメント（直前の `/` を含む）を取り除く。
+fn pop_segment(output: &mut String) {
+    let end = output.rfind('/').unwrap_or(0);
+    output.truncate(end);
+}
+
+/// §5.3: 構成要素をつなぎ直す。
+fn recompose(
+    scheme: &str,
+    authority: Option<&str>,
+    path: &str,
+    query: Option<&str>,
+    fragment: Option<&str>,
+) -> String {
+    let mut out = String::with_capacity(scheme.len() + path.len() + 16);
+    out.push_str(scheme);
+    out.push(':');
+    if let Some(authority) = authority {
+        out.push_str("//");
+        out.push_str(authority);
+    }
+    out.push_str(path);
+    if let Some(query) = query {
+        out.push('?');
+        out.push_str(query);
+    }
+    if let Some(fragment) = fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
@@ -6,6 +6,8 @@
 #[cfg(test)]
 mod tests;
 
+use std::fmt::Write;
+
 // ─── タグ分類（traversal 用） ──────────────────────────────────────────────
 
 /// コンテンツごと常にスキップするタグ。
@@ -23,10 +25,17 @@ pub(crate) fn is_skip_tag(tag: &str) -> bool {
             | "embed"
             | "noscript"
             | "head"
-            | "svg"
     )
 }
 
+/// 宣言的 Shadow DOM のルート（`<template shadowrootmode="open|closed">`）か。
+/// 旧仕様の `shadowroot` 属性も受け付ける。
+#[inline]
+pub(crate) fn is_shadow_root(elem: &scraper::node::Element) -> bool {
+    elem.name() == "template"
+        && (elem.attr("shadowrootmode").is_some() || elem.attr("shadowroot").is_some())
+}
+
 /// シェル要素（minimal モードで除外対象）。
 #[inline]
 pub(crate) fn is_shell_tag(tag: &str) -> bool {
@@ -75,6 +84,135 @@ pub(crate) fn is_structural_tag(tag: &str) -> bool {
     )
 }
 
+// ─── 非表示判定（traversal 用） ────────────────────────────────────────────
+
+/// スクリーンリーダー専用テキストを示す慣用クラス。
+const SCREEN_READER_CLASSES: &[&str] = &[
+    "sr-only",
+    "visually-hidden",
+    "visuallyhidden",
+    "screen-reader-text",
+];
+
+/// 要素が描画されないことを HTML / ARIA / インラインスタイルが示しているか。
+///
+/// * `hidden` 属性（`hidden="until-found"` はページ内検索で表示されるため除く）
+/// * `aria-hidden="true"`
+/// * `style` の `display: none` / `visibility: hidden | collapse`
+/// * `open` 属性のない `<dialog>`（既定で非表示のモーダル）
+pub(crate) fn is_hidden(elem: &scraper::node::Element) -> bool {
+    if elem
+        .attr("hidden")
+        .is_some_and(|v| !v.eq_ignore_ascii_case("until-found"))
+    {
+        return true;
+    }
+    if elem
+        .attr("aria-hidden")
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+    {
+        return true;
+    }
+    if elem.name() == "dialog" && elem.attr("open").is_none() {
+        return true;
+    }
+    let Some(style) = elem.attr("style") else {
+        return false;
+    };
+    style_declarations(style).any(|(prop, value)| {
+        (prop.eq_ignore_ascii_case("display") && value.eq_ignore_ascii_case("none"))
+            || (prop.eq_ignore_ascii_case("visibility")
+                && (value.eq_ignore_ascii_case("hidden") || value.eq_ignore_ascii_case("collapse")))
+    })
+}
+
+/// 視覚的には隠し、スクリーンリーダーにだけ読ませる要素か（`sr-only` など）。
+pub(crate) fn is_screen_reader_only(elem: &scraper::node::Element) -> bool {
+    elem.classes().any(|cls| {
+        SCREEN_READER_CLASSES
+            .iter()
+            .any(|c| cls.eq_ignore_ascii_case(c))
+    })
+}
+
+/// インライン `style` 属性を `(プロパティ, 値)` の組に分解する。
+///
+/// 前後の空白と `!important` を取り除く。プロパティ名・値の大小文字は
+/// そのまま返すため、比較は呼び出し側で大小文字を無視して行う。
+pub(crate) fn style_declarations(style: &str) -> impl Iterator<Item = (&str, &str)> {
+    style.split(';').filter_map(|decl| {
+        let (prop, value) = decl.split_once(':')?;
+        let value = value.trim();
+        let value = value
+            .strip_suffix("!important")
+            .map_or(value, |v| v.trim_end());
+        let prop = prop.trim();
+        (!prop.is_empty()).then_some((prop, value))
+    })
+}
+
+// ─── インラインスタイルの意味付け ──────────────────────────────────────────
+
+/// 等幅フォントとみなす `font-family` の名前（小文字、部分一致）。
+const MONOSPACE_FAMILIES: &[&str] = &[
+    "monospace",
+    "courier",
+    "consolas",
+    "menlo",
+    "monaco",
+    "source code",
+    "roboto mono",
+    "fira code",
+    "fira mono",
+    "dejavu sans mono",
+    "liberation mono",
+    "lucida console",
+];
+
+/// インライン `style` が表す強調。対応する Markdown 記法で出力する。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct StyleEmphasis {
+    /// `font-weight: bold | bolder | 600〜900` → `**`
+    pub strong: bool,
+    /// `font-style: italic | oblique` → `*`
+    pub em: bool,
+    /// `text-decoration(-line)` に `line-through` → `~~`
+    pub strike: bool,
+    /// 等幅の `font-family` → `` ` ``
+    pub code: bool,
+}
+
+impl StyleEmphasis {
+    pub fn is_empty(self) -> bool {
+        self == Self::default()
+    }
+}
+
+/// インライン `style` 属性から強調を読み取る。
+pub(crate) fn style_emphasis(style: &str) -> StyleEmphasis {
+    let mut e = StyleEmphasis::default();
+    for (prop, value) in style_declarations(style) {
+        let value = value.to_ascii_lowercase();
+        match prop.to_ascii_lowercase().as_str() {
+            "font-weight" => {
+                e.strong = match value.as_str() {
+                    "bold" | "bolder" => true,
+                    v => v.parse::<u16>().is_ok_and(|w| w >= 600),
+                };
+            }
+            "font-style" => e.em = value.starts_with("italic") || value.starts_with("oblique"),
+            "text-decoration" | "text-decoration-line" => {
+                e.strike = value.contains("line-through");
+            }
+            "font-family" => {
+                e.code = MONOSPACE_FAMILIES.iter().any(|f| value.contains(f));
+            }
+            _ => {}
+        }
+    }
+    e
+}
+
 // ─── テキスト正規化 ───────────────────────────────────────────────────────
 
 /// シングルパス テキスト正規化 + Markdownエスケープ。
@@ -138,6 +276,206 @@ pub fn write_normalised(
     }
 }
 
+// ─── リンク・画像の構文要素 ───────────────────────────────────────────────
+
+/// リンク・画像の行き先を CommonMark の link destination として書く。
+///
+/// 空白を含む URL は `<…>` で囲む。それ以外はそのまま書き、対応の取れない
+/// 括弧があれば括弧をすべてエスケープする。どちらの形でも `<` `>` と、
+/// ASCII 記号の前の `\` をエスケープし、改行などの制御文字は
+/// パーセントエンコードする。前後の空白は捨てる。
+pub(crate) fn push_link_destination(out: &mut String, url: &str) {
+    let url = url.trim_matches(|c: char| c.is_ascii_whitespace());
+    let angle = url.contains(' ');
+    let escape_parens = !angle && !parens_balanced(url);
+    if angle {
+        out.push('<');
+    }
+    let mut chars = url.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '<' | '>' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '(' | ')' if escape_parens => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\\' if chars.peek().is_some_and(|n| n.is_ascii_punctuation()) => {
+                out.push_str("\\\\");
+            }
+            c if c.is_ascii_control() => {
+                let _ = write!(out, "%{:02X}", c as u8);
+            }
+            c => out.push(c),
+        }
+    }
+    if angle {
+        out.push('>');
+    }
+}
+
+/// 括弧の対応が取れているか（閉じ括弧が先行せず、開きと閉じが同数）。
+fn parens_balanced(s: &str) -> bool {
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+/// リンク・画像のタイトルを ` "…"` として書く。空のタイトルは書かない。
+/// `"` と、ASCII 記号の前の `\` をエスケープし、改行は空白にする
+/// （空行を含むタイトルはリンクにならない）。
+pub(crate) fn push_link_title(out: &mut String, title: &str) {
+    if title.is_empty() {
+        return;
+    }
+    out.push_str(" \"");
+    let mut chars = title.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' if chars.peek().is_some_and(|n| n.is_ascii_punctuation()) => {
+                out.push_str("\\\\");
+            }
+            '\n' | '\r' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// 脚注の本文として URL を書く。
+///
+/// 脚注の本文は段落なので、リンク定義の `<…>` や `"…"` は使えない
+/// （`<a b.html>` は HTML のタグとして読まれる）。スキームのある絶対 URL は
+/// 自動リンク `<…>` にし、それ以外はテキストとして Markdown の記号を
+/// エスケープする。どちらも空白・制御文字・`<` `>` はパーセントエンコードする。
+pub(crate) fn push_footnote_url(out: &mut String, url: &str) {
+    let url = url.trim_matches(|c: char| c.is_ascii_whitespace());
+    // CommonMark の自動リンクのスキームは 2〜32 文字
+    let autolink = url.split_once(':').is_some_and(|(scheme, _)| {
+        (2..=32).contains(&scheme.len()) && crate::url::is_scheme(scheme)
+    });
+    if autolink {
+        out.push('<');
+    }
+    for c in url.chars() {
+        if c.is_whitespace() || c.is_control() || c == '<' || c == '>' {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                let _ = write!(out, "%{b:02X}");
+            }
+        } else if autolink {
+            out.push(c);
+        } else {
+            push_footnote_char(out, c);
+        }
+    }
+    if autolink {
+        out.push('>');
+    }
+}
+
+/// 脚注の本文として、リンクのタイトルを ` — …` のテキストで書く。
+/// 空のタイトルは書かない。改行は空白にする。
+pub(crate) fn push_footnote_title(out: &mut String, title: &str) {
+    if title.trim().is_empty() {
+        return;
+    }
+    out.push_str(" — ");
+    for c in title.trim().chars() {
+        match c {
+            '\n' | '\r' => out.push(' '),
+            c => push_footnote_char(out, c),
+        }
+    }
+}
+
+/// 脚注の本文（行頭ではないテキスト）で意味を持つ記号をエスケープする。
+fn push_footnote_char(out: &mut String, c: char) {
+    if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '&' | '~') {
+        out.push('\\');
+    }
+    out.push(c);
+}
+
+/// 画像の代替テキストを `![…]` の中身として書く。`\` と角括弧を
+/// エスケープし（本文のテキストと同じ規則）、改行は空白にする。
+pub(crate) fn push_alt_text(out: &mut String, alt: &str) {
+    for c in alt.chars() {
+        match c {
+            '\\' | '[' | ']' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' | '\r' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Pandoc / kramdown の属性リスト `{name=value …}` を書く。値が空の属性は
+/// 書かず、すべて空なら何も書かない。英数字と `.` `%` 以外を含む値は
+/// `"` で囲む。
+pub(crate) fn push_attribute_list(out: &mut String, attrs: &[(&str, &str)]) {
+    let mut first = true;
+    for (name, value) in attrs {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        out.push(if first { '{' } else { ' ' });
+        first = false;
+        out.push_str(name);
+        out.push('=');
+        if value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '%')
+        {
+            out.push_str(value);
+        } else {
+            out.push('"');
+            for c in value.chars() {
+                if matches!(c, '"' | '\\') {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            out.push('"');
+        }
+    }
+    if !first {
+        out.push('}');
+    }
+}
+
+/// HTML の属性 ` name="value"` を書く。`&` `"` `<` `>` は文字参照にする。
+pub(crate) fn push_html_attr(out: &mut String, name: &str, value: &str) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 /// `<code class="language-xxx">` からコード言語を抽出する。
 pub fn extract_code_lang(class: Option<&str>) -> Option<&str> {
     class?
@@ -145,3 +483,57 @@ pub fn extract_code_lang(class: Option<&str>) -> Option<&str> {
         .find(|cls| cls.starts_with("language-"))
         .map(|cls| &cls["language-".len()..])
 }
+
+// ─── Base64 ───────────────────────────────────────────────────────────────
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Base64 をデコードする。空白は読み飛ばし、パディングは省略できる。
+/// URL 安全な alphabet（`-` `_`）も受け付ける。不正な文字があれば `None`。
+pub(crate) fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for b in s.bytes() {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            b if b.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        acc = (acc << 6) | u32::from(v);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+/// 標準 Base64（RFC 4648、パディングあり）でエンコードして `out` へ追記する。
+/// `data:` URI の生成専用。
+pub(crate) fn write_base64(bytes: &[u8], out: &mut String) {
+    out.reserve(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
@@ -71,7 +71,8 @@ fn test_is_skip_tag() {
     assert!(is_skip_tag("script"));
     assert!(is_skip_tag("style"));
     assert!(is_skip_tag("head"));
-    assert!(is_skip_tag("svg"));
+    // svg は SvgPolicy で個別に扱うため、常時スキップの対象ではない
+    assert!(!is_skip_tag("svg"));
     assert!(!is_skip_tag("div"));
     assert!(!is_skip_tag("p"));
 }
@@ -91,3 +92,104 @@ fn test_is_structural_tag() {
     assert!(!is_structural_tag("div"));
     assert!(!is_structural_tag("span"));
 }
+
+#[test]
+fn test_write_base64() {
+    // RFC 4648 §10 のテストベクタ
+    for (input, expected) in [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ] {
+        let mut out = String::new();
+        write_base64(input.as_bytes(), &mut out);
+        assert_eq!(out, expected, "input: {input:?}");
+    }
+}
+
+#[test]
+fn test_decode_base64() {
+    for (expected, input) in [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+        // パディングの省略・空白・URL 安全な alphabet
+        ("foob", "Zm9v\r\nYg"),
+        ("\u{fb}\u{ff}", "w7vDvw"),
+    ] {
+        assert_eq!(
+            decode_base64(input).as_deref(),
+            Some(expected.as_bytes()),
+            "input: {input:?}"
+        );
+    }
+    assert_eq!(decode_base64("-_8").unwrap(), [0xfb, 0xff]);
+    assert_eq!(decode_base64("Zm9v!"), None);
+}
+
+// ─── 非表示判定のテスト ──────────────────────────────────────────────────
+
+#[test]
+fn test_style_declarations() {
+    let decls: Vec<_> =
+        style_declarations(" Display : NONE !important;; color:red; bogus ").collect();
+    assert_eq!(decls, vec![("Display", "NONE"), ("color", "red")]);
+}
+
+#[test]
+fn test_style_emphasis() {
+    let e =
+        style_emphasis("font-weight:700;font-style:italic;text-decoration:underline line-through");
+    assert!(e.strong && e.em && e.strike && !e.code);
+    assert!(!style_emphasis("font-weight:400").strong);
+    assert!(style_emphasis("font-weight: bold").strong);
+    // 後の宣言が前の宣言を上書きする
+    assert!(!style_emphasis("font-weight:bold;font-weight:normal").strong);
+    assert!(style_emphasis("font-family:'Courier New',Courier,monospace").code);
+    assert!(style_emphasis("color:red").is_empty());
+}
+
+// ─── リンク・画像の構文要素のテスト ──────────────────────────────────────
+
+fn dest(url: &str) -> String {
+    let mut out = String::new();
+    push_link_destination(&mut out, url);
+    out
+}
+
+#[test]
+fn test_push_link_destination() {
+    assert_eq!(
+        dest("https://example.com/a?b=1"),
+        "https://example.com/a?b=1"
+    );
+    assert_eq!(dest(""), "");
+    assert_eq!(dest("a b"), "<a b>");
+    // 山括弧形式では括弧をエスケープしない
+    assert_eq!(dest("a (b"), "<a (b>");
+    assert_eq!(dest("(a)(b)"), "(a)(b)");
+    assert_eq!(dest(")a("), "\\)a\\(");
+    assert_eq!(dest("a\\*b\\c"), "a\\\\*b\\c");
+    assert_eq!(dest("a\u{7f}b"), "a%7Fb");
+}
+
+#[test]
+fn test_push_link_title_and_alt() {
+    let mut out = String::new();
+    push_link_title(&mut out, "");
+    assert_eq!(out, "");
+    push_link_title(&mut out, "a \"b\"\\!");
+    assert_eq!(out, " \"a \\\"b\\\"\\\\!\"");
+
+    let mut out = String::new();
+    push_alt_text(&mut out, "[a]\\\nb");
+    assert_eq!(out, "\\[a\\]\\\\ b");
+}
@@ -0,0 +1,162 @@
+//! Integration tests: base URL resolution (`base_url`, `url_resolution`)
+//! Covers: the RFC 3986 §5.4 reference resolution examples, `<base href>`
+//! detection and its resolution against `base_url`, scheme- and path-relative
+//! only rewriting, `Off`, fragment-only and absolute URLs left alone, and
+//! `srcset` used for images without `src`.
+
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionOptions, UrlResolution};
+
+const RFC_BASE: &str = "http://a/b/c/d;p?q";
+
+fn link(href: &str, opts: &ConversionOptions) -> String {
+    html_to_markdown_with(&format!("<a href=\"{href}\">x</a>"), opts)
+}
+
+fn opts() -> ConversionOptions {
+    ConversionOptions::default().base_url(RFC_BASE)
+}
+
+#[test]
+fn rfc3986_normal_examples() {
+    let cases = [
+        ("g:h", "g:h"),
+        ("g", "http://a/b/c/g"),
+        ("./g", "http://a/b/c/g"),
+        ("g/", "http://a/b/c/g/"),
+        ("/g", "http://a/g"),
+        ("//g", "http://g"),
+        ("?y", "http://a/b/c/d;p?y"),
+        ("g?y", "http://a/b/c/g?y"),
+        ("g#s", "http://a/b/c/g#s"),
+        ("g?y#s", "http://a/b/c/g?y#s"),
+        (";x", "http://a/b/c/;x"),
+        ("g;x", "http://a/b/c/g;x"),
+        ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+        (".", "http://a/b/c/"),
+        ("./", "http://a/b/c/"),
+        ("..", "http://a/b/"),
+        ("../", "http://a/b/"),
+        ("../g", "http://a/b/g"),
+        ("../..", "http://a/"),
+        ("../../", "http://a/"),
+        ("../../g", "http://a/g"),
+    ];
+    for (reference, expected) in cases {
+        assert_eq!(
+            link(reference, &opts()),
+            format!("[x]({expected})\n"),
+            "{reference}"
+        );
+    }
+}
+
+#[test]
+fn rfc3986_abnormal_examples() {
+    let cases = [
+        ("../../../g", "http://a/g"),
+        ("../../../../g", "http://a/g"),
+        ("/./g", "http://a/g"),
+        ("/../g", "http://a/g"),
+        ("g.", "http://a/b/c/g."),
+        (".g", "http://a/b/c/.g"),
+        ("g..", "http://a/b/c/g.."),
+        ("..g", "http://a/b/c/..g"),
+        ("./../g", "http://a/b/g"),
+        ("./g/.", "http://a/b/c/g/"),
+        ("g/./h", "http://a/b/c/g/h"),
+        ("g/../h", "http://a/b/c/h"),
+        ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+        ("g;x=1/../y", "http://a/b/c/y"),
+        ("g?y/./x", "http://a/b/c/g?y/./x"),
+        ("g?y/../x", "http://a/b/c/g?y/../x"),
+        ("g#s/./x", "http://a/b/c/g#s/./x"),
+        ("g#s/../x", "http://a/b/c/g#s/../x"),
+        ("http:g", "http:g"),
+    ];
+    for (reference, expected) in cases {
+        assert_eq!(
+            link(reference, &opts()),
+            format!("[x]({expected})\n"),
+            "{reference}"
+        );
+    }
+}
+
+#[test]
+fn fragments_and_absolute_urls_kept() {
+    for href in [
+        "#s",
+        "mailto:a@example.com",
+        "https://other.example/x",
+        "data:,x",
+    ] {
+        assert_eq!(link(href, &opts()), format!("[x]({href})\n"));
+    }
+    // no base URL → nothing is rewritten
+    assert_eq!(link("../g", &ConversionOptions::default()), "[x](../g)\n");
+}
+
+#[test]
+fn base_href_detected() {
+    let html = r#"<html><head><base href="https://example.com/docs/"></head>
+      <body><a href="intro.html">Intro</a> <img src="img/a.png" alt="A"></body></html>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &ConversionOptions::default()),
+        "[Intro](https://example.com/docs/intro.html) ![A](https://example.com/docs/img/a.png)\n"
+    );
+
+    // a relative <base href> is resolved against base_url
+    let relative = html.replace("https://example.com/docs/", "../guide/");
+    let opts = ConversionOptions::default().base_url("https://example.com/v2/api/page");
+    assert_eq!(
+        html_to_markdown_with(&relative, &opts),
+        "[Intro](https://example.com/v2/guide/intro.html) ![A](https://example.com/v2/guide/img/a.png)\n"
+    );
+    // ...and ignored when there is nothing to resolve it against
+    assert_eq!(
+        html_to_markdown_with(&relative, &ConversionOptions::default()),
+        "[Intro](intro.html) ![A](img/a.png)\n"
+    );
+}
+
+#[test]
+fn resolution_modes() {
+    let html = r#"<a href="/docs">Docs</a> <img src="//cdn.example/a.png" alt="A">"#;
+    let base = ConversionOptions::default().base_url("https://example.com/blog/");
+    assert_eq!(
+        html_to_markdown_with(html, &base),
+        "[Docs](https://example.com/docs) ![A](https://cdn.example/a.png)\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(
+            html,
+            &base.clone().url_resolution(UrlResolution::SchemeRelative)
+        ),
+        "[Docs](/docs) ![A](https://cdn.example/a.png)\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(
+            html,
+            &base.clone().url_resolution(UrlResolution::PathRelative)
+        ),
+        "[Docs](https://example.com/docs) ![A](//cdn.example/a.png)\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(html, &base.url_resolution(UrlResolution::Off)),
+        "[Docs](/docs) ![A](//cdn.example/a.png)\n"
+    );
+    assert_eq!("path-relative".parse(), Ok(UrlResolution::PathRelative));
+    assert!("relative".parse::<UrlResolution>().is_err());
+}
+
+#[test]
+fn srcset_used_without_src() {
+    let html = r#"<img srcset="small.jpg 480w, large.jpg 1080w, medium.jpg 800w" alt="A">
+      <img srcset="a.png, a@2x.png 2x" alt="B">"#;
+    let opts = ConversionOptions::default().base_url("https://example.com/p/");
+    assert_eq!(
+        html_to_markdown_with(html, &opts),
+        "![A](https://example.com/p/large.jpg) ![B](https://example.com/p/a@2x.png)\n"
+    );
+}
@@ -0,0 +1,321 @@
+//! Integration tests: batch conversion (`html_files_to_markdown_batch`)
+//! Covers: site-wide boilerplate removal across pages, the reported blocks,
+//! the threshold, whitespace-insensitive matching, leaving code fences
+//! intact, per-file errors, near-duplicate grouping, and the SimHash helpers.
+
+use std::path::{Path, PathBuf};
+
+use mdka::options::ConversionOptions;
+use mdka::{BatchOptions, html_files_to_markdown_batch, markdown_simhash, simhash_similarity};
+
+const BANNER: &str =
+    "<div class=\"consent\"><p>We use cookies to improve your experience.</p></div>";
+const FOOTER: &str = "<div class=\"legal\"><p>© 2026 Example Corp. All rights reserved.</p></div>";
+
+fn write_pages(dir: &Path, pages: &[String]) -> Vec<PathBuf> {
+    std::fs::create_dir_all(dir).unwrap();
+    pages
+        .iter()
+        .enumerate()
+        .map(|(i, html)| {
+            let p = dir.join(format!("page{i}.html"));
+            std::fs::write(&p, html).unwrap();
+            p
+        })
+        .collect()
+}
+
+fn site_pages() -> Vec<String> {
+    ["Alpha", "Beta", "Gamma"]
+        .iter()
+        .map(|t| format!("<body>{BANNER}<h1>{t}</h1><p>Body of {t}.</p>{FOOTER}</body>"))
+        .collect()
+}
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn shared_blocks_removed_and_reported() {
+    let dir = std::env::temp_dir().join("mdka_batch_boilerplate");
+    let files = write_pages(&dir, &site_pages());
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().boilerplate_threshold(0.5);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+
+    assert_eq!(report.files.len(), 3);
+    let dest = report.files[1].1.as_ref().unwrap();
+    assert_eq!(read(dest), "# Beta\n\nBody of Beta.\n");
+
+    let dropped: Vec<_> = report
+        .boilerplate
+        .iter()
+        .map(|b| (b.markdown.as_str(), b.pages))
+        .collect();
+    assert_eq!(
+        dropped,
+        vec![
+            ("We use cookies to improve your experience.", 3),
+            ("© 2026 Example Corp. All rights reserved.", 3),
+        ]
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn no_threshold_keeps_everything() {
+    let dir = std::env::temp_dir().join("mdka_batch_no_threshold");
+    let files = write_pages(&dir, &site_pages());
+    let out = dir.join("out");
+
+    let report = html_files_to_markdown_batch(
+        &files,
+        &out,
+        &ConversionOptions::default(),
+        &BatchOptions::default(),
+    );
+
+    assert!(report.boilerplate.is_empty());
+    let md = read(report.files[0].1.as_ref().unwrap());
+    assert!(md.contains("We use cookies"), "got: {md}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn blocks_below_threshold_kept() {
+    let dir = std::env::temp_dir().join("mdka_batch_below_threshold");
+    let mut pages = site_pages();
+    pages.push("<h1>Delta</h1><p>Only content.</p>".to_string());
+    pages.push("<h1>Epsilon</h1><p>Only content.</p>".to_string());
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+
+    // 3 of 5 pages share the banner: dropped at 0.5, kept at 0.6
+    let opts = ConversionOptions::default();
+    let report = html_files_to_markdown_batch(
+        &files,
+        &out,
+        &opts,
+        &BatchOptions::default().boilerplate_threshold(0.6),
+    );
+    assert!(report.boilerplate.is_empty(), "{:?}", report.boilerplate);
+
+    let report = html_files_to_markdown_batch(
+        &files,
+        &out,
+        &opts,
+        &BatchOptions::default().boilerplate_threshold(0.5),
+    );
+    assert_eq!(report.boilerplate.len(), 2);
+    assert_eq!(
+        read(report.files[3].1.as_ref().unwrap()),
+        "# Delta\n\nOnly content.\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn single_page_has_no_boilerplate() {
+    let dir = std::env::temp_dir().join("mdka_batch_single");
+    let files = write_pages(&dir, &site_pages()[..1]);
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().boilerplate_threshold(0.0);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+    assert!(report.boilerplate.is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn whitespace_differences_still_match() {
+    let dir = std::env::temp_dir().join("mdka_batch_whitespace");
+    let pages = vec![
+        "<p>Sign up   for our\nnewsletter today.</p><p>First page.</p>".to_string(),
+        "<p>Sign up for our newsletter today.</p><p>Second page.</p>".to_string(),
+    ];
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().boilerplate_threshold(0.5);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+    assert_eq!(report.boilerplate.len(), 1);
+    assert_eq!(read(report.files[0].1.as_ref().unwrap()), "First page.\n");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn code_fence_with_blank_lines_is_one_block() {
+    let dir = std::env::temp_dir().join("mdka_batch_fence");
+    let code = "<pre><code>fn a() {}\n\nfn b() {}</code></pre>";
+    let pages = vec![
+        format!("<p>One.</p>{code}"),
+        "<p>Two.</p><pre><code>fn a() {}</code></pre>".to_string(),
+    ];
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().boilerplate_threshold(0.5);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+    assert!(report.boilerplate.is_empty(), "{:?}", report.boilerplate);
+    let md = read(report.files[0].1.as_ref().unwrap());
+    assert!(md.contains("fn a() {}\n\nfn b() {}"), "got: {md}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_file_reported_and_ignored_for_counting() {
+    let dir = std::env::temp_dir().join("mdka_batch_missing");
+    let mut files = write_pages(&dir, &site_pages()[..2]);
+    files.push(dir.join("missing.html"));
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().boilerplate_threshold(0.9);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+    assert!(report.files[2].1.is_err());
+    // 2 of the 2 readable pages share the banner and footer
+    assert_eq!(report.boilerplate.len(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ─── near-duplicate detection ─────────────────────────────────────────────
+
+const GUIDE: &str = "<h1>Installation</h1><p>Download the archive from the releases page, \
+unpack it into a directory on your PATH, and run the binary once to create the default \
+configuration file. The configuration lives in your home directory and can be edited \
+with any text editor. Restart the service after editing.</p>";
+
+const RECIPE: &str = "<h1>Pasta</h1><p>Boil the pasta in salted water, fry garlic in olive \
+oil, add chopped tomatoes and basil, and toss everything together with grated parmesan \
+before serving it warm on a large plate.</p>";
+
+#[test]
+fn near_duplicates_grouped() {
+    let dir = std::env::temp_dir().join("mdka_batch_near_dup");
+    let pages = vec![
+        GUIDE.to_string(),
+        RECIPE.to_string(),
+        GUIDE.replace("service", "daemon"),
+        GUIDE.to_string(),
+    ];
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+
+    let batch = BatchOptions::default().near_duplicate_threshold(0.85);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+    assert_eq!(report.duplicates, vec![vec![0, 2, 3]]);
+    // duplicates are reported, not dropped
+    assert!(report.files.iter().all(|(_, r)| r.is_ok()));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn near_duplicates_banding_misses_no_pair() {
+    let dir = std::env::temp_dir().join("mdka_batch_near_dup_many");
+    let mut pages: Vec<String> = (0..40)
+        .map(|i| {
+            format!(
+                "<h1>Page {i}</h1><p>Entry {i} covers topic {} in section {}.</p>",
+                i * 7,
+                i % 5
+            )
+        })
+        .collect();
+    pages.push(GUIDE.to_string());
+    pages.push(GUIDE.replace("service", "daemon"));
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+
+    for threshold in [0.85, 0.75, 0.6] {
+        let batch = BatchOptions::default().near_duplicate_threshold(threshold);
+        let report =
+            html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+        let hashes: Vec<u64> = report
+            .files
+            .iter()
+            .map(|(_, r)| markdown_simhash(&read(r.as_ref().unwrap())))
+            .collect();
+        let group_of = |i: usize| report.duplicates.iter().position(|g| g.contains(&i));
+        // every pair within the threshold ends up in the same group
+        for i in 0..hashes.len() {
+            for j in i + 1..hashes.len() {
+                if simhash_similarity(hashes[i], hashes[j]) >= threshold {
+                    assert!(
+                        group_of(i).is_some() && group_of(i) == group_of(j),
+                        "{threshold}: {i} and {j} not grouped"
+                    );
+                }
+            }
+        }
+        assert!(
+            group_of(40).is_some() && group_of(40) == group_of(41),
+            "{threshold}"
+        );
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn near_duplicates_off_by_default() {
+    let dir = std::env::temp_dir().join("mdka_batch_near_dup_off");
+    let files = write_pages(&dir, &[GUIDE.to_string(), GUIDE.to_string()]);
+    let out = dir.join("out");
+
+    let report = html_files_to_markdown_batch(
+        &files,
+        &out,
+        &ConversionOptions::default(),
+        &BatchOptions::default(),
+    );
+    assert!(report.duplicates.is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn near_duplicates_compared_after_boilerplate_removal() {
+    let dir = std::env::temp_dir().join("mdka_batch_near_dup_boilerplate");
+    // Two distinct short pages that share a long footer: similar as a whole,
+    // but not once the footer is removed.
+    let footer = "<p>Example Corp is a registered trademark. All rights reserved. \
+Contact us at the address below for licensing, press and partnership enquiries, \
+or visit the help centre for support with your account and billing.</p>";
+    let pages = vec![
+        format!("<p>Alpha release notes.</p>{footer}"),
+        format!("<p>Beta pricing overview.</p>{footer}"),
+    ];
+    let files = write_pages(&dir, &pages);
+    let out = dir.join("out");
+    let opts = ConversionOptions::default();
+
+    let dup_only = BatchOptions::default().near_duplicate_threshold(0.8);
+    let report = html_files_to_markdown_batch(&files, &out, &opts, &dup_only);
+    assert_eq!(report.duplicates, vec![vec![0, 1]]);
+
+    let both = dup_only.boilerplate_threshold(0.5);
+    let report = html_files_to_markdown_batch(&files, &out, &opts, &both);
+    assert!(report.duplicates.is_empty(), "{:?}", report.duplicates);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn simhash_similarity_reflects_content() {
+    let a = markdown_simhash("# Installation\n\nDownload the archive and run the binary once.\n");
+    let same = markdown_simhash("# installation\n\nDownload  the archive and run the binary once.");
+    let other = markdown_simhash("Boil the pasta in salted water and serve it warm.");
+    assert_eq!(a, same);
+    assert_eq!(simhash_similarity(a, a), 1.0);
+    assert!(simhash_similarity(a, other) < 0.8);
+    assert_eq!(markdown_simhash(""), 0);
+}
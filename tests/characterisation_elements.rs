@@ -129,10 +129,20 @@ fn always_skipped_script() {
 
 #[test]
 fn always_skipped_svg() {
+    // Previously asserted ["A\n\nB\n"; 5]: `<svg>` was in the always-skip
+    // list. It is now governed by `svg_policy` — an unlabelled SVG still
+    // emits nothing under `AltText` (every mode but Preserve), while
+    // Preserve's `Raw` keeps the markup. Updated here, not deleted.
     assert_matrix(
-        "always-skipped <svg>",
+        "unlabelled <svg>",
         "<p>A</p><svg><circle/></svg><p>B</p>",
-        ["A\n\nB\n"; 5],
+        [
+            "A\n\nB\n",
+            "A\n\nB\n",
+            "A\n\nB\n",
+            "A\n\nB\n",
+            "A\n\n<svg><circle></circle></svg>\n\nB\n",
+        ],
     );
 }
 
@@ -184,10 +194,14 @@ fn figure_and_figcaption_never_unwrapped() {
     // is_wrapper_tag and are additionally listed in is_structural_tag, so
     // they are never unwrapped in any mode, including Minimal and Semantic
     // (unwrap_unknown_wrappers = true in both).
+    //
+    // Previously ["![A](a.png)\n\nCap\n"; 5]: the caption was a loose
+    // paragraph. It is now an italic line after the figure's content, and the
+    // figure's only image takes the caption as its title (its alt is set).
     assert_matrix(
         "figure/figcaption",
         r#"<figure><img src="a.png" alt="A"><figcaption>Cap</figcaption></figure>"#,
-        ["![A](a.png)\n\nCap\n"; 5],
+        ["![A](a.png \"Cap\")\n\n*Cap*\n"; 5],
     );
 }
 
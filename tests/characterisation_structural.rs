@@ -42,11 +42,11 @@ fn drop_interactive_shell_toggle_changes_output_in_every_mode() {
         "[Home](/)\n\nContent\n", // preserve (default false)
     ];
     let expected_flipped = [
-        "Content\n",              // balanced, flipped to true
-        "Content\n",              // strict, flipped to true
-        "[Home](/)\n\nContent\n", // minimal, flipped to false
-        "Content\n",              // semantic, flipped to true
-        "Content\n",              // preserve, flipped to true
+        "Content\n",         // balanced, flipped to true
+        "Content\n",         // strict, flipped to true
+        "Home\n\nContent\n", // minimal, flipped to false (internal link as text)
+        "Content\n",         // semantic, flipped to true
+        "Content\n",         // preserve, flipped to true
     ];
 
     for ((mode, exp_base), exp_flip) in MODES
@@ -0,0 +1,143 @@
+//! Integration tests: `data:` URI images (`data_uri_policy`)
+//! Covers: `Inline` keeps the URI, `File` decodes base64 and percent-encoded
+//! payloads into `assets/` with the extension of the media type (one file per
+//! distinct content) and falls back to `Inline` for string conversion and
+//! undecodable URIs, `AltText` and `Drop`, images inside links, the
+//! `Minimal` default, and policy names.
+
+use std::str::FromStr;
+
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionMode, ConversionOptions, DataUriPolicy};
+
+/// "foobar" as a base64 PNG payload.
+const PNG_URI: &str = "data:image/png;base64,Zm9vYmFy";
+
+fn with_policy(p: DataUriPolicy) -> ConversionOptions {
+    ConversionOptions::default().data_uri_policy(p)
+}
+
+fn img(src: &str) -> String {
+    format!("<p>Before <img src=\"{src}\" alt=\"Chart\"> after</p>")
+}
+
+#[test]
+fn inline_is_default_and_keeps_uri() {
+    let expected = format!("Before ![Chart]({PNG_URI}) after\n");
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &ConversionOptions::default()),
+        expected
+    );
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &with_policy(DataUriPolicy::Inline)),
+        expected
+    );
+}
+
+#[test]
+fn alt_text_and_drop() {
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &with_policy(DataUriPolicy::AltText)),
+        "Before Chart after\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &with_policy(DataUriPolicy::Drop)),
+        "Before after\n"
+    );
+    // inside a link the alt text becomes the link text
+    assert_eq!(
+        html_to_markdown_with(
+            &format!("<a href=\"/a\"><img src=\"{PNG_URI}\" alt=\"Logo\"></a>"),
+            &with_policy(DataUriPolicy::AltText)
+        ),
+        "[Logo](/a)\n"
+    );
+    // ordinary images are not affected
+    assert_eq!(
+        html_to_markdown_with(
+            "<img src=\"a.png\" alt=\"A\">",
+            &with_policy(DataUriPolicy::Drop)
+        ),
+        "![A](a.png)\n"
+    );
+}
+
+#[test]
+fn minimal_uses_alt_text() {
+    let opts = ConversionOptions::for_mode(ConversionMode::Minimal);
+    assert_eq!(opts.data_uri_policy, DataUriPolicy::AltText);
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &opts),
+        "Before Chart after\n"
+    );
+}
+
+#[test]
+fn file_falls_back_to_inline_for_strings() {
+    assert_eq!(
+        html_to_markdown_with(&img(PNG_URI), &with_policy(DataUriPolicy::File)),
+        format!("Before ![Chart]({PNG_URI}) after\n")
+    );
+}
+
+#[test]
+fn file_writes_decoded_assets() {
+    let dir = std::env::temp_dir().join("mdka_test_data_uri_file");
+    let _ = std::fs::remove_dir_all(&dir);
+    let out = dir.join("out");
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join("mail.html");
+    std::fs::write(
+        &src,
+        format!(
+            "<img src=\"{PNG_URI}\" alt=\"A\">\
+             <img src=\"DATA:image/PNG;charset=x;BASE64,Zm9v%0AYmFy\" alt=\"Same\">\
+             <img src=\"data:image/svg+xml,%3Csvg%2F%3E\" alt=\"Icon\">\
+             <img src=\"data:image/png;base64,!!\" alt=\"Broken\">\
+             <img src=\"data:text/plain,hi\" alt=\"Text\">"
+        ),
+    )
+    .unwrap();
+
+    let opts = with_policy(DataUriPolicy::File);
+    let result = mdka::html_file_to_markdown_with(&src, Some(&out), &opts).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    let srcs: Vec<&str> = md
+        .split("](")
+        .skip(1)
+        .map(|rest| rest.split(')').next().unwrap())
+        .collect();
+    assert_eq!(srcs.len(), 5, "got: {md}");
+    assert!(
+        srcs[0].starts_with("assets/") && srcs[0].ends_with(".png"),
+        "got: {md}"
+    );
+    assert_eq!(srcs[1], srcs[0], "got: {md}");
+    assert!(srcs[2].ends_with(".svg"), "got: {md}");
+    assert_eq!(srcs[3], "data:image/png;base64,!!");
+    assert_eq!(srcs[4], "data:text/plain,hi");
+
+    assert_eq!(std::fs::read(out.join(srcs[0])).unwrap(), b"foobar");
+    assert_eq!(
+        std::fs::read_to_string(out.join(srcs[2])).unwrap(),
+        "<svg/>"
+    );
+    assert_eq!(std::fs::read_dir(out.join("assets")).unwrap().count(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn policy_parses_from_str() {
+    for p in [
+        DataUriPolicy::Inline,
+        DataUriPolicy::File,
+        DataUriPolicy::AltText,
+        DataUriPolicy::Drop,
+    ] {
+        assert_eq!(DataUriPolicy::from_str(p.as_str()), Ok(p));
+        assert_eq!(p.to_string(), p.as_str());
+    }
+    assert!("embed".parse::<DataUriPolicy>().is_err());
+}
@@ -0,0 +1,141 @@
+//! Integration tests: `SourceProfile::Email`
+//! Covers: layout tables rendered as paragraphs, spacer / 1×1 / tracker
+//! image removal, `<center>` and `<font>` as plain text, preheader removal
+//! (class and email-client hiding styles) without touching MJML's
+//! `font-size:0` column wrappers, and full newsletter fixtures.
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionMode, ConversionOptions, SourceProfile};
+
+fn email() -> ConversionOptions {
+    ConversionOptions::default().profile(SourceProfile::Email)
+}
+
+#[test]
+fn layout_table_cells_become_paragraphs() {
+    let html = concat!(
+        r#"<table role="presentation" width="100%"><tr><td align="center">"#,
+        r#"<table><tr><td>Hello</td></tr><tr><td>World</td></tr></table>"#,
+        r#"</td></tr></table>"#,
+    );
+    assert_eq!(conv_with(html, &email()), "Hello\n\nWorld\n");
+}
+
+#[test]
+fn tracking_and_spacer_images_dropped() {
+    let html = concat!(
+        r#"<p>Hi<img src="https://example.com/o.gif" width="1" height="1" alt=""></p>"#,
+        r#"<p><img src="https://example.com/images/spacer.gif" width="20" height="10"></p>"#,
+        r#"<p><img src="https://click.example.com/wf/open?upn=abc" alt=""></p>"#,
+        r#"<p><img src="pic.png" style="width:1px;height:1px"></p>"#,
+        r#"<p><img src="logo.png" width="120" height="40" alt="Logo"></p>"#,
+    );
+    assert_eq!(conv_with(html, &email()), "Hi\n\n![Logo](logo.png)\n");
+}
+
+#[test]
+fn center_and_font_become_text() {
+    let html = r##"<center><font face="Arial" color="#333333" size="2">Centered</font></center><font>Inline</font>"##;
+    assert_eq!(conv_with(html, &email()), "Centered\n\nInline\n");
+}
+
+#[test]
+fn preheader_removed() {
+    let preheaders = [
+        r#"<div class="preheader">Preview text</div>"#,
+        r#"<span style="display:none;max-height:0;overflow:hidden">Preview text</span>"#,
+        r#"<div style="mso-hide:all">Preview text</div>"#,
+        // history: a zero font-size / opacity alone used to count; it now
+        // needs overflow:hidden, since MJML puts font-size:0 on content cells
+        r#"<div style="font-size:0px;line-height:0;overflow:hidden">Preview text</div>"#,
+        r#"<div style="opacity:0;color:transparent;overflow:hidden">Preview text</div>"#,
+    ];
+    for pre in preheaders {
+        let html = format!("{pre}<p>Body</p>");
+        assert_eq!(conv_with(&html, &email()), "Body\n", "{pre}");
+    }
+}
+
+#[test]
+fn preheader_removed_even_when_hidden_content_is_kept() {
+    let opts = ConversionOptions::for_mode(ConversionMode::Strict).profile(SourceProfile::Email);
+    assert_eq!(
+        conv_with(r#"<div class="preheader">Preview</div><p>Body</p>"#, &opts),
+        "Body\n"
+    );
+}
+
+#[test]
+fn newsletter_reads_as_message() {
+    let html = r##"<!DOCTYPE html><html><body style="margin:0">
+<div class="preheader" style="display:none">This week: three new features</div>
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" bgcolor="#eeeeee"><tr><td>
+  <center>
+  <table width="600" cellpadding="0" cellspacing="0"><tr><td height="20"><img src="spacer.gif" width="1" height="20"></td></tr>
+    <tr><td><h1 style="font-family:Arial">Weekly update</h1></td></tr>
+    <tr><td><font face="Arial" size="3">Hello <b>Reader</b>, here is what changed.</font></td></tr>
+    <tr><td><a href="https://example.com/blog">Read more</a></td></tr>
+  </table>
+  </center>
+</td></tr></table>
+<img src="https://example.list-manage.com/track/open.php?u=1" width="1" height="1" alt="">
+</body></html>"##;
+    assert_eq!(
+        conv_with(html, &email()),
+        "# Weekly update\n\nHello **Reader**, here is what changed.\n\n[Read more](https://example.com/blog)\n"
+    );
+}
+
+#[test]
+fn zero_font_size_alone_is_not_a_preheader() {
+    let html = r#"<table><tr><td style="font-size:0px;text-align:center"><div class="mj-column"><p>Your order has shipped.</p></div></td></tr></table>"#;
+    assert_eq!(
+        conv_with(html, &email()),
+        "Your order has shipped.
+"
+    );
+}
+
+#[test]
+fn mjml_newsletter_keeps_its_columns() {
+    // trimmed output of the MJML compiler for a one-section, two-column mail
+    let html = r##"<!doctype html><html><body style="word-spacing:normal;background-color:#f4f4f4;">
+<div style="display:none;font-size:1px;color:#ffffff;line-height:1px;max-height:0px;max-width:0px;opacity:0;overflow:hidden;">Your order is on its way</div>
+<div style="background-color:#f4f4f4;">
+<div style="margin:0px auto;max-width:600px;">
+<table align="center" border="0" cellpadding="0" cellspacing="0" role="presentation" style="width:100%;"><tbody><tr>
+<td style="direction:ltr;font-size:0px;padding:20px 0;text-align:center;">
+<div class="mj-column-per-50 mj-outlook-group-fix" style="font-size:0px;text-align:left;direction:ltr;display:inline-block;vertical-align:top;width:100%;">
+<table border="0" cellpadding="0" cellspacing="0" role="presentation" style="vertical-align:top;" width="100%"><tbody><tr>
+<td align="left" style="font-size:0px;padding:10px 25px;word-break:break-word;">
+<div style="font-family:Arial, sans-serif;font-size:13px;line-height:1;text-align:left;color:#000000;">Your order has shipped.</div>
+</td></tr></tbody></table></div>
+<div class="mj-column-per-50 mj-outlook-group-fix" style="font-size:0px;text-align:left;direction:ltr;display:inline-block;vertical-align:top;width:100%;">
+<table border="0" cellpadding="0" cellspacing="0" role="presentation" style="vertical-align:top;" width="100%"><tbody><tr>
+<td align="left" style="font-size:0px;padding:10px 25px;word-break:break-word;">
+<div style="font-family:Arial, sans-serif;font-size:13px;line-height:1;text-align:left;color:#000000;"><a href="https://example.com/track">Track your parcel</a></div>
+</td></tr></tbody></table></div>
+</td></tr></tbody></table></div></div>
+</body></html>"##;
+    assert_eq!(
+        conv_with(html, &email()),
+        "Your order has shipped.
+
+[Track your parcel](https://example.com/track)
+"
+    );
+}
+
+#[test]
+fn generic_profile_keeps_table_text_inline() {
+    let html = "<table><tr><td>A</td><td>B</td></tr></table>";
+    assert_eq!(conv(html), "AB\n");
+    assert_eq!(conv_with(html, &email()), "A\n\nB\n");
+}
+
+#[test]
+fn email_parses_from_str() {
+    assert_eq!("email".parse::<SourceProfile>(), Ok(SourceProfile::Email));
+    assert_eq!(SourceProfile::Email.to_string(), "email");
+}
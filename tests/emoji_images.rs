@@ -0,0 +1,152 @@
+//! Integration tests: emoji images (`emoji_policy`)
+//! Covers: emoji images detected by class (GitHub, WordPress, Slack) and by
+//! emoji CDN host (Twemoji, WordPress, GitHub), the character taken from
+//! `alt` or recovered from the file name's code points, `:shortcode:` from
+//! `<g-emoji alias>`, `data-stringify-emoji` and `:name:` alt text, fallbacks
+//! in both directions, ordinary images left alone, emoji inside links, the
+//! `Preserve` default, and policy names.
+
+use std::str::FromStr;
+
+use mdka::options::{ConversionMode, ConversionOptions, EmojiPolicy};
+use mdka::{html_to_markdown, html_to_markdown_with};
+
+fn shortcodes() -> ConversionOptions {
+    ConversionOptions::default().emoji_policy(EmojiPolicy::Shortcode)
+}
+
+#[test]
+fn emoji_class_becomes_character() {
+    // WordPress
+    assert_eq!(
+        html_to_markdown(
+            "<p>Thanks <img draggable=\"false\" role=\"img\" class=\"emoji\" alt=\"😄\" src=\"https://example.com/smile.png\"> all</p>"
+        ),
+        "Thanks 😄 all\n"
+    );
+    assert_eq!(
+        html_to_markdown("<p>Hi <img class=\"wp-smiley\" alt=\"🙂\" src=\"/s.png\"></p>"),
+        "Hi 🙂\n"
+    );
+}
+
+#[test]
+fn emoji_hosts_are_detected_without_class() {
+    for src in [
+        "https://twemoji.maxcdn.com/v/latest/72x72/1f604.png",
+        "https://cdn.jsdelivr.net/gh/twitter/twemoji@14.0.2/assets/svg/1f604.svg",
+        "https://s.w.org/images/core/emoji/14.0.0/72x72/1f604.png",
+        "https://github.githubassets.com/images/icons/emoji/unicode/1f604.png?v8",
+        "//a.slack-edge.com/production-standard-emoji-assets/14.0/apple-medium/1f604@2x.png",
+    ] {
+        assert_eq!(
+            html_to_markdown(&format!("<p>A <img src=\"{src}\"> B</p>")),
+            "A 😄 B\n",
+            "src: {src}"
+        );
+    }
+}
+
+#[test]
+fn character_recovered_from_file_name() {
+    // ZWJ sequence; the alt is a shortcode, so the file name is used
+    assert_eq!(
+        html_to_markdown(
+            "<img class=\"emoji\" alt=\":family_man_woman:\" src=\"https://twemoji.maxcdn.com/2/72x72/1f468-200d-1f469.png\">"
+        ),
+        "👨\u{200d}👩\n"
+    );
+}
+
+#[test]
+fn shortcodes_from_alias_and_alt() {
+    let opts = shortcodes();
+    assert_eq!(
+        html_to_markdown_with(
+            "<p><g-emoji class=\"g-emoji\" alias=\"thumbs_up\" fallback-src=\"https://github.githubassets.com/images/icons/emoji/unicode/1f44d.png\">👍</g-emoji> LGTM</p>",
+            &opts
+        ),
+        ":thumbs_up: LGTM\n"
+    );
+    // Slack export
+    assert_eq!(
+        html_to_markdown_with(
+            "<p>Done <img class=\"c-emoji\" data-stringify-emoji=\":+1:\" alt=\"thumbs up\" src=\"https://a.slack-edge.com/production-standard-emoji-assets/14.0/google-medium/1f44d.png\"></p>",
+            &opts
+        ),
+        "Done :+1:\n"
+    );
+    // GitHub custom emoji
+    assert_eq!(
+        html_to_markdown_with(
+            "<img class=\"emoji\" title=\":octocat:\" alt=\":octocat:\" src=\"https://github.githubassets.com/images/icons/emoji/octocat.png\">",
+            &opts
+        ),
+        ":octocat:\n"
+    );
+}
+
+#[test]
+fn fallbacks_between_character_and_shortcode() {
+    // no name known: Shortcode falls back to the character
+    assert_eq!(
+        html_to_markdown_with("<p><img class=\"emoji\" alt=\"😄\"> x</p>", &shortcodes()),
+        "😄 x\n"
+    );
+    // no character known: Unicode falls back to the shortcode
+    assert_eq!(
+        html_to_markdown(
+            "<img class=\"emoji\" alt=\":octocat:\" src=\"https://github.githubassets.com/images/icons/emoji/octocat.png\">"
+        ),
+        ":octocat:\n"
+    );
+    // neither: the image is kept
+    assert_eq!(
+        html_to_markdown("<img class=\"emoji\" src=\"party.png\">"),
+        "![](party.png)\n"
+    );
+    // <g-emoji> keeps its text
+    assert_eq!(
+        html_to_markdown("<p><g-emoji>🎉</g-emoji> yay</p>"),
+        "🎉 yay\n"
+    );
+}
+
+#[test]
+fn ordinary_images_and_links() {
+    assert_eq!(
+        html_to_markdown("<img src=\"https://example.com/1f604.png\" alt=\"😄\">"),
+        "![😄](https://example.com/1f604.png)\n"
+    );
+    assert_eq!(
+        html_to_markdown("<a href=\"/r\"><img class=\"emoji\" alt=\"🚀\"> Ship</a>"),
+        "[🚀 Ship](/r)\n"
+    );
+}
+
+#[test]
+fn image_policy_and_preserve_keep_images() {
+    let html = "<p>Hi <img class=\"emoji\" alt=\"😄\" src=\"s.png\"></p>";
+    let opts = ConversionOptions::default().emoji_policy(EmojiPolicy::Image);
+    assert_eq!(html_to_markdown_with(html, &opts), "Hi ![😄](s.png)\n");
+
+    let preserve = ConversionOptions::for_mode(ConversionMode::Preserve);
+    assert_eq!(preserve.emoji_policy, EmojiPolicy::Image);
+    assert_eq!(
+        ConversionOptions::default().emoji_policy,
+        EmojiPolicy::Unicode
+    );
+}
+
+#[test]
+fn policy_parses_from_str() {
+    for p in [
+        EmojiPolicy::Unicode,
+        EmojiPolicy::Shortcode,
+        EmojiPolicy::Image,
+    ] {
+        assert_eq!(EmojiPolicy::from_str(p.as_str()), Ok(p));
+        assert_eq!(p.to_string(), p.as_str());
+    }
+    assert!("text".parse::<EmojiPolicy>().is_err());
+}
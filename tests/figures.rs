@@ -0,0 +1,202 @@
+//! Integration tests: figures, captions and image dimensions
+//! Covers: `<figcaption>` rendered as an italic line after the figure's
+//! content wherever it appears, the caption filling the only image's alt or
+//! title, caption paragraphs kept inline and separated, captions with
+//! lists or tables rendered as blocks, empty captions and figures with
+//! several images, and `image_dimensions` (`Drop`, Pandoc-style
+//! `Attributes`, `Html` passthrough and its fallback inside links, the
+//! `Preserve` default, names).
+
+use std::str::FromStr;
+
+use mdka::options::{ConversionMode, ConversionOptions, ImageDimensions};
+use mdka::{html_to_markdown, html_to_markdown_with};
+
+fn with_dims(d: ImageDimensions) -> ConversionOptions {
+    ConversionOptions::default().image_dimensions(d)
+}
+
+#[test]
+fn caption_follows_image_as_italic_line() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><figcaption>Fig. 1: Sales</figcaption><img src=\"a.png\" alt=\"Chart\"></figure><p>Next</p>"
+        ),
+        "![Chart](a.png \"Fig. 1: Sales\")\n\n*Fig. 1: Sales*\n\nNext\n"
+    );
+}
+
+#[test]
+fn caption_fills_missing_alt_before_title() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\"><figcaption>  A   chart </figcaption></figure>"
+        ),
+        "![A chart](a.png)\n\n*A chart*\n"
+    );
+    // an existing title is kept
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\" title=\"T\"><figcaption>Cap</figcaption></figure>"
+        ),
+        "![A](a.png \"T\")\n\n*Cap*\n"
+    );
+}
+
+#[test]
+fn caption_paragraphs_stay_inline() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\"><figcaption><p>Photo by <a href=\"https://x.dev\">Ann</a></p></figcaption></figure>"
+        ),
+        "![A](a.png \"Photo by Ann\")\n\n*Photo by [Ann](https://x.dev)*\n"
+    );
+}
+
+#[test]
+fn caption_paragraphs_are_separated_by_a_space() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\"><figcaption><p>First para.</p><p>Second para.</p></figcaption></figure>"
+        ),
+        "![First para. Second para.](a.png)\n\n*First para. Second para.*\n"
+    );
+}
+
+#[test]
+fn caption_with_a_list_is_not_italic() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\"><figcaption>See <ul><li>one</li><li>two</li></ul></figcaption></figure><p>Next</p>"
+        ),
+        "![A](a.png \"See one two\")\n\nSee\n\n- one\n- two\n\nNext\n"
+    );
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\"><figcaption><blockquote>Quoted</blockquote>Source</figcaption></figure>"
+        ),
+        "![A](a.png \"Quoted Source\")\n\n> Quoted\n\nSource\n"
+    );
+}
+
+#[test]
+fn several_images_or_none_keep_their_own_text() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\"><img src=\"b.png\"><figcaption>Both</figcaption></figure>"
+        ),
+        "![A](a.png)![](b.png)\n\n*Both*\n"
+    );
+    assert_eq!(
+        html_to_markdown(
+            "<figure><blockquote><p>Quote</p></blockquote><figcaption>Author</figcaption></figure>"
+        ),
+        "> Quote\n\n*Author*\n"
+    );
+}
+
+#[test]
+fn empty_caption_is_skipped() {
+    assert_eq!(
+        html_to_markdown(
+            "<figure><img src=\"a.png\" alt=\"A\"><figcaption> </figcaption></figure>"
+        ),
+        "![A](a.png)\n"
+    );
+}
+
+#[test]
+fn dimensions_dropped_by_default() {
+    let html = "<img src=\"a.png\" alt=\"A\" width=\"100\" height=\"50\">";
+    assert_eq!(html_to_markdown(html), "![A](a.png)\n");
+    assert_eq!(
+        html_to_markdown_with(html, &with_dims(ImageDimensions::Drop)),
+        "![A](a.png)\n"
+    );
+}
+
+#[test]
+fn dimensions_as_attribute_list() {
+    let opts = with_dims(ImageDimensions::Attributes);
+    assert_eq!(
+        html_to_markdown_with(
+            "<img src=\"a.png\" alt=\"A\" width=\"100\" height=\"50\">",
+            &opts
+        ),
+        "![A](a.png){width=100 height=50}\n"
+    );
+    assert_eq!(
+        html_to_markdown_with("<img src=\"a.png\" width=\"50%\">", &opts),
+        "![](a.png){width=50%}\n"
+    );
+    assert_eq!(
+        html_to_markdown_with("<img src=\"a.png\" height=\"10 px\">", &opts),
+        "![](a.png){height=\"10 px\"}\n"
+    );
+    assert_eq!(
+        html_to_markdown_with("<img src=\"a.png\">", &opts),
+        "![](a.png)\n"
+    );
+}
+
+#[test]
+fn dimensions_as_html() {
+    let opts = with_dims(ImageDimensions::Html);
+    assert_eq!(
+        html_to_markdown_with(
+            "<p>See <img src=\"a b.png\" alt='say \"hi\"' width=\"100\"> here</p>",
+            &opts
+        ),
+        "See <img src=\"a b.png\" alt=\"say &quot;hi&quot;\" width=\"100\"> here\n"
+    );
+    // without dimensions, and inside links, the image stays Markdown
+    assert_eq!(
+        html_to_markdown_with("<img src=\"a.png\" alt=\"A\">", &opts),
+        "![A](a.png)\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(
+            "<a href=\"/\"><img src=\"a.png\" alt=\"A\" width=\"10\"></a>",
+            &opts
+        ),
+        "[![A](a.png)](/)\n"
+    );
+}
+
+#[test]
+fn preserve_keeps_img_tags() {
+    let opts = ConversionOptions::for_mode(ConversionMode::Preserve);
+    assert_eq!(opts.image_dimensions, ImageDimensions::Html);
+    assert_eq!(
+        html_to_markdown_with(
+            "<figure><img src=\"a.png\" width=\"100\" height=\"50\"><figcaption>Cap</figcaption></figure>",
+            &opts
+        ),
+        "<img src=\"a.png\" alt=\"Cap\" width=\"100\" height=\"50\">\n\n*Cap*\n"
+    );
+}
+
+#[test]
+fn dimensions_parse_from_str() {
+    for d in [
+        ImageDimensions::Drop,
+        ImageDimensions::Attributes,
+        ImageDimensions::Html,
+    ] {
+        assert_eq!(ImageDimensions::from_str(d.as_str()), Ok(d));
+        assert_eq!(d.to_string(), d.as_str());
+    }
+    assert!("pandoc".parse::<ImageDimensions>().is_err());
+}
+
+#[test]
+fn caption_without_visible_text_is_skipped() {
+    let opts = ConversionOptions::default().svg_policy(mdka::options::SvgPolicy::Skip);
+    assert_eq!(
+        html_to_markdown_with(
+            "<figure><img src=\"a.png\" alt=\"A\"><figcaption><svg><title>x</title></svg></figcaption></figure><p>Next</p>",
+            &opts
+        ),
+        "![A](a.png \"x\")\n\nNext\n"
+    );
+}
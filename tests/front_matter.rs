@@ -0,0 +1,144 @@
+//! Integration tests: front matter generation (`front_matter`,
+//! `front_matter_fields`)
+//! Covers: YAML and TOML output with the default fields, field selection and
+//! renaming, quoting and escaping, the date fallback, omission when nothing
+//! is known, the source path in file conversion, and parsing of field specs.
+
+mod common;
+use common::conv_with;
+use mdka::options::{ConversionOptions, FrontMatterField, FrontMatterFormat, MetadataField};
+
+const PAGE: &str = r#"<html><head>
+<title>Hello "World"</title>
+<meta name="description" content="A C:\path demo">
+<link rel="canonical" href="https://example.com/hello">
+<meta name="keywords" content="rust, markdown">
+<meta property="article:modified_time" content="2026-02-03">
+<meta name="author" content="Sam Lee">
+</head><body><p>Body</p></body></html>"#;
+
+fn with(format: FrontMatterFormat) -> ConversionOptions {
+    ConversionOptions::default().front_matter(format)
+}
+
+#[test]
+fn off_by_default() {
+    assert_eq!(conv_with(PAGE, &ConversionOptions::default()), "Body\n");
+}
+
+#[test]
+fn yaml_default_fields() {
+    assert_eq!(
+        conv_with(PAGE, &with(FrontMatterFormat::Yaml)),
+        "---\n\
+         title: \"Hello \\\"World\\\"\"\n\
+         description: \"A C:\\\\path demo\"\n\
+         canonical_url: \"https://example.com/hello\"\n\
+         date: \"2026-02-03\"\n\
+         tags: [\"rust\", \"markdown\"]\n\
+         ---\n\
+         \n\
+         Body\n"
+    );
+}
+
+#[test]
+fn toml_default_fields() {
+    let md = conv_with(PAGE, &with(FrontMatterFormat::Toml));
+    assert!(
+        md.starts_with("+++\ntitle = \"Hello \\\"World\\\"\"\n"),
+        "{md}"
+    );
+    assert!(
+        md.contains("tags = [\"rust\", \"markdown\"]\n+++\n\nBody\n"),
+        "{md}"
+    );
+}
+
+#[test]
+fn fields_selected_and_renamed() {
+    let opts = with(FrontMatterFormat::Yaml).front_matter_fields(vec![
+        FrontMatterField::new(MetadataField::Title),
+        FrontMatterField::new(MetadataField::Date).renamed("pubDate"),
+        FrontMatterField::new(MetadataField::Author),
+        FrontMatterField::new(MetadataField::Lang),
+        FrontMatterField::new(MetadataField::Tags).renamed("og:tags"),
+    ]);
+    assert_eq!(
+        conv_with(PAGE, &opts),
+        "---\n\
+         title: \"Hello \\\"World\\\"\"\n\
+         pubDate: \"2026-02-03\"\n\
+         author: \"Sam Lee\"\n\
+         \"og:tags\": [\"rust\", \"markdown\"]\n\
+         ---\n\
+         \n\
+         Body\n"
+    );
+}
+
+#[test]
+fn nothing_known_means_no_front_matter() {
+    let html = "<p>Body</p>";
+    assert_eq!(conv_with(html, &with(FrontMatterFormat::Yaml)), "Body\n");
+}
+
+#[test]
+fn front_matter_without_body() {
+    let html = "<title>Only</title>";
+    assert_eq!(
+        conv_with(html, &with(FrontMatterFormat::Toml)),
+        "+++\ntitle = \"Only\"\n+++\n"
+    );
+}
+
+#[test]
+fn source_path_written_by_file_conversion() {
+    let dir = std::env::temp_dir().join("mdka_front_matter_source");
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join("post.html");
+    std::fs::write(&src, PAGE).unwrap();
+
+    let opts = with(FrontMatterFormat::Yaml).front_matter_fields(vec![
+        FrontMatterField::new(MetadataField::Title),
+        FrontMatterField::new(MetadataField::Source),
+    ]);
+    let result = mdka::html_file_to_markdown_with(&src, None::<&str>, &opts).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+    let expected_source = format!(
+        "source: \"{}\"\n",
+        src.display().to_string().replace('\\', "\\\\")
+    );
+    assert!(md.contains(&expected_source), "{md}");
+    assert!(md.ends_with("---\n\nBody\n"), "{md}");
+
+    // string conversion has no source path
+    assert!(!conv_with(PAGE, &opts).contains("source:"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn document_api_includes_front_matter() {
+    let doc = mdka::html_to_markdown_document(PAGE, &with(FrontMatterFormat::Yaml));
+    assert!(doc.markdown.starts_with("---\ntitle:"), "{}", doc.markdown);
+    assert_eq!(doc.metadata.author.as_deref(), Some("Sam Lee"));
+}
+
+#[test]
+fn field_spec_parsing() {
+    let f: FrontMatterField = "date=pubDate".parse().unwrap();
+    assert_eq!(
+        f,
+        FrontMatterField::new(MetadataField::Date).renamed("pubDate")
+    );
+    assert_eq!(f.to_string(), "date=pubDate");
+    let f: FrontMatterField = "canonical_url".parse().unwrap();
+    assert_eq!(f.key, "canonical_url");
+    assert!("nope".parse::<FrontMatterField>().is_err());
+    assert!("title=".parse::<FrontMatterField>().is_err());
+    assert_eq!(
+        "TOML".parse::<FrontMatterFormat>(),
+        Ok(FrontMatterFormat::Toml)
+    );
+}
@@ -0,0 +1,90 @@
+//! Integration tests: hidden-content elimination
+//! Covers: each HTML / ARIA / inline-style signal `drop_hidden_content`
+//! recognises, the screen-reader-only carve-out (`keep_screen_reader_text`),
+//! and the per-mode defaults (on in Balanced/Minimal, sr-only kept in
+//! Semantic, off in Strict/Preserve).
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionMode, ConversionOptions};
+
+#[test]
+fn hidden_attribute_dropped() {
+    assert_eq!(
+        conv("<p>A</p><div hidden><p>Modal</p></div><p>B</p>"),
+        "A\n\nB\n"
+    );
+}
+
+#[test]
+fn hidden_until_found_kept() {
+    assert_eq!(
+        conv(r#"<p>A</p><div hidden="until-found"><p>Found</p></div>"#),
+        "A\n\nFound\n"
+    );
+}
+
+#[test]
+fn aria_hidden_true_dropped() {
+    assert_eq!(
+        conv(r#"<p>Menu<span aria-hidden="true"> ☰</span></p>"#),
+        "Menu\n"
+    );
+    assert_eq!(
+        conv(r#"<p>Menu<span aria-hidden="false"> ☰</span></p>"#),
+        "Menu ☰\n"
+    );
+}
+
+#[test]
+fn inline_display_none_and_visibility_hidden_dropped() {
+    assert_eq!(
+        conv(r#"<p>A</p><ul style="DISPLAY: none !important"><li>Mobile menu</li></ul><p>B</p>"#),
+        "A\n\nB\n"
+    );
+    assert_eq!(
+        conv(r#"<p>A<span style="color:red;visibility:hidden">x</span></p>"#),
+        "A\n"
+    );
+}
+
+#[test]
+fn closed_dialog_dropped_open_dialog_kept() {
+    assert_eq!(
+        conv("<p>A</p><dialog><p>Cookie consent</p></dialog>"),
+        "A\n"
+    );
+    assert_eq!(
+        conv("<p>A</p><dialog open><p>Shown</p></dialog>"),
+        "A\n\nShown\n"
+    );
+}
+
+#[test]
+fn screen_reader_only_dropped_by_default() {
+    let html = r##"<a class="sr-only" href="#main">Skip to content</a><p>Body <span class="visually-hidden">(opens in new tab)</span></p>"##;
+    assert_eq!(conv(html), "Body\n");
+}
+
+#[test]
+fn semantic_keeps_screen_reader_text_but_drops_hidden() {
+    let html = r#"<p>Body <span class="visually-hidden">(opens in new tab)</span></p><div hidden>Modal</div>"#;
+    let opts = ConversionOptions::for_mode(ConversionMode::Semantic);
+    assert_eq!(conv_with(html, &opts), "Body (opens in new tab)\n");
+}
+
+#[test]
+fn strict_and_preserve_keep_everything() {
+    let html = r#"<p>A</p><div hidden><p>B</p></div>"#;
+    for mode in [ConversionMode::Strict, ConversionMode::Preserve] {
+        let opts = ConversionOptions::for_mode(mode);
+        assert_eq!(conv_with(html, &opts), "A\n\nB\n", "{mode}");
+    }
+}
+
+#[test]
+fn drop_hidden_content_false_keeps_everything() {
+    let html = r#"<p>A</p><div aria-hidden="true"><p>B</p></div>"#;
+    let opts = ConversionOptions::default().drop_hidden_content(false);
+    assert_eq!(conv_with(html, &opts), "A\n\nB\n");
+}
@@ -0,0 +1,139 @@
+//! Integration tests: SPA hydration payloads (`recover_hydration_payloads`)
+//! Covers: Next.js `__NEXT_DATA__` HTML fields, `window.__NUXT__ = {...}`
+//! assignments, Contentful Rich Text and Sanity Portable Text, duplicate
+//! payloads collapsed, JavaScript that is not JSON ignored, the payload
+//! replacing only an empty DOM shell and otherwise appended after the DOM
+//! content unless the DOM already shows it, and the payload left alone when
+//! the option is off.
+
+use mdka::html_to_markdown_with;
+use mdka::options::ConversionOptions;
+
+fn opts() -> ConversionOptions {
+    ConversionOptions::default().recover_hydration_payloads(true)
+}
+
+const NEXT: &str = r#"<html><body><div id="__next"></div>
+<script id="__NEXT_DATA__" type="application/json">
+{"props": {"pageProps": {"post": {
+  "title": "Hello",
+  "bodyHtml": "<h2>Intro</h2><p>First <strong>post</strong>.</p>",
+  "slug": "hello"
+}}, "__N_SSG": true}, "page": "/posts/[slug]"}
+</script></body></html>"#;
+
+#[test]
+fn next_data_html_field() {
+    assert_eq!(
+        html_to_markdown_with(NEXT, &opts()),
+        "## Intro\n\nFirst **post**.\n"
+    );
+}
+
+#[test]
+fn off_by_default() {
+    assert_eq!(
+        html_to_markdown_with(NEXT, &ConversionOptions::default()),
+        "<a id=\"__next\"></a>\n"
+    );
+}
+
+// History: the payload used to replace any DOM body under 200 visible
+// characters, discarding short server-rendered pages (and most CJK ones).
+// It now replaces only an empty shell; otherwise it is appended.
+#[test]
+fn short_dom_body_is_kept_and_payload_appended() {
+    let html = r#"<p>Server rendered body paragraph.</p>
+      <script id="__NEXT_DATA__" type="application/json">{"props": {"html": "<p>dup</p>"}}</script>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "Server rendered body paragraph.\n\ndup\n"
+    );
+    let html = NEXT.replace(
+        "<div id=\"__next\"></div>",
+        "<div id=\"__next\"><p>Loading…</p></div>",
+    );
+    assert_eq!(
+        html_to_markdown_with(&html, &opts()),
+        "<a id=\"__next\"></a>\n\nLoading…\n\n## Intro\n\nFirst **post**.\n"
+    );
+    let html = r#"<article><h1>見出し</h1><p>本文の段落です。</p></article>
+      <script type="application/json">{"body": "<p>続きの段落です。</p>"}</script>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "# 見出し\n\n本文の段落です。\n\n続きの段落です。\n"
+    );
+}
+
+#[test]
+fn payload_already_shown_by_the_dom_is_not_repeated() {
+    let html = NEXT.replace(
+        "<div id=\"__next\"></div>",
+        "<div id=\"__next\"><h2>Intro</h2><p>First <strong>post</strong>.</p></div>",
+    );
+    assert_eq!(
+        html_to_markdown_with(&html, &opts()),
+        "<a id=\"__next\"></a>\n\n## Intro\n\nFirst **post**.\n"
+    );
+}
+
+#[test]
+fn nuxt_assignment_and_duplicates() {
+    let html = r#"<div id="__nuxt"></div>
+      <script>window.__NUXT__ = {"data": [{"page": {"content": "<p>Same body</p>"}}],
+        "state": {"cache": {"content": "<p>Same body</p>"}}};</script>
+      <script>window.__NUXT__=(function(a){return {content: a}}("<p>Not JSON</p>"));</script>"#;
+    assert_eq!(html_to_markdown_with(html, &opts()), "Same body\n");
+}
+
+#[test]
+fn contentful_rich_text() {
+    let html = r#"<div id="root"></div><script type="application/json">
+      {"entry": {"body": {"nodeType": "document", "data": {}, "content": [
+        {"nodeType": "heading-2", "content": [{"nodeType": "text", "value": "Setup", "marks": []}]},
+        {"nodeType": "paragraph", "content": [
+          {"nodeType": "text", "value": "Run ", "marks": []},
+          {"nodeType": "text", "value": "npm i", "marks": [{"type": "code"}]},
+          {"nodeType": "text", "value": " then read ", "marks": []},
+          {"nodeType": "hyperlink", "data": {"uri": "https://example.com/docs"},
+           "content": [{"nodeType": "text", "value": "the docs", "marks": []}]}
+        ]},
+        {"nodeType": "unordered-list", "content": [
+          {"nodeType": "list-item", "content": [{"nodeType": "paragraph", "content": [
+            {"nodeType": "text", "value": "a < b", "marks": [{"type": "bold"}]}]}]}
+        ]}
+      ]}}}</script>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "## Setup\n\nRun `npm i` then read [the docs](https://example.com/docs)\n\n- **a < b**\n"
+    );
+}
+
+#[test]
+fn portable_text() {
+    let html = r#"<div id="app"></div><script>
+      self.__INITIAL_STATE__ = {"article": {"body": [
+        {"_type": "block", "style": "h1", "markDefs": [], "children": [{"_type": "span", "text": "Title", "marks": []}]},
+        {"_type": "block", "style": "normal", "markDefs": [{"_key": "l1", "_type": "link", "href": "/more"}],
+         "children": [{"_type": "span", "text": "Read ", "marks": []},
+                      {"_type": "span", "text": "more", "marks": ["em", "l1"]}]},
+        {"_type": "block", "listItem": "number", "markDefs": [], "children": [{"_type": "span", "text": "One", "marks": []}]},
+        {"_type": "block", "listItem": "number", "markDefs": [], "children": [{"_type": "span", "text": "Two", "marks": []}]},
+        {"_type": "image", "asset": {"_ref": "image-1"}}
+      ]}};
+    </script>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "# Title\n\nRead *[more](/more)*\n\n1. One\n2. Two\n"
+    );
+}
+
+#[test]
+fn plain_strings_ignored() {
+    let html = r#"<div id="root"></div><script type="application/json">
+      {"title": "Only <3 plain text", "tags": ["a", "b"]}</script>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "<a id=\"root\"></a>\n"
+    );
+}
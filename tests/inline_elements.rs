@@ -108,6 +108,18 @@ fn link_text_with_inline_formatting() {
     assert!(md.contains("https://example.com"), "got: {md}");
 }
 
+#[test]
+fn link_text_keeps_emphasis_markers_and_spaces() {
+    assert_eq!(
+        conv(r#"<p><a href="https://example.com"><strong>bold link</strong></a></p>"#),
+        "[**bold link**](https://example.com)\n"
+    );
+    assert_eq!(
+        conv(r#"<p><a href="https://example.com">a <em>b</em> c</a></p>"#),
+        "[a *b* c](https://example.com)\n"
+    );
+}
+
 // ─── Images ───────────────────────────────────────────────────────────────
 
 #[test]
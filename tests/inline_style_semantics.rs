@@ -0,0 +1,133 @@
+//! Integration tests: inline `style` semantics on `<span>`
+//! Covers: bold / italic / strikethrough / monospace mapping, combined
+//! styles, nested spans, spans inside links, whitespace-only spans, code
+//! blocks, deep nesting of colour-only spans, and the per-mode default (off
+//! in Minimal).
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionMode, ConversionOptions};
+
+#[test]
+fn font_weight_maps_to_strong() {
+    assert_eq!(
+        conv(r#"<p>a <span style="font-weight:700">bold</span> b</p>"#),
+        "a **bold** b\n"
+    );
+    assert_eq!(
+        conv(r#"<p><span style="font-weight: bold">bold</span></p>"#),
+        "**bold**\n"
+    );
+    assert_eq!(
+        conv(r#"<p><span style="font-weight:400">plain</span></p>"#),
+        "plain\n"
+    );
+}
+
+#[test]
+fn font_style_maps_to_em() {
+    assert_eq!(
+        conv(r#"<p><span style="font-style:italic">it</span></p>"#),
+        "*it*\n"
+    );
+}
+
+#[test]
+fn line_through_maps_to_strikethrough() {
+    assert_eq!(
+        conv(r#"<p><span style="text-decoration:line-through">old</span></p>"#),
+        "~~old~~\n"
+    );
+}
+
+#[test]
+fn monospace_family_maps_to_code() {
+    assert_eq!(
+        conv(r#"<p>run <span style="font-family:'Courier New',monospace">ls</span></p>"#),
+        "run `ls`\n"
+    );
+}
+
+#[test]
+fn combined_styles_nest_in_order() {
+    assert_eq!(
+        conv(
+            r#"<p><span style="font-weight:700;font-style:italic;text-decoration:line-through">x</span></p>"#
+        ),
+        "***~~x~~***\n"
+    );
+}
+
+#[test]
+fn whitespace_only_span_emits_no_markers() {
+    assert_eq!(
+        conv(r#"<p>a<span style="font-weight:700"> </span>b</p>"#),
+        "a b\n"
+    );
+}
+
+#[test]
+fn google_docs_runs() {
+    let html = concat!(
+        r#"<p dir="ltr"><span style="font-size:11pt;font-family:Arial;font-weight:400">Plain, </span>"#,
+        r#"<span style="font-size:11pt;font-family:Arial;font-weight:700">bold</span>"#,
+        r#"<span style="font-size:11pt;font-family:Arial;font-weight:400"> and </span>"#,
+        r#"<span style="font-size:11pt;font-family:Arial;font-style:italic;font-weight:400">italic</span></p>"#,
+    );
+    assert_eq!(conv(html), "Plain, **bold** and *italic*\n");
+}
+
+#[test]
+fn disabled_in_minimal_and_by_flag() {
+    let html = r#"<p><span style="font-weight:700">bold</span></p>"#;
+    let minimal = ConversionOptions::for_mode(ConversionMode::Minimal);
+    assert_eq!(conv_with(html, &minimal), "bold\n");
+    let off = ConversionOptions::default().inline_style_semantics(false);
+    assert_eq!(conv_with(html, &off), "bold\n");
+}
+
+#[test]
+fn ignored_inside_code_block() {
+    assert_eq!(
+        conv(r#"<pre><code><span style="font-weight:700">fn</span> main</code></pre>"#),
+        "```\nfn main\n```\n"
+    );
+}
+
+#[test]
+fn nested_spans_close_in_order() {
+    assert_eq!(
+        conv(
+            r#"<p><span style="font-weight:bold">a <span style="color:red"><span style="font-style:italic">b</span></span> c</span></p>"#
+        ),
+        "**a *b* c**\n"
+    );
+}
+
+#[test]
+fn styled_span_inside_link_stays_in_link_text() {
+    assert_eq!(
+        conv(r#"<p><a href="https://x.test/"><span style="font-weight:bold">Link</span></a></p>"#),
+        "[**Link**](https://x.test/)\n"
+    );
+    assert_eq!(
+        conv(
+            r#"<p>a <a href="https://x.test/">x <span style="font-style:italic;text-decoration:line-through">y </span>z</a> b</p>"#
+        ),
+        "a [x *~~y~~* z](https://x.test/) b\n"
+    );
+}
+
+#[test]
+fn deeply_nested_plain_spans_stay_linear() {
+    // colour-only spans imply no emphasis, so their text is never scanned
+    let n = 20_000;
+    let html = format!(
+        "<p>{}x{}</p>",
+        r#"<span style="color:red">"#.repeat(n),
+        "</span>".repeat(n)
+    );
+    let start = std::time::Instant::now();
+    assert_eq!(conv(&html), "x\n");
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+}
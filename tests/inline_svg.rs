@@ -0,0 +1,139 @@
+//! Integration tests: inline `<svg>` handling via `svg_policy`
+//! Covers: every `SvgPolicy` variant, accessible-name lookup (`aria-label`
+//! before `<title>`), icon-only links, and `File` writing an asset during
+//! file conversion while falling back to a data URI for string conversion.
+
+mod common;
+use common::conv_with;
+use mdka::options::{ConversionOptions, SvgPolicy};
+
+const ICON_LINK: &str = r#"<p><a href="https://github.com/x"><svg viewBox="0 0 16 16"><title>GitHub</title><path d="M0 0h16v16H0z"/></svg></a></p>"#;
+
+fn with_policy(p: SvgPolicy) -> ConversionOptions {
+    ConversionOptions::default().svg_policy(p)
+}
+
+#[test]
+fn skip_emits_nothing() {
+    assert_eq!(
+        conv_with(ICON_LINK, &with_policy(SvgPolicy::Skip)),
+        "[](https://github.com/x)\n"
+    );
+}
+
+#[test]
+fn alt_text_names_icon_link() {
+    assert_eq!(
+        conv_with(ICON_LINK, &with_policy(SvgPolicy::AltText)),
+        "[GitHub](https://github.com/x)\n"
+    );
+}
+
+#[test]
+fn alt_text_is_the_default() {
+    assert_eq!(
+        mdka::html_to_markdown(ICON_LINK),
+        "[GitHub](https://github.com/x)\n"
+    );
+}
+
+#[test]
+fn aria_label_takes_precedence_over_title() {
+    let html = r#"<p>Go <svg aria-label="  Home   page "><title>ignored</title></svg></p>"#;
+    assert_eq!(
+        conv_with(html, &with_policy(SvgPolicy::AltText)),
+        "Go Home page\n"
+    );
+}
+
+#[test]
+fn unlabelled_svg_emits_nothing_under_alt_text() {
+    assert_eq!(
+        conv_with(
+            "<p>A<svg><circle/></svg>B</p>",
+            &with_policy(SvgPolicy::AltText)
+        ),
+        "AB\n"
+    );
+}
+
+#[test]
+fn data_uri_embeds_standalone_svg_image() {
+    let md = conv_with(ICON_LINK, &with_policy(SvgPolicy::DataUri));
+    assert!(
+        md.starts_with("[![GitHub](data:image/svg+xml;base64,"),
+        "got: {md}"
+    );
+    assert!(md.ends_with(")](https://github.com/x)\n"), "got: {md}");
+}
+
+#[test]
+fn file_falls_back_to_data_uri_for_string_conversion() {
+    assert_eq!(
+        conv_with(ICON_LINK, &with_policy(SvgPolicy::File)),
+        conv_with(ICON_LINK, &with_policy(SvgPolicy::DataUri))
+    );
+}
+
+#[test]
+fn raw_keeps_markup_outside_links() {
+    let html = r#"<p>Icon <svg width="1"><circle r="1"/></svg> here</p>"#;
+    assert_eq!(
+        conv_with(html, &with_policy(SvgPolicy::Raw)),
+        "Icon <svg width=\"1\"><circle r=\"1\"></circle></svg> here\n"
+    );
+}
+
+#[test]
+fn raw_falls_back_to_name_inside_links() {
+    assert_eq!(
+        conv_with(ICON_LINK, &with_policy(SvgPolicy::Raw)),
+        "[GitHub](https://github.com/x)\n"
+    );
+}
+
+#[test]
+fn policy_parses_from_str() {
+    for p in [
+        SvgPolicy::Skip,
+        SvgPolicy::AltText,
+        SvgPolicy::File,
+        SvgPolicy::DataUri,
+        SvgPolicy::Raw,
+    ] {
+        assert_eq!(p.as_str().parse::<SvgPolicy>(), Ok(p));
+    }
+    assert!("inline".parse::<SvgPolicy>().is_err());
+}
+
+#[test]
+fn file_policy_writes_asset_for_file_conversion() {
+    let dir = std::env::temp_dir().join("mdka_test_svg_file");
+    let out = dir.join("out");
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join("icons.html");
+    std::fs::write(&src, format!("{ICON_LINK}{ICON_LINK}")).unwrap();
+
+    let opts = with_policy(SvgPolicy::File);
+    let result = mdka::html_file_to_markdown_with(&src, Some(&out), &opts).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    let href = md
+        .split("](")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .unwrap();
+    assert!(
+        href.starts_with("assets/") && href.ends_with(".svg"),
+        "got: {md}"
+    );
+    let svg = std::fs::read_to_string(out.join(href)).unwrap();
+    assert!(
+        svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#),
+        "got: {svg}"
+    );
+    // identical SVGs are written once
+    assert_eq!(std::fs::read_dir(out.join("assets")).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,101 @@
+//! Integration tests: link and image destination escaping
+//! Covers: destinations with spaces wrapped in `<…>`, unbalanced parentheses
+//! escaped while balanced ones stay raw, `<`/`>` and backslashes escaped,
+//! control characters percent-encoded, titles quoted with `"` escaped,
+//! brackets escaped in link text and alt text, the same rules for reference
+//! definitions. Expected outputs follow the link examples of the CommonMark
+//! spec ("Links" and "Images" sections).
+
+use mdka::html_to_markdown;
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionOptions, LinkStyle};
+
+fn link(href: &str) -> String {
+    html_to_markdown(&format!("<a href=\"{href}\">link</a>"))
+}
+
+#[test]
+fn destination_with_space_uses_angle_brackets() {
+    // spec: [link](</my uri>)
+    assert_eq!(link("/my uri"), "[link](</my uri>)\n");
+}
+
+#[test]
+fn balanced_parentheses_stay_raw() {
+    // spec: [link](foo(and(bar)))
+    assert_eq!(link("foo(and(bar))"), "[link](foo(and(bar)))\n");
+}
+
+#[test]
+fn unbalanced_parentheses_are_escaped() {
+    // spec: [link](foo\(and\(bar\))
+    assert_eq!(link("foo(and(bar)"), "[link](foo\\(and\\(bar\\))\n");
+    assert_eq!(link("foo)bar("), "[link](foo\\)bar\\()\n");
+}
+
+#[test]
+fn angle_brackets_are_escaped() {
+    // spec: [a](<b)c>) — and a bare `<` must not open a pointy form
+    assert_eq!(link("&lt;b)c&gt;"), "[link](\\<b\\)c\\>)\n");
+    assert_eq!(link("a&lt;b"), "[link](a\\<b)\n");
+}
+
+#[test]
+fn backslash_before_punctuation_is_escaped() {
+    // spec: [link](foo\)\:) — a literal backslash must survive
+    assert_eq!(link("foo\\)"), "[link](foo\\\\\\))\n");
+    assert_eq!(link("C:\\dir\\file"), "[link](C:\\dir\\file)\n");
+}
+
+#[test]
+fn control_characters_are_percent_encoded() {
+    assert_eq!(
+        html_to_markdown("<a href=\"/a&#10;b&#9;c\">link</a>"),
+        "[link](/a%0Ab%09c)\n"
+    );
+    // surrounding whitespace is not part of the destination
+    assert_eq!(link("  /path  "), "[link](/path)\n");
+}
+
+#[test]
+fn title_quotes_are_escaped() {
+    // spec: [link](/url "title \"&quot;")
+    assert_eq!(
+        html_to_markdown("<a href=\"/url\" title='title \"&quot;'>link</a>"),
+        "[link](/url \"title \\\"\\\"\")\n"
+    );
+    assert_eq!(
+        html_to_markdown("<a href=\"/url\" title=\"a\nb\">link</a>"),
+        "[link](/url \"a b\")\n"
+    );
+}
+
+#[test]
+fn brackets_in_link_text_are_escaped() {
+    // spec: [link \[bar](/uri)
+    assert_eq!(
+        html_to_markdown("<a href=\"/uri\">link [bar</a>"),
+        "[link \\[bar](/uri)\n"
+    );
+}
+
+#[test]
+fn image_alt_and_destination_are_escaped() {
+    assert_eq!(
+        html_to_markdown("<img src=\"/my image.png\" alt=\"a ]b[ c\" title='say \"hi\"'>"),
+        "![a \\]b\\[ c](</my image.png> \"say \\\"hi\\\"\")\n"
+    );
+    assert_eq!(
+        html_to_markdown("<img src=\"/a(b.png\" alt=\"x\">"),
+        "![x](/a\\(b.png)\n"
+    );
+}
+
+#[test]
+fn reference_definitions_use_the_same_rules() {
+    let opts = ConversionOptions::default().link_style(LinkStyle::Reference);
+    assert_eq!(
+        html_to_markdown_with("<p><a href=\"/my uri\" title='a \"b\"'>x</a></p>", &opts),
+        "[x][1]\n\n[1]: </my uri> \"a \\\"b\\\"\"\n"
+    );
+}
@@ -0,0 +1,108 @@
+//! Integration tests: link policy (`link_policy`)
+//! Covers: keeping, text-only and dropping links, external-only links (with
+//! and without a base URL on the same host), dead links (`javascript:`,
+//! empty, `#`, no `href`) stripped to text, autolinks for URL and email
+//! text, the `Minimal` defaults, and parsing of the filter names.
+
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionMode, ConversionOptions, LinkFilter, LinkPolicy};
+
+const HTML: &str = r#"<p>Read <a href="https://other.example/a">the paper</a>, the
+<a href="/guide">guide</a> and <a href="//cdn.example/x">mirror</a>.</p>"#;
+
+fn policy(policy: LinkPolicy) -> ConversionOptions {
+    ConversionOptions::default().link_policy(policy)
+}
+
+#[test]
+fn keep_by_default() {
+    assert_eq!(
+        html_to_markdown_with(HTML, &ConversionOptions::default()),
+        "Read [the paper](https://other.example/a), the [guide](/guide) and [mirror](//cdn.example/x).\n"
+    );
+}
+
+#[test]
+fn text_only_and_drop() {
+    assert_eq!(
+        html_to_markdown_with(
+            HTML,
+            &policy(LinkPolicy::default().filter(LinkFilter::TextOnly))
+        ),
+        "Read the paper, the guide and mirror.\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(
+            HTML,
+            &policy(LinkPolicy::default().filter(LinkFilter::Drop))
+        ),
+        "Read , the and .\n"
+    );
+}
+
+#[test]
+fn external_only() {
+    let opts = policy(LinkPolicy::default().filter(LinkFilter::ExternalOnly));
+    assert_eq!(
+        html_to_markdown_with(HTML, &opts),
+        "Read [the paper](https://other.example/a), the guide and [mirror](//cdn.example/x).\n"
+    );
+    // with a base URL, links resolved onto the same host are internal
+    let html = r#"<a href="/guide">guide</a> <a href="https://example.com/faq">faq</a>
+      <a href="mailto:team@example.com">mail</a>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts.base_url("https://EXAMPLE.com/docs/")),
+        "guide faq [mail](mailto:team@example.com)\n"
+    );
+}
+
+#[test]
+fn dead_links_stripped() {
+    let html = r##"<p><a href="javascript:void(0)">Menu</a> <a href="#">Top</a>
+      <a href="">Empty</a> <a name="x">Named</a> <a href="#install">Install</a></p>"##;
+    assert_eq!(
+        html_to_markdown_with(html, &ConversionOptions::default().preserve_ids(false)),
+        "[Menu](javascript:void(0)) [Top](#) [Empty]() [Named]() [Install](#install)\n"
+    );
+    assert_eq!(
+        html_to_markdown_with(
+            html,
+            &policy(LinkPolicy::default().strip_dead(true)).preserve_ids(false)
+        ),
+        "Menu Top Empty Named [Install](#install)\n"
+    );
+}
+
+#[test]
+fn autolinks() {
+    let html = r#"<p><a href="https://example.com/a_b">https://example.com/a_b</a>
+      <a href="mailto:team@example.com">team@example.com</a>
+      <a href="https://example.com/">Example</a> <a href="/x">/x</a></p>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &policy(LinkPolicy::default().autolink(true))),
+        "<https://example.com/a_b> <team@example.com> [Example](https://example.com/) [/x](/x)\n"
+    );
+}
+
+#[test]
+fn minimal_keeps_external_links_only() {
+    let html = r#"<p><a href="/guide">Guide</a>, <a href="https://example.com">https://example.com</a>
+      and <a href="javascript:go()">more</a>.</p>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &ConversionOptions::for_mode(ConversionMode::Minimal)),
+        "Guide, <https://example.com> and more.\n"
+    );
+}
+
+#[test]
+fn filter_names() {
+    for f in [
+        LinkFilter::Keep,
+        LinkFilter::ExternalOnly,
+        LinkFilter::TextOnly,
+        LinkFilter::Drop,
+    ] {
+        assert_eq!(f.as_str().parse(), Ok(f));
+    }
+    assert!("external".parse::<LinkFilter>().is_err());
+}
@@ -0,0 +1,108 @@
+//! Integration tests: link output style (`link_style`)
+//! Covers: inline by default, numbered reference links with definitions at
+//! the end, identical URLs sharing one definition, definitions written per
+//! top-level section, footnote-style links with plain-text bodies (autolinks
+//! for absolute URLs, escaped text otherwise), links with an empty `href`
+//! kept inline, and parsing of the style names.
+
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionOptions, LinkStyle};
+
+const HTML: &str = r#"<h1>Guide</h1>
+<p>See <a href="https://example.com/a" title="Alpha">the docs</a> and
+<a href="https://example.com/b">more</a>.</p>
+<h2>Setup</h2>
+<p>Again <a href="https://example.com/a">the docs</a>, then <a href="/c">C</a>.</p>
+<h3>Details</h3>
+<p><a href="/d">D</a></p>"#;
+
+fn style(style: LinkStyle) -> ConversionOptions {
+    ConversionOptions::default().link_style(style)
+}
+
+#[test]
+fn inline_by_default() {
+    let md = html_to_markdown_with(HTML, &ConversionOptions::default());
+    assert!(
+        md.contains(
+            "See [the docs](https://example.com/a \"Alpha\") and [more](https://example.com/b)."
+        ),
+        "{md}"
+    );
+}
+
+#[test]
+fn reference_definitions_at_end() {
+    assert_eq!(
+        html_to_markdown_with(HTML, &style(LinkStyle::Reference)),
+        "# Guide\n\nSee [the docs][1] and [more][2].\n\n## Setup\n\n\
+         Again [the docs][1], then [C][3].\n\n### Details\n\n[D][4]\n\n\
+         [1]: https://example.com/a \"Alpha\"\n[2]: https://example.com/b\n[3]: /c\n[4]: /d\n"
+    );
+}
+
+#[test]
+fn reference_definitions_per_section() {
+    // numbering continues across sections; a URL defined in an earlier
+    // section is not defined again
+    assert_eq!(
+        html_to_markdown_with(HTML, &style(LinkStyle::ReferencePerSection)),
+        "# Guide\n\nSee [the docs][1] and [more][2].\n\n\
+         [1]: https://example.com/a \"Alpha\"\n[2]: https://example.com/b\n\n## Setup\n\n\
+         Again [the docs][1], then [C][3].\n\n### Details\n\n[D][4]\n\n[3]: /c\n[4]: /d\n"
+    );
+}
+
+// History: footnote bodies used the link-definition syntax, so an absolute
+// URL was written bare and `[^1]: <a b.html> "T"` parsed as raw HTML.
+#[test]
+fn footnotes() {
+    assert_eq!(
+        html_to_markdown_with(
+            r#"<p>Read <a href="https://example.com/a">this</a> and <a href="https://example.com/a">that</a>.</p>"#,
+            &style(LinkStyle::Footnote)
+        ),
+        "Read this[^1] and that[^1].\n\n[^1]: <https://example.com/a>\n"
+    );
+}
+
+#[test]
+fn footnote_bodies_are_plain_text() {
+    let footnote = |html| html_to_markdown_with(html, &style(LinkStyle::Footnote));
+    assert_eq!(
+        footnote(r#"<a href="a b.html" title="T">x</a>"#),
+        "x[^1]\n\n[^1]: a%20b.html — T\n"
+    );
+    assert_eq!(
+        footnote(r#"<a href="https://example.com/a b?q=<x>" title="Say &quot;*hi*&quot;">x</a>"#),
+        "x[^1]\n\n[^1]: <https://example.com/a%20b?q=%3Cx%3E> — Say \"\\*hi\\*\"\n"
+    );
+    assert_eq!(
+        footnote(r#"<a href="/docs/my_page*.html">x</a>"#),
+        "x[^1]\n\n[^1]: /docs/my\\_page\\*.html\n"
+    );
+}
+
+#[test]
+fn empty_href_stays_inline() {
+    assert_eq!(
+        html_to_markdown_with(
+            r#"<p><a>none</a> <a href="/x">x</a></p>"#,
+            &style(LinkStyle::Reference)
+        ),
+        "[none]() [x][1]\n\n[1]: /x\n"
+    );
+}
+
+#[test]
+fn style_names() {
+    for s in [
+        LinkStyle::Inline,
+        LinkStyle::Reference,
+        LinkStyle::ReferencePerSection,
+        LinkStyle::Footnote,
+    ] {
+        assert_eq!(s.as_str().parse(), Ok(s));
+    }
+    assert!("numbered".parse::<LinkStyle>().is_err());
+}
@@ -0,0 +1,240 @@
+//! Integration tests: local asset collection (`collect_local_assets`)
+//! Covers: images referenced by relative `src` copied into `assets/` next to
+//! the output and the references rewritten, deduplication by content,
+//! percent-encoded paths and `srcset`, remote / root-relative / missing /
+//! non-image references left alone, references leaving the input's
+//! directory (or the batch's site root) refused, precedence over
+//! `base_url`, the bulk and batch APIs, and off by default and for string
+//! conversion.
+
+use std::path::{Path, PathBuf};
+
+use mdka::options::ConversionOptions;
+use mdka::{BatchOptions, html_files_to_markdown_batch};
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nfake png";
+const JPEG: &[u8] = b"\xff\xd8\xff\xe0fake jpeg";
+
+fn opts() -> ConversionOptions {
+    ConversionOptions::default().collect_local_assets(true)
+}
+
+/// `dir/site/page.html` with images in `dir/site/img` and, outside the
+/// page's directory, `dir/shared`.
+fn write_site(dir: &Path, html: &str) -> PathBuf {
+    let _ = std::fs::remove_dir_all(dir);
+    let site = dir.join("site");
+    std::fs::create_dir_all(site.join("img")).unwrap();
+    std::fs::create_dir_all(dir.join("shared")).unwrap();
+    std::fs::write(site.join("img/a.png"), PNG).unwrap();
+    std::fs::write(site.join("img/copy of a.png"), PNG).unwrap();
+    std::fs::write(site.join("img/b.JPG"), JPEG).unwrap();
+    std::fs::write(dir.join("shared/b.JPG"), JPEG).unwrap();
+    std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+    let page = site.join("page.html");
+    std::fs::write(&page, html).unwrap();
+    page
+}
+
+/// The image destinations of `md`, in order.
+fn image_srcs(md: &str) -> Vec<String> {
+    md.split("](")
+        .skip(1)
+        .map(|rest| rest.split(')').next().unwrap().to_string())
+        .collect()
+}
+
+fn asset_count(out: &Path) -> usize {
+    std::fs::read_dir(out.join("assets")).map_or(0, |d| d.count())
+}
+
+#[test]
+fn relative_images_copied_and_rewritten() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_copy");
+    let page = write_site(
+        &dir,
+        "<img src=\"img/a.png\" alt=\"A\"> <img src=\"img/sub/../b.JPG\" alt=\"B\">",
+    );
+    std::fs::create_dir_all(dir.join("site/img/sub")).unwrap();
+    let out = dir.join("out");
+    let result = mdka::html_file_to_markdown_with(&page, Some(&out), &opts()).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    let srcs = image_srcs(&md);
+    assert_eq!(srcs.len(), 2, "got: {md}");
+    assert!(
+        srcs[0].starts_with("assets/") && srcs[0].ends_with(".png"),
+        "got: {md}"
+    );
+    assert!(srcs[1].ends_with(".jpg"), "got: {md}");
+    assert_eq!(std::fs::read(out.join(&srcs[0])).unwrap(), PNG);
+    assert_eq!(std::fs::read(out.join(&srcs[1])).unwrap(), JPEG);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn identical_content_copied_once() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_dedup");
+    let page = write_site(
+        &dir,
+        "<img src=\"img/a.png\"><img src=\"./img/copy%20of%20a.png?v=2\"><img srcset=\"img/a.png 2x\">",
+    );
+    let out = dir.join("out");
+    let result = mdka::html_file_to_markdown_with(&page, Some(&out), &opts()).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    let srcs = image_srcs(&md);
+    assert_eq!(srcs.len(), 3, "got: {md}");
+    assert!(srcs.iter().all(|s| *s == srcs[0]), "got: {md}");
+    assert_eq!(asset_count(&out), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn other_references_left_alone() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_untouched");
+    let page = write_site(
+        &dir,
+        "<img src=\"https://example.com/a.png\"><img src=\"/img/a.png\">\
+         <img src=\"img/missing.png\"><img src=\"../secret.txt\">",
+    );
+    let out = dir.join("out");
+    let result = mdka::html_file_to_markdown_with(&page, Some(&out), &opts()).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    assert_eq!(
+        image_srcs(&md),
+        [
+            "https://example.com/a.png",
+            "/img/a.png",
+            "img/missing.png",
+            "../secret.txt"
+        ]
+    );
+    assert!(!out.join("assets").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn references_leaving_the_input_directory_are_refused() {
+    // `../` used to be followed anywhere; only the input's directory is
+    // read now, whatever the spelling of the path
+    let dir = std::env::temp_dir().join("mdka_local_assets_traversal");
+    let page = write_site(&dir, "");
+    std::fs::write(dir.join("private.png"), PNG).unwrap();
+    let absolute = dir.join("private.png");
+    let html = format!(
+        "<img src=\"../shared/b.JPG\"><img src=\"../private.png\">\
+         <img src=\"%2e%2e/private.png\"><img src=\"img/../../private.png\">\
+         <img src=\"%2F{}\"><img src=\"img/link.png\">",
+        absolute.display().to_string().trim_start_matches('/')
+    );
+    std::fs::write(&page, &html).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&absolute, dir.join("site/img/link.png")).unwrap();
+    let out = dir.join("out");
+    let result = mdka::html_file_to_markdown_with(&page, Some(&out), &opts()).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    assert!(
+        image_srcs(&md).iter().all(|s| !s.starts_with("assets/")),
+        "got: {md}"
+    );
+    assert!(!out.join("assets").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn batch_reads_images_under_the_site_root() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_batch");
+    let _ = std::fs::remove_dir_all(&dir);
+    let site = dir.join("site");
+    for sub in ["a", "b", "img"] {
+        std::fs::create_dir_all(site.join(sub)).unwrap();
+    }
+    std::fs::write(site.join("img/logo.png"), PNG).unwrap();
+    std::fs::write(dir.join("private.png"), JPEG).unwrap();
+    let first = site.join("a/first.html");
+    let second = site.join("b/second.html");
+    std::fs::write(
+        &first,
+        "<img src=\"../img/logo.png\"><img src=\"../../private.png\">",
+    )
+    .unwrap();
+    std::fs::write(&second, "<p>Second</p>").unwrap();
+    let out = dir.join("out");
+
+    for batch in [
+        BatchOptions::default(),
+        BatchOptions::default().site_links(true),
+    ] {
+        let _ = std::fs::remove_dir_all(&out);
+        let paths = [first.clone(), second.clone()];
+        let report = html_files_to_markdown_batch(&paths, &out, &opts(), &batch);
+        let dest = report.files[0].1.as_ref().unwrap();
+        let md = std::fs::read_to_string(dest).unwrap();
+        let srcs = image_srcs(&md);
+        assert!(srcs[0].starts_with("assets/"), "got: {md}");
+        assert_eq!(srcs[1], "../../private.png");
+        assert_eq!(asset_count(dest.parent().unwrap()), 1);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn local_file_wins_over_base_url() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_base");
+    let page = write_site(&dir, "<img src=\"img/a.png\"><img src=\"img/missing.png\">");
+    let out = dir.join("out");
+    let opts = opts().base_url("https://example.com/docs/");
+    let result = mdka::html_file_to_markdown_with(&page, Some(&out), &opts).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+
+    let srcs = image_srcs(&md);
+    assert!(srcs[0].starts_with("assets/"), "got: {md}");
+    assert_eq!(srcs[1], "https://example.com/docs/img/missing.png");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn bulk_conversion_collects_assets() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_bulk");
+    let page = write_site(&dir, "<img src=\"img/a.png\">");
+    let other = dir.join("site/other.html");
+    std::fs::write(&other, "<img src=\"img/b.JPG\">").unwrap();
+    let out = dir.join("out");
+
+    let paths = [page, other];
+    let results = mdka::html_files_to_markdown_with(&paths, &out, &opts());
+    for (_, result) in &results {
+        let md = std::fs::read_to_string(result.as_ref().unwrap()).unwrap();
+        assert!(out.join(&image_srcs(&md)[0]).is_file(), "got: {md}");
+    }
+    assert_eq!(asset_count(&out), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn off_by_default_and_for_strings() {
+    let dir = std::env::temp_dir().join("mdka_local_assets_off");
+    let page = write_site(&dir, "<img src=\"img/a.png\">");
+    let out = dir.join("out");
+    let result = mdka::html_file_to_markdown(&page, Some(&out)).unwrap();
+    let md = std::fs::read_to_string(&result.dest).unwrap();
+    assert_eq!(md, "![](img/a.png)\n");
+    assert!(!out.join("assets").exists());
+
+    assert_eq!(
+        mdka::html_to_markdown_with("<img src=\"img/a.png\">", &opts()),
+        "![](img/a.png)\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
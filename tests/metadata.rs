@@ -0,0 +1,121 @@
+//! Integration tests: document metadata (`html_to_markdown_document`,
+//! `extract_metadata`)
+//! Covers: title / description / canonical / lang / author / dates from
+//! standard meta tags, OpenGraph and Twitter fallbacks, keyword splitting,
+//! `<svg><title>` not mistaken for the document title, the Markdown being
+//! identical to `html_to_markdown_with`, and documents without a head.
+
+use mdka::options::{ConversionMode, ConversionOptions};
+use mdka::scraper::Html;
+use mdka::{extract_metadata, html_to_markdown_document, html_to_markdown_with};
+
+const ARTICLE: &str = r#"<!doctype html>
+<html lang="en-GB">
+<head>
+  <title>  Why we rewrote
+    the parser | Blog </title>
+  <meta name="description" content="What went wrong and how we fixed it.">
+  <meta name="author" content="Sam Lee">
+  <meta name="keywords" content="rust, parsing , performance,">
+  <link rel="canonical" href="https://example.com/blog/parser">
+  <meta property="og:title" content="Why we rewrote the parser">
+  <meta property="og:site_name" content="Example Blog">
+  <meta property="og:image" content="https://example.com/cover.png">
+  <meta property="article:published_time" content="2026-03-01T09:00:00Z">
+  <meta property="article:modified_time" content="2026-03-04T12:30:00Z">
+  <meta name="twitter:card" content="summary_large_image">
+</head>
+<body><h1>Why we rewrote the parser</h1><p>Body text.</p></body>
+</html>"#;
+
+#[test]
+fn standard_tags_read() {
+    let doc = html_to_markdown_document(ARTICLE, &ConversionOptions::default());
+    let m = &doc.metadata;
+    assert_eq!(m.title.as_deref(), Some("Why we rewrote the parser | Blog"));
+    assert_eq!(
+        m.description.as_deref(),
+        Some("What went wrong and how we fixed it.")
+    );
+    assert_eq!(
+        m.canonical_url.as_deref(),
+        Some("https://example.com/blog/parser")
+    );
+    assert_eq!(m.lang.as_deref(), Some("en-GB"));
+    assert_eq!(m.author.as_deref(), Some("Sam Lee"));
+    assert_eq!(m.published.as_deref(), Some("2026-03-01T09:00:00Z"));
+    assert_eq!(m.modified.as_deref(), Some("2026-03-04T12:30:00Z"));
+    assert_eq!(m.site_name.as_deref(), Some("Example Blog"));
+    assert_eq!(m.image.as_deref(), Some("https://example.com/cover.png"));
+    assert_eq!(m.keywords, vec!["rust", "parsing", "performance"]);
+}
+
+#[test]
+fn open_graph_and_twitter_collected_in_order() {
+    let m = extract_metadata(&Html::parse_document(ARTICLE));
+    let og: Vec<&str> = m.open_graph.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(
+        og,
+        vec![
+            "og:title",
+            "og:site_name",
+            "og:image",
+            "article:published_time",
+            "article:modified_time",
+        ]
+    );
+    assert_eq!(
+        m.twitter,
+        vec![(
+            "twitter:card".to_string(),
+            "summary_large_image".to_string()
+        )]
+    );
+}
+
+#[test]
+fn social_tags_used_as_fallback() {
+    let html = r#"<head>
+<meta property="og:title" content="OG Title">
+<meta name="twitter:description" content="Tweet description">
+<meta property="og:url" content="https://example.com/p">
+<meta property="og:locale" content="fr_FR">
+<meta name="twitter:creator" content="@sam">
+<meta name="twitter:image" content="https://example.com/t.png">
+</head><p>x</p>"#;
+    let m = extract_metadata(&Html::parse_document(html));
+    assert_eq!(m.title.as_deref(), Some("OG Title"));
+    assert_eq!(m.description.as_deref(), Some("Tweet description"));
+    assert_eq!(m.canonical_url.as_deref(), Some("https://example.com/p"));
+    assert_eq!(m.lang.as_deref(), Some("fr_FR"));
+    assert_eq!(m.author.as_deref(), Some("@sam"));
+    assert_eq!(m.image.as_deref(), Some("https://example.com/t.png"));
+}
+
+#[test]
+fn svg_title_is_not_document_title() {
+    let html = r#"<body><svg><title>Logo</title></svg><p>Text</p></body>"#;
+    let m = extract_metadata(&Html::parse_document(html));
+    assert_eq!(m.title, None);
+}
+
+#[test]
+fn markdown_matches_plain_conversion() {
+    for mode in [ConversionMode::Balanced, ConversionMode::Minimal] {
+        let opts = ConversionOptions::for_mode(mode);
+        let doc = html_to_markdown_document(ARTICLE, &opts);
+        assert_eq!(
+            doc.markdown,
+            html_to_markdown_with(ARTICLE, &opts),
+            "{mode}"
+        );
+        assert!(!doc.markdown.contains("Blog</"), "{mode}");
+    }
+}
+
+#[test]
+fn fragment_without_head_has_empty_metadata() {
+    let doc = html_to_markdown_document("<p>Just text</p>", &ConversionOptions::default());
+    assert_eq!(doc.markdown, "Just text\n");
+    assert_eq!(doc.metadata, mdka::DocumentMetadata::default());
+}
@@ -0,0 +1,124 @@
+//! Integration tests: `<noscript>` and declarative shadow DOM recovery
+//! Covers: `recover_noscript` (opt-in in every mode) restoring fallback
+//! content only when nothing else in the same parent renders (replacing a
+//! lazy-loading placeholder image, in time linear in the number of siblings),
+//! and `expand_shadow_roots` walking `<template shadowrootmode>` contents
+//! while ordinary `<template>` elements stay skipped.
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionMode, ConversionOptions};
+use std::time::{Duration, Instant};
+
+fn recovering() -> ConversionOptions {
+    ConversionOptions::default().recover_noscript(true)
+}
+
+fn without_recovery() -> ConversionOptions {
+    ConversionOptions::default()
+        .recover_noscript(false)
+        .expand_shadow_roots(false)
+}
+
+// History: `<noscript>` recovery used to be on in every mode except
+// `Preserve`, so the "You need to enable JavaScript to run this app." fallback
+// of every Create React App shell leaked into the output by default.
+#[test]
+fn noscript_skipped_by_default_in_every_mode() {
+    let html = r#"<body><noscript>You need to enable JavaScript to run this app.</noscript><div id="root"></div></body>"#;
+    for mode in [
+        ConversionMode::Balanced,
+        ConversionMode::Strict,
+        ConversionMode::Minimal,
+        ConversionMode::Semantic,
+        ConversionMode::Preserve,
+    ] {
+        let opts = ConversionOptions::for_mode(mode);
+        assert!(!opts.recover_noscript, "{mode:?}");
+        assert!(!conv_with(html, &opts).contains("JavaScript"), "{mode:?}");
+    }
+    let html =
+        r#"<p><img data-src="a.png" alt="A"><noscript><img src="a.png" alt="A"></noscript></p>"#;
+    assert_eq!(conv(html), "![A]()\n");
+}
+
+#[test]
+fn noscript_recovered_when_app_root_is_empty() {
+    let html = r#"<body><div class="app"></div><noscript><h1>Title</h1><p>Server text</p></noscript></body>"#;
+    assert_eq!(conv_with(html, &recovering()), "# Title\n\nServer text\n");
+}
+
+#[test]
+fn noscript_lazy_image_fallback() {
+    let html = r#"<p><span class="lazy" data-src="real.png"></span><noscript><img src="real.png" alt="Real"></noscript></p>"#;
+    assert_eq!(conv_with(html, &recovering()), "![Real](real.png)\n");
+}
+
+#[test]
+fn lazy_placeholder_image_replaced_by_noscript_image() {
+    let html = r#"<p><img data-src="a.png" class="lazy"><noscript><img src="a.png" alt="A"></noscript></p>"#;
+    assert_eq!(conv_with(html, &recovering()), "![A](a.png)\n");
+    let html = r#"<p><img src="data:image/gif;base64,R0lGOD" data-src="a.png"><noscript><img src="a.png" alt="A"></noscript></p>"#;
+    assert_eq!(conv_with(html, &recovering()), "![A](a.png)\n");
+    // without recovery the placeholder is left as it was
+    let html =
+        r#"<p><img data-src="a.png" alt="A"><noscript><img src="a.png" alt="A"></noscript></p>"#;
+    assert_eq!(conv_with(html, &without_recovery()), "![A]()\n");
+}
+
+#[test]
+fn noscript_ignored_when_siblings_render() {
+    let html = r#"<body><p>Content</p><noscript><p>Please enable JavaScript</p></noscript></body>"#;
+    assert_eq!(conv_with(html, &recovering()), "Content\n");
+}
+
+#[test]
+fn noscript_ignored_next_to_a_real_image() {
+    let html = r#"<p><img src="a.png" alt="A"><noscript><img src="a.png" alt="A"></noscript></p>"#;
+    assert_eq!(conv_with(html, &recovering()), "![A](a.png)\n");
+}
+
+#[test]
+fn lazy_image_gallery_converts_in_linear_time() {
+    let item = r#"<img data-src="a.png"><noscript><img src="a.png" alt="A"></noscript>"#;
+    let html = format!("<div>{}</div>", item.repeat(20_000));
+    let start = Instant::now();
+    let md = conv_with(&html, &recovering());
+    assert!(
+        start.elapsed() < Duration::from_secs(5),
+        "{:?}",
+        start.elapsed()
+    );
+    assert_eq!(md.matches("![A](a.png)").count(), 20_000);
+    assert!(!md.contains("![]("));
+}
+
+#[test]
+fn noscript_skipped_when_disabled() {
+    let html = r#"<body><div class="app"></div><noscript><p>Server text</p></noscript></body>"#;
+    assert_eq!(conv_with(html, &without_recovery()), "");
+}
+
+#[test]
+fn shadow_root_contents_rendered() {
+    let html = r#"<my-card><template shadowrootmode="open"><h2>Card</h2><p>Inside</p></template></my-card>"#;
+    assert_eq!(conv(html), "## Card\n\nInside\n");
+}
+
+#[test]
+fn legacy_shadowroot_attribute_rendered() {
+    let html = r#"<my-card><template shadowroot="open"><p>Inside</p></template></my-card>"#;
+    assert_eq!(conv(html), "Inside\n");
+}
+
+#[test]
+fn plain_template_stays_skipped() {
+    let html = r#"<p>A</p><template><p>Inert</p></template><p>B</p>"#;
+    assert_eq!(conv(html), "A\n\nB\n");
+}
+
+#[test]
+fn shadow_root_skipped_when_disabled() {
+    let html = r#"<my-card><template shadowrootmode="open"><p>Inside</p></template></my-card>"#;
+    assert_eq!(conv_with(html, &without_recovery()), "");
+}
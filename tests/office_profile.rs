@@ -0,0 +1,158 @@
+//! Integration tests: `SourceProfile::Office` (Word / Outlook / Google Docs)
+//! Covers: Office namespace elements, `mso-list` pseudo-list reconstruction
+//! (levels, ordered vs. bullet markers, list termination, mixing with real
+//! lists), the Google Docs non-bold `<b>` wrapper, and the default
+//! `Generic` profile leaving all of it untouched.
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionMode, ConversionOptions, SourceProfile};
+
+fn office() -> ConversionOptions {
+    ConversionOptions::default().profile(SourceProfile::Office)
+}
+
+/// One Word list paragraph as Word's "Filtered HTML" export writes it.
+fn word_item(level: usize, marker: &str, text: &str) -> String {
+    format!(
+        concat!(
+            r#"<p class=MsoListParagraphCxSpMiddle style='text-indent:-18.0pt;mso-list:l0 level{} lfo1'>"#,
+            r#"<![if !supportLists]><span style='font-family:Symbol'><span style='mso-list:Ignore'>{}"#,
+            r#"<span style='font:7.0pt "Times New Roman"'>&nbsp;&nbsp;&nbsp; </span></span></span><![endif]>"#,
+            r#"{}<o:p></o:p></p>"#,
+        ),
+        level, marker, text
+    )
+}
+
+#[test]
+fn word_bullets_become_nested_list() {
+    let html = format!(
+        "<p class=MsoNormal>Intro<o:p></o:p></p>{}{}{}{}<p class=MsoNormal>After<o:p>&nbsp;</o:p></p>",
+        word_item(1, "·", "One"),
+        word_item(2, "o", "One.a"),
+        word_item(2, "o", "One.b"),
+        word_item(1, "·", "Two"),
+    );
+    assert_eq!(
+        conv_with(&html, &office()),
+        "Intro\n\n- One\n  - One.a\n  - One.b\n- Two\n\nAfter\n"
+    );
+}
+
+#[test]
+fn word_numbered_markers_become_ordered_list() {
+    let html = format!(
+        "{}{}{}{}",
+        word_item(1, "1.", "First"),
+        word_item(2, "a.", "Sub"),
+        word_item(1, "2.", "Second"),
+        word_item(1, "3)", "Third"),
+    );
+    assert_eq!(
+        conv_with(&html, &office()),
+        "1. First\n  1. Sub\n2. Second\n3. Third\n"
+    );
+}
+
+#[test]
+fn separate_lists_restart_numbering() {
+    let html = format!(
+        "{}{}<p class=MsoNormal>Between</p>{}",
+        word_item(1, "1.", "A"),
+        word_item(1, "2.", "B"),
+        word_item(1, "1.", "C"),
+    );
+    assert_eq!(
+        conv_with(&html, &office()),
+        "1. A\n2. B\n\nBetween\n\n1. C\n"
+    );
+}
+
+#[test]
+fn real_list_after_word_list_is_not_nested() {
+    let html = format!(
+        "{}{}<ul><li>real1</li><li>real2</li></ul>",
+        word_item(1, "·", "One"),
+        word_item(2, "o", "One.a"),
+    );
+    assert_eq!(
+        conv_with(&html, &office()),
+        "- One\n  - One.a\n\n- real1\n- real2\n"
+    );
+}
+
+#[test]
+fn word_list_inside_real_list_item_ends_with_it() {
+    let html = format!(
+        "<ul><li>outer{}</li><li>outer2</li></ul>",
+        word_item(1, "·", "inner"),
+    );
+    assert_eq!(
+        conv_with(&html, &office()),
+        "- outer\n  - inner\n- outer2\n"
+    );
+}
+
+#[test]
+fn list_paragraph_class_without_level_is_top_level() {
+    let html = r#"<p class="MsoListParagraph"><span style="mso-list:Ignore">-</span>Only</p>"#;
+    assert_eq!(conv_with(html, &office()), "- Only\n");
+}
+
+#[test]
+fn office_namespace_elements_removed() {
+    let html = concat!(
+        r#"<p class=MsoNormal>Text<o:p>&nbsp;</o:p></p>"#,
+        r#"<p><v:shape id="pic"><v:imagedata src="image001.png"/></v:shape>Caption</p>"#,
+        r#"<p><st1:place w:st="on">Tokyo</st1:place> office</p>"#,
+    );
+    assert_eq!(
+        conv_with(html, &office()),
+        "Text\n\nCaption\n\nTokyo office\n"
+    );
+}
+
+#[test]
+fn google_docs_wrapper_is_not_bold() {
+    let html = concat!(
+        r#"<meta charset="utf-8"><b style="font-weight:normal;" id="docs-internal-guid-1a2b3c4d-7fff-1234">"#,
+        r#"<p dir="ltr"><span style="font-weight:400">Plain and </span>"#,
+        r#"<span style="font-weight:700">bold</span></p></b>"#,
+    );
+    assert_eq!(conv_with(html, &office()), "Plain and **bold**\n");
+}
+
+#[test]
+fn real_bold_kept() {
+    assert_eq!(
+        conv_with("<p><b>Bold</b> text</p>", &office()),
+        "**Bold** text\n"
+    );
+}
+
+#[test]
+fn generic_profile_is_the_default_in_every_mode() {
+    for mode in [
+        ConversionMode::Balanced,
+        ConversionMode::Strict,
+        ConversionMode::Minimal,
+        ConversionMode::Semantic,
+        ConversionMode::Preserve,
+    ] {
+        assert_eq!(
+            ConversionOptions::for_mode(mode).profile,
+            SourceProfile::Generic
+        );
+    }
+    let html = word_item(1, "·", "One");
+    assert_ne!(conv(&html), "- One\n");
+}
+
+#[test]
+fn profile_parses_from_str() {
+    for p in [SourceProfile::Generic, SourceProfile::Office] {
+        assert_eq!(p.as_str().parse::<SourceProfile>(), Ok(p));
+    }
+    assert!("word".parse::<SourceProfile>().is_err());
+}
@@ -0,0 +1,93 @@
+//! Integration tests: `include_selectors` / `exclude_selectors`
+//! Covers: converting only matching subtrees in document order, nested
+//! matches not being duplicated, exclusion at any depth (including inside
+//! included subtrees), comma-separated selector lists, and parse errors.
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionOptions, CssSelector};
+
+fn sel(s: &str) -> CssSelector {
+    CssSelector::parse(s).unwrap()
+}
+
+const PAGE: &str = r#"<body>
+<div class="banner"><p>Sale!</p></div>
+<article><h1>Title</h1><p>Lead</p><div class="share">Share this</div><p>Body</p></article>
+<section class="comments"><p>First comment</p></section>
+</body>"#;
+
+#[test]
+fn include_converts_only_matching_subtree() {
+    let opts = ConversionOptions::default().include_selectors(vec![sel("article")]);
+    assert_eq!(
+        conv_with(PAGE, &opts),
+        "# Title\n\nLead\n\nShare this\n\nBody\n"
+    );
+}
+
+#[test]
+fn include_keeps_document_order_across_selectors() {
+    let opts =
+        ConversionOptions::default().include_selectors(vec![sel(".comments"), sel("article h1")]);
+    assert_eq!(conv_with(PAGE, &opts), "# Title\n\nFirst comment\n");
+}
+
+#[test]
+fn nested_matches_are_not_duplicated() {
+    let opts = ConversionOptions::default().include_selectors(vec![sel("article, article p")]);
+    assert_eq!(
+        conv_with(PAGE, &opts),
+        "# Title\n\nLead\n\nShare this\n\nBody\n"
+    );
+}
+
+#[test]
+fn inline_matches_become_separate_blocks() {
+    let html = r#"<p>Price: <span class="price">$10</span> or <span class="price">$12</span></p>"#;
+    let opts = ConversionOptions::default().include_selectors(vec![sel(".price")]);
+    assert_eq!(conv_with(html, &opts), "$10\n\n$12\n");
+}
+
+#[test]
+fn include_without_match_is_empty() {
+    let opts = ConversionOptions::default().include_selectors(vec![sel("#missing")]);
+    assert_eq!(conv_with(PAGE, &opts), "");
+}
+
+#[test]
+fn exclude_drops_matching_subtrees() {
+    let opts = ConversionOptions::default()
+        .exclude_selectors(vec![sel(".banner"), sel("section.comments")]);
+    assert_eq!(
+        conv_with(PAGE, &opts),
+        "# Title\n\nLead\n\nShare this\n\nBody\n"
+    );
+}
+
+#[test]
+fn exclude_applies_inside_included_subtree() {
+    let opts = ConversionOptions::default()
+        .include_selectors(vec![sel("article")])
+        .exclude_selectors(vec![sel(".share")]);
+    assert_eq!(conv_with(PAGE, &opts), "# Title\n\nLead\n\nBody\n");
+}
+
+#[test]
+fn empty_selectors_change_nothing() {
+    let opts = ConversionOptions::default()
+        .include_selectors(Vec::new())
+        .exclude_selectors(Vec::new());
+    assert_eq!(conv_with(PAGE, &opts), conv(PAGE));
+}
+
+#[test]
+fn invalid_selector_is_an_error() {
+    let err = CssSelector::parse("div[").unwrap_err();
+    assert!(
+        err.to_string().starts_with("invalid selector `div[`"),
+        "{err}"
+    );
+    assert!("p >".parse::<CssSelector>().is_err());
+    assert_eq!(sel("main  p").as_str(), "main  p");
+}
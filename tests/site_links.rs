@@ -0,0 +1,160 @@
+//! Integration tests: site conversion (`BatchOptions::site_links`)
+//! Covers: links between input files rewritten to the `.md` outputs across
+//! subdirectories (relative, root-relative, directory and percent-encoded
+//! links), the input directory layout kept under `out_dir`, `#fragment`
+//! targets mapped to heading anchors with and without `preserve_ids`, and
+//! dangling links reported while external links and existing non-input
+//! files are left alone.
+
+use std::path::{Path, PathBuf};
+
+use mdka::options::ConversionOptions;
+use mdka::{BatchOptions, DanglingLink, html_files_to_markdown_batch};
+
+fn write_site(dir: &Path) -> Vec<PathBuf> {
+    let _ = std::fs::remove_dir_all(dir);
+    let pages = [
+        (
+            "index.html",
+            r##"<h1>Home</h1><p><a href="guide/intro.html">Intro</a>
+              <a href="guide/">Guide</a> <a href="guide/intro.html#setup-step">Setup</a>
+              <a href="my%20page.html">Mine</a> <a href="missing.html">Gone</a>
+              <a href="guide/intro.html#nope">Nope</a> <a href="files/doc.pdf">PDF</a>
+              <a href="https://example.com/x.html">Ext</a> <a href="#top">Top</a></p>"##,
+        ),
+        (
+            "my page.html",
+            r#"<h1>Mine</h1><p><a href="guide/intro.html#faq">FAQ</a></p>"#,
+        ),
+        (
+            "guide/index.html",
+            r#"<h1>Guide</h1><p><a href="../index.html">Home</a>
+              <a href="/guide/intro.html?lang=en">Intro</a></p>"#,
+        ),
+        (
+            "guide/intro.html",
+            r##"<h1>Intro</h1><h2 id="setup-step">Set up &amp; run</h2><p>Text.</p>
+              <section id="faq"><h2>FAQ</h2><p>Q.</p></section>
+              <div id="note"><p>Note.</p></div><h2>FAQ</h2>
+              <p><a href="#faq">First</a> <a href="#note">Note</a> <a href="#faq-1">Second</a></p>"##,
+        ),
+    ];
+    std::fs::create_dir_all(dir.join("files")).unwrap();
+    std::fs::write(dir.join("files/doc.pdf"), b"%PDF").unwrap();
+    pages
+        .iter()
+        .map(|(name, html)| {
+            let path = dir.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, html).unwrap();
+            path
+        })
+        .collect()
+}
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn links_rewritten_across_directories() {
+    let dir = std::env::temp_dir().join("mdka_site_links");
+    let files = write_site(&dir);
+    let out = dir.join("out");
+    let opts = ConversionOptions::default().preserve_ids(false);
+    let batch = BatchOptions::default().site_links(true);
+    let report = html_files_to_markdown_batch(&files, &out, &opts, &batch);
+
+    let dests: Vec<PathBuf> = report
+        .files
+        .iter()
+        .map(|(_, r)| r.as_ref().unwrap().clone())
+        .collect();
+    assert_eq!(
+        dests,
+        [
+            out.join("index.md"),
+            out.join("my page.md"),
+            out.join("guide/index.md"),
+            out.join("guide/intro.md"),
+        ]
+    );
+    assert_eq!(
+        read(&dests[0]),
+        "# Home\n\n[Intro](guide/intro.md) [Guide](guide/index.md) \
+         [Setup](guide/intro.md#set-up--run) [Mine](my%20page.md) [Gone](missing.html) \
+         [Nope](guide/intro.md#nope) [PDF](files/doc.pdf) [Ext](https://example.com/x.html) \
+         [Top](#top)\n"
+    );
+    assert_eq!(read(&dests[1]), "# Mine\n\n[FAQ](guide/intro.md#faq)\n");
+    assert_eq!(
+        read(&dests[2]),
+        "# Guide\n\n[Home](../index.md) [Intro](intro.md)\n"
+    );
+    // a section's id points at its first heading, any other element's id at
+    // the heading before it; repeated headings get numbered anchors
+    assert!(
+        read(&dests[3]).ends_with("[First](#faq) [Note](#faq) [Second](#faq-1)\n"),
+        "{}",
+        read(&dests[3])
+    );
+
+    assert_eq!(
+        report.dangling_links,
+        [
+            DanglingLink {
+                file: 0,
+                href: "missing.html".into(),
+                missing_fragment: false,
+            },
+            DanglingLink {
+                file: 0,
+                href: "guide/intro.html#nope".into(),
+                missing_fragment: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn preserved_ids_kept_as_fragments() {
+    let dir = std::env::temp_dir().join("mdka_site_links_ids");
+    let files = write_site(&dir);
+    let out = dir.join("out");
+    let batch = BatchOptions::default().site_links(true);
+    let report = html_files_to_markdown_batch(&files, &out, &ConversionOptions::default(), &batch);
+
+    let intro = read(report.files[3].1.as_ref().unwrap());
+    // `<a id="note">` is written, so the fragment still resolves; heading ids
+    // still map to the heading's own anchor
+    assert!(intro.contains("<a id=\"note\"></a>"), "{intro}");
+    assert!(
+        intro.ends_with("[First](#faq) [Note](#note) [Second](#faq-1)\n"),
+        "{intro}"
+    );
+    let index = read(report.files[0].1.as_ref().unwrap());
+    assert!(
+        index.contains("[Setup](guide/intro.md#set-up--run)"),
+        "{index}"
+    );
+}
+
+#[test]
+fn off_by_default() {
+    let dir = std::env::temp_dir().join("mdka_site_links_off");
+    let files = write_site(&dir);
+    let out = dir.join("out");
+    let report = html_files_to_markdown_batch(
+        &files,
+        &out,
+        &ConversionOptions::default(),
+        &BatchOptions::default(),
+    );
+    // flat output named after the file stem, links untouched
+    assert_eq!(report.files[3].1.as_ref().unwrap(), &out.join("intro.md"));
+    assert_eq!(
+        read(&out.join("my page.md")),
+        "# Mine\n\n[FAQ](guide/intro.html#faq)\n"
+    );
+    assert!(report.dangling_links.is_empty());
+}
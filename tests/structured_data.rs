@@ -0,0 +1,384 @@
+//! Integration tests: structured data (`extract_structured_data`,
+//! `render_structured_data`, `MetadataField::StructuredData`)
+//! Covers: JSON-LD with `@graph`, arrays and comment wrappers, raw control
+//! characters in JSON-LD strings, strict `\u` escapes, invalid JSON-LD
+//! skipped, microdata with nested scopes and repeated properties, deeply
+//! nested microdata / microformats items without stack overflow, h-entry
+//! microformats, metadata fallbacks from structured data, Recipe and
+//! FAQPage rendered only when asked and richer than the visible HTML with
+//! their text escaped, and structured data in YAML / TOML front matter.
+
+use mdka::options::{ConversionMode, ConversionOptions};
+use mdka::scraper::Html;
+use mdka::{
+    FrontMatterField, FrontMatterFormat, JsonValue, MetadataField, extract_structured_data,
+    html_to_markdown_document, html_to_markdown_with,
+};
+
+const RECIPE: &str = r#"<html><head>
+<script type="application/ld+json">
+{
+  "@context": "https://schema.org",
+  "@type": "Recipe",
+  "name": "Pancakes",
+  "description": "Fluffy &amp; quick.",
+  "datePublished": "2026-02-01",
+  "author": {"@type": "Person", "name": "Ana"},
+  "keywords": "breakfast, easy",
+  "recipeIngredient": ["200g flour", "2 eggs", "300ml milk"],
+  "recipeInstructions": [
+    {"@type": "HowToStep", "text": "Whisk everything."},
+    {"@type": "HowToSection", "name": "Cook", "itemListElement": [
+      {"@type": "HowToStep", "text": "Fry in a hot pan."}
+    ]}
+  ]
+}
+</script></head>
+<body><h1>Pancakes</h1><p>My grandmother's favourite.</p></body></html>"#;
+
+fn text(value: Option<&JsonValue>) -> Option<&str> {
+    value.and_then(JsonValue::as_str)
+}
+
+#[test]
+fn json_ld_graph_and_arrays_flattened() {
+    let html = r#"
+      <script type="application/ld+json"><!--
+      {"@context": "https://schema.org", "@graph": [
+        {"@type": "WebSite", "name": "Example"},
+        {"@type": "Article", "headline": "Hello"}
+      ]}
+      --></script>
+      <script type="application/ld+json">[{"@type": "Person", "name": "Ana"}]</script>
+      <script type="application/ld+json">{ not json </script>"#;
+    let data = extract_structured_data(&Html::parse_document(html));
+    assert_eq!(data.json_ld.len(), 3);
+    assert_eq!(text(data.json_ld[1].get("headline")), Some("Hello"));
+    assert_eq!(text(data.json_ld[2].get("name")), Some("Ana"));
+}
+
+#[test]
+fn json_ld_values_parsed() {
+    let html = r#"<script type="application/ld+json">
+      {"a": [1, -2.5e2, true, null], "b": "é\n😀"}</script>"#;
+    let data = extract_structured_data(&Html::parse_document(html));
+    assert_eq!(
+        data.json_ld[0],
+        JsonValue::Object(vec![
+            (
+                "a".into(),
+                JsonValue::Array(vec![
+                    JsonValue::Number(1.0),
+                    JsonValue::Number(-250.0),
+                    JsonValue::Bool(true),
+                    JsonValue::Null,
+                ])
+            ),
+            ("b".into(), JsonValue::String("é\n😀".into())),
+        ])
+    );
+    assert_eq!(
+        data.json_ld[0].to_string(),
+        r#"{"a":[1,-250,true,null],"b":"é\n😀"}"#
+    );
+}
+
+#[test]
+fn json_ld_strings_may_hold_raw_control_characters() {
+    let html = "<script type=\"application/ld+json\">{\"headline\":\"a\nb\tc\"}</script>";
+    let data = extract_structured_data(&Html::parse_document(html));
+    assert_eq!(data.json_ld.len(), 1);
+    assert_eq!(text(data.json_ld[0].get("headline")), Some("a\nb\tc"));
+    // the public parser stays strict
+    assert_eq!(JsonValue::parse("\"a\nb\""), None);
+}
+
+#[test]
+fn unicode_escapes_need_four_hex_digits() {
+    assert_eq!(
+        JsonValue::parse(r#""\u0041\u00e9""#),
+        Some(JsonValue::String("Aé".into()))
+    );
+    for bad in [
+        r#""\u+041""#,
+        r#""\u-041""#,
+        r#""\u 041""#,
+        r#""\u04G1""#,
+        r#""\u041""#,
+    ] {
+        assert_eq!(JsonValue::parse(bad), None, "{bad}");
+    }
+}
+
+#[test]
+fn deeply_nested_json_ld_rejected() {
+    let html = format!(
+        r#"<script type="application/ld+json">{}{}</script>"#,
+        "[".repeat(100_000),
+        "]".repeat(100_000)
+    );
+    let data = extract_structured_data(&Html::parse_document(&html));
+    assert!(data.json_ld.is_empty());
+}
+
+#[test]
+fn deeply_nested_items_do_not_overflow_the_stack() {
+    const DEPTH: usize = 3_000;
+    let microdata = format!(
+        "<div itemscope>{}x{}</div>",
+        r#"<div itemscope itemprop="p">"#.repeat(DEPTH),
+        "</div>".repeat(DEPTH)
+    );
+    let microformats = format!(
+        r#"<div class="h-entry">{}x{}</div>"#,
+        r#"<div class="h-card p-author">"#.repeat(DEPTH),
+        "</div>".repeat(DEPTH)
+    );
+    // one stack frame per level would need far more than this
+    std::thread::Builder::new()
+        .stack_size(128 * 1024)
+        .spawn(move || {
+            let data = extract_structured_data(&Html::parse_document(&microdata));
+            assert_eq!(data.microdata.len(), 1);
+            assert!(data.microdata[0].get("p").is_some());
+            let data = extract_structured_data(&Html::parse_document(&microformats));
+            assert_eq!(data.microformats.len(), 1);
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn microdata_nested_and_repeated() {
+    let html = r#"
+      <div itemscope itemtype="https://schema.org/Product">
+        <h2 itemprop="name">Kettle</h2>
+        <img itemprop="image" src="/k1.jpg"><img itemprop="image" src="/k2.jpg">
+        <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+          <meta itemprop="priceCurrency" content="EUR">
+          <span itemprop="price">39.90</span>
+        </div>
+      </div>"#;
+    let data = extract_structured_data(&Html::parse_document(html));
+    assert_eq!(data.microdata.len(), 1);
+    let product = &data.microdata[0];
+    assert_eq!(text(product.get("@type")), Some("Product"));
+    assert_eq!(text(product.get("name")), Some("Kettle"));
+    assert_eq!(
+        product.get("image"),
+        Some(&JsonValue::Array(vec![
+            JsonValue::String("/k1.jpg".into()),
+            JsonValue::String("/k2.jpg".into()),
+        ]))
+    );
+    let offer = product.get("offers").unwrap();
+    assert_eq!(text(offer.get("@type")), Some("Offer"));
+    assert_eq!(text(offer.get("price")), Some("39.90"));
+    assert_eq!(text(offer.get("priceCurrency")), Some("EUR"));
+    // the nested item's properties do not leak into the outer item
+    assert!(product.get("price").is_none());
+}
+
+#[test]
+fn h_entry_microformat() {
+    let html = r#"
+      <article class="h-entry">
+        <h1 class="p-name">Notes</h1>
+        <a class="p-author h-card" href="/me">Ana</a>
+        <time class="dt-published" datetime="2026-01-05">5 Jan</time>
+        <a class="p-category" href="/t/rust">rust</a>
+        <div class="e-content"><p>Hi <b>there</b></p></div>
+      </article>"#;
+    let document = Html::parse_document(html);
+    let data = extract_structured_data(&document);
+    assert_eq!(data.microformats.len(), 1);
+    let entry = &data.microformats[0];
+    let props = entry.get("properties").unwrap();
+    assert_eq!(
+        text(entry.get("type").unwrap().as_slice().first()),
+        Some("h-entry")
+    );
+    assert_eq!(
+        text(props.get("name").unwrap().as_slice().first()),
+        Some("Notes")
+    );
+    assert_eq!(
+        text(props.get("published").unwrap().as_slice().first()),
+        Some("2026-01-05")
+    );
+    let author = &props.get("author").unwrap().as_slice()[0];
+    assert_eq!(text(author.get("value")), Some("Ana"));
+    let content = &props.get("content").unwrap().as_slice()[0];
+    assert_eq!(text(content.get("html")), Some("<p>Hi <b>there</b></p>"));
+    assert_eq!(text(content.get("value")), Some("Hi there"));
+
+    let meta = mdka::extract_metadata(&document);
+    assert_eq!(meta.title.as_deref(), Some("Notes"));
+    assert_eq!(meta.author.as_deref(), Some("Ana"));
+    assert_eq!(meta.published.as_deref(), Some("2026-01-05"));
+    assert_eq!(meta.keywords, ["rust"]);
+}
+
+#[test]
+fn metadata_falls_back_to_json_ld() {
+    let doc = html_to_markdown_document(RECIPE, &ConversionOptions::default());
+    let m = &doc.metadata;
+    assert_eq!(m.title.as_deref(), Some("Pancakes"));
+    assert_eq!(m.description.as_deref(), Some("Fluffy &amp; quick."));
+    assert_eq!(m.author.as_deref(), Some("Ana"));
+    assert_eq!(m.published.as_deref(), Some("2026-02-01"));
+    assert_eq!(m.keywords, ["breakfast", "easy"]);
+    assert_eq!(m.structured_data.json_ld.len(), 1);
+
+    // <meta> still wins over structured data
+    let html = RECIPE.replace("<head>", "<head><title>Meta title</title>");
+    let doc = html_to_markdown_document(&html, &ConversionOptions::default());
+    assert_eq!(doc.metadata.title.as_deref(), Some("Meta title"));
+}
+
+fn rendering() -> ConversionOptions {
+    ConversionOptions::default().render_structured_data(true)
+}
+
+#[test]
+fn recipe_rendered_when_richer_than_html() {
+    let md = html_to_markdown_with(RECIPE, &rendering());
+    assert_eq!(
+        md,
+        "# Pancakes\n\nMy grandmother's favourite.\n\n\
+         ## Pancakes\n\nFluffy & quick.\n\n\
+         ### Ingredients\n\n- 200g flour\n- 2 eggs\n- 300ml milk\n\n\
+         ### Instructions\n\n1. Whisk everything.\n2. Fry in a hot pan.\n"
+    );
+
+    // opt-in in every mode: the sections are invented, English-headed text
+    // (it used to be on in Balanced, Minimal and Semantic)
+    for mode in [
+        ConversionMode::Balanced,
+        ConversionMode::Strict,
+        ConversionMode::Minimal,
+        ConversionMode::Semantic,
+        ConversionMode::Preserve,
+    ] {
+        let opts = ConversionOptions::for_mode(mode);
+        assert!(
+            !html_to_markdown_with(RECIPE, &opts).contains("Ingredients"),
+            "{mode}"
+        );
+    }
+}
+
+#[test]
+fn recipe_not_rendered_when_html_has_it() {
+    let html = RECIPE.replace(
+        "<p>My grandmother's favourite.</p>",
+        "<ul><li>200 g flour</li><li>2 Eggs</li><li>300ml milk</li></ul>",
+    );
+    let md = html_to_markdown_with(&html, &rendering());
+    assert!(!md.contains("### Ingredients"), "{md}");
+}
+
+#[test]
+fn faq_rendered_when_questions_missing() {
+    let html = r#"<script type="application/ld+json">
+      {"@type": "FAQPage", "mainEntity": [
+        {"@type": "Question", "name": "Is it free?",
+         "acceptedAnswer": {"@type": "Answer", "text": "<p>Yes, <b>always</b>.</p>"}},
+        {"@type": "Question", "name": "Can I self-host?",
+         "acceptedAnswer": {"@type": "Answer", "text": "Of course."}}
+      ]}</script><p>Questions? See below.</p>"#;
+    let md = html_to_markdown_with(html, &rendering());
+    assert_eq!(
+        md,
+        "Questions? See below.\n\n\
+         ## Is it free?\n\nYes, **always**.\n\n\
+         ## Can I self-host?\n\nOf course.\n"
+    );
+
+    let visible = format!("{html}<h3>Is it free?</h3><h3>Can I self-host?</h3>");
+    let md = html_to_markdown_with(&visible, &rendering());
+    assert_eq!(md.matches("Is it free?").count(), 1, "{md}");
+}
+
+#[test]
+fn rich_content_text_is_escaped() {
+    let html = r#"<script type="application/ld+json">
+      {"@type": "Recipe", "name": "Fish &amp; chips",
+       "recipeIngredient": ["2 tbsp M&Ms", "salt &not pepper", "</li><li>injected"],
+       "recipeInstructions": ["Heat the oil to <180C.", "Serve & enjoy."]}
+      </script>
+      <script type="application/ld+json">
+      {"@type": "FAQPage", "mainEntity": [
+        {"@type": "Question", "name": "Is 1 < 2?",
+         "acceptedAnswer": {"@type": "Answer", "text": "<p>Yes <b>always"}},
+        {"@type": "Question", "name": "Next?",
+         "acceptedAnswer": {"@type": "Answer", "text": "</div></li>Done."}}
+      ]}</script><p>Body</p>"#;
+    let md = html_to_markdown_with(html, &rendering());
+    assert_eq!(
+        md,
+        "Body\n\n\
+         ## Fish & chips\n\n\
+         ### Ingredients\n\n- 2 tbsp M&Ms\n- salt &not pepper\n- </li><li>injected\n\n\
+         ### Instructions\n\n1. Heat the oil to <180C.\n2. Serve & enjoy.\n\n\
+         ## Is 1 < 2?\n\nYes **always**\n\n\
+         ## Next?\n\nDone.\n"
+    );
+}
+
+#[test]
+fn structured_data_in_front_matter() {
+    let html = r#"<script type="application/ld+json">
+      {"@type": "Product", "name": "Kettle", "offers": {"price": 39.9, "sku": null}}
+      </script><p>Body</p>"#;
+    let fields = vec![FrontMatterField::new(MetadataField::StructuredData).renamed("schema")];
+    let yaml = ConversionOptions::default()
+        .front_matter(FrontMatterFormat::Yaml)
+        .front_matter_fields(fields.clone());
+    assert_eq!(
+        html_to_markdown_with(html, &yaml),
+        "---\nschema: [{\"@type\":\"Product\",\"name\":\"Kettle\",\"offers\":{\"price\":39.9,\"sku\":null}}]\n---\n\nBody\n"
+    );
+
+    let toml = yaml.front_matter(FrontMatterFormat::Toml);
+    assert_eq!(
+        html_to_markdown_with(html, &toml),
+        "+++\nschema = [{ \"@type\" = \"Product\", name = \"Kettle\", offers = { price = 39.9 } }]\n+++\n\nBody\n"
+    );
+
+    // nothing to write → no front matter at all
+    assert_eq!(html_to_markdown_with("<p>Body</p>", &toml), "Body\n");
+}
+
+#[test]
+fn front_matter_keys_are_never_repeated() {
+    let html = r#"<head><title>Kettle page</title></head>
+      <script type="application/ld+json">
+      {"@type": "Product", "name": "Kettle", "name": "Teapot", "offers": {"price": 1, "price": 2}}
+      </script><p>Body</p>"#;
+    let fields = vec![
+        FrontMatterField::new(MetadataField::Title),
+        FrontMatterField::new(MetadataField::StructuredData).renamed("title"),
+        FrontMatterField::new(MetadataField::Description).renamed("schema"),
+        FrontMatterField::new(MetadataField::StructuredData).renamed("schema"),
+    ];
+    let toml = ConversionOptions::default()
+        .front_matter(FrontMatterFormat::Toml)
+        .front_matter_fields(fields);
+    // the second `title` is dropped; the empty description leaves `schema` free
+    assert_eq!(
+        html_to_markdown_with(html, &toml),
+        "+++\ntitle = \"Kettle page\"\n\
+         schema = [{ \"@type\" = \"Product\", name = \"Kettle\", offers = { price = 1 } }]\n\
+         +++\n\nBody\n"
+    );
+
+    let yaml = toml.front_matter(FrontMatterFormat::Yaml);
+    assert_eq!(
+        html_to_markdown_with(html, &yaml),
+        "---\ntitle: \"Kettle page\"\n\
+         schema: [{\"@type\":\"Product\",\"name\":\"Kettle\",\"offers\":{\"price\":1}}]\n\
+         ---\n\nBody\n"
+    );
+}
@@ -0,0 +1,79 @@
+//! Integration tests: user-configurable tag classification (`tag_rules`)
+//! Covers: adding skip tags, keeping a default-skipped tag, extra shell and
+//! wrapper tags, custom elements as block containers, case-insensitive
+//! names, and the built-in lists staying in effect by default.
+
+mod common;
+use common::{conv, conv_with};
+use mdka::options::{ConversionOptions, TagRules};
+
+fn with_rules(rules: TagRules) -> ConversionOptions {
+    ConversionOptions::default().tag_rules(rules)
+}
+
+#[test]
+fn custom_elements_run_together_by_default() {
+    let html = "<my-card>First</my-card><my-card>Second</my-card>";
+    assert_eq!(conv(html), "FirstSecond\n");
+}
+
+#[test]
+fn block_tag_separates_custom_elements() {
+    let html =
+        "<x-section><my-card>First</my-card><my-card>Second</my-card></x-section><p>After</p>";
+    let opts = with_rules(
+        TagRules::default()
+            .block_tag("my-card")
+            .block_tag("x-section"),
+    );
+    assert_eq!(conv_with(html, &opts), "First\n\nSecond\n\nAfter\n");
+}
+
+#[test]
+fn skip_tag_drops_content() {
+    let html = "<p>Intro</p><form><label>Email</label><button>Subscribe</button></form><p>End</p>";
+    assert_eq!(conv(html), "Intro\n\nEmailSubscribe\n\nEnd\n");
+    let opts = with_rules(TagRules::default().skip_tag("form"));
+    assert_eq!(conv_with(html, &opts), "Intro\n\nEnd\n");
+}
+
+#[test]
+fn keep_tag_restores_default_skipped_element() {
+    let html = "<p>A</p><iframe>Fallback text</iframe>";
+    assert_eq!(conv(html), "A\n");
+    let opts = with_rules(TagRules::default().keep_tag("iframe"));
+    assert_eq!(conv_with(html, &opts), "A\n\nFallback text\n");
+}
+
+#[test]
+fn shell_tag_dropped_with_drop_interactive_shell() {
+    let html = "<site-nav>Home About</site-nav><p>Body</p>";
+    let rules = TagRules::default().shell_tag("site-nav");
+    assert_eq!(
+        conv_with(html, &with_rules(rules.clone())),
+        "Home About\n\nBody\n"
+    );
+    let opts = with_rules(rules).drop_interactive_shell(true);
+    assert_eq!(conv_with(html, &opts), "Body\n");
+}
+
+#[test]
+fn wrapper_tag_unwrapped_with_unwrap_unknown_wrappers() {
+    let html = "<p>A<x-wrap><b>B</b></x-wrap>C</p>";
+    let mut opts = with_rules(TagRules::default().wrapper_tag("x-wrap"));
+    opts.unwrap_unknown_wrappers = true;
+    assert_eq!(conv_with(html, &opts), "A**B**C\n");
+}
+
+#[test]
+fn tag_names_are_case_insensitive() {
+    let html = "<my-card>One</my-card><my-card>Two</my-card>";
+    let opts = with_rules(TagRules::default().block_tag("MY-CARD"));
+    assert_eq!(conv_with(html, &opts), "One\n\nTwo\n");
+}
+
+#[test]
+fn default_rules_keep_builtin_lists() {
+    let html = "<script>x()</script><style>p{}</style><p>Text</p>";
+    assert_eq!(conv_with(html, &with_rules(TagRules::default())), "Text\n");
+}
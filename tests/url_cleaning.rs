@@ -0,0 +1,119 @@
+//! Integration tests: URL cleaning (`clean_urls`, `tracking_params`)
+//! Covers: tracking parameters stripped (including `utm_*` prefixes) while
+//! other parameters stay, redirectors unwrapped (Google, Facebook, Outlook
+//! safelinks, nested), percent-encoding and scheme/host case normalised,
+//! images cleaned too, non-HTTP schemes left alone, a custom parameter list,
+//! deduplication of reference links, and off by default.
+
+use mdka::html_to_markdown_with;
+use mdka::options::{ConversionOptions, LinkStyle};
+
+fn link(href: &str, opts: &ConversionOptions) -> String {
+    html_to_markdown_with(&format!("<a href=\"{href}\">x</a>"), opts)
+}
+
+fn opts() -> ConversionOptions {
+    ConversionOptions::default().clean_urls(true)
+}
+
+#[test]
+fn tracking_params_stripped() {
+    let cases = [
+        (
+            "https://example.com/a?utm_source=x&amp;id=3&amp;UTM_Medium=y&amp;fbclid=abc",
+            "https://example.com/a?id=3",
+        ),
+        (
+            "https://example.com/a?gclid=1&amp;ref=hn#top",
+            "https://example.com/a#top",
+        ),
+        ("/docs?page=2&amp;msclkid=9", "/docs?page=2"),
+        // names are matched whole: `reference` is not `ref`
+        (
+            "https://example.com/?reference=1",
+            "https://example.com/?reference=1",
+        ),
+    ];
+    for (href, expected) in cases {
+        assert_eq!(link(href, &opts()), format!("[x]({expected})\n"), "{href}");
+    }
+}
+
+#[test]
+fn redirectors_unwrapped() {
+    let cases = [
+        (
+            "https://www.google.com/url?sa=t&amp;q=https%3A%2F%2Fexample.com%2Fpost%3Fid%3D1%26utm_campaign%3Dz&amp;usg=A",
+            "https://example.com/post?id=1",
+        ),
+        (
+            "https://l.facebook.com/l.php?u=https%3A%2F%2Fexample.com%2F&amp;h=AT0",
+            "https://example.com/",
+        ),
+        (
+            "https://eur01.safelinks.protection.outlook.com/?url=https%3A%2F%2Fexample.com%2Fdoc&amp;data=05",
+            "https://example.com/doc",
+        ),
+        // nested: a safelink around a Google redirect
+        (
+            "https://nam02.safelinks.protection.outlook.com/?url=https%3A%2F%2Fwww.google.com%2Furl%3Fq%3Dhttps%253A%252F%252Fexample.com%252Fx",
+            "https://example.com/x",
+        ),
+        // only http(s) targets are unwrapped
+        (
+            "https://www.google.com/url?q=javascript:alert(1)",
+            "https://www.google.com/url?q=javascript:alert(1)",
+        ),
+    ];
+    for (href, expected) in cases {
+        assert_eq!(link(href, &opts()), format!("[x]({expected})\n"), "{href}");
+    }
+}
+
+#[test]
+fn percent_encoding_normalised() {
+    assert_eq!(
+        link("HTTPS://Example.COM/%7euser/a%2fb%c3%a9?q=%41", &opts()),
+        "[x](https://example.com/~user/a%2Fb%C3%A9?q=A)\n"
+    );
+}
+
+#[test]
+fn images_and_other_schemes() {
+    let html = r#"<img src="https://cdn.example/a.png?utm_source=feed" alt="A">
+      <a href="mailto:a@example.com?subject=hi&amp;utm_source=x">mail</a>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts()),
+        "![A](https://cdn.example/a.png) [mail](mailto:a@example.com?subject=hi&utm_source=x)\n"
+    );
+}
+
+#[test]
+fn custom_params() {
+    let opts = opts().tracking_params(vec!["session*".into(), "src".into()]);
+    assert_eq!(
+        link("/a?sessionid=1&amp;src=tw&amp;utm_source=x", &opts),
+        "[x](/a?utm_source=x)\n"
+    );
+}
+
+#[test]
+fn reference_links_deduplicated() {
+    let html = r#"<a href="https://example.com/a?utm_source=1">one</a>
+      <a href="https://example.com/a?fbclid=2">two</a>"#;
+    assert_eq!(
+        html_to_markdown_with(html, &opts().link_style(LinkStyle::Reference)),
+        "[one][1] [two][1]\n\n[1]: https://example.com/a\n"
+    );
+}
+
+#[test]
+fn off_by_default() {
+    assert_eq!(
+        link(
+            "https://example.com/a?utm_source=x",
+            &ConversionOptions::default()
+        ),
+        "[x](https://example.com/a?utm_source=x)\n"
+    );
+}